@@ -2,6 +2,25 @@ use std::io::{Result, Write};
 
 const PROTO_DIR: &str = "doljabiproto";
 const OUT_DIR: &str = "src";
+/// 프로토콜 버전 파일. .proto 를 예전 클라이언트와 맞지 않게 바꾸면 1 올린다
+const VERSION_FILE: &str = "doljabiproto/PROTOCOL_VERSION";
+/// JSON 에서 문자열로 쓰는 64비트 필드 (proto3 JSON 매핑). 64비트 필드를 추가하면 여기에도 넣는다
+const U64_FIELDS: &[&str] = &[
+    ".badukboard.SeatPresence.grace_deadline_ms",
+    ".badukboard.GameSync.server_time_ms",
+    ".badukboard.GameSync.turn_started_at_ms",
+    ".common.ChatMessage.user_id",
+    ".common.ChatMessage.sent_at_ms",
+    ".common.Ping.sent_at_ms",
+    ".common.Pong.ping_sent_at_ms",
+    ".common.Pong.sent_at_ms",
+    ".common.Resync.from_seq",
+    ".common.ServerToClient.seq",
+];
+const REPEATED_U64_FIELDS: &[&str] = &[
+    ".badukboard.BadukBoardState.black",
+    ".badukboard.BadukBoardState.white",
+];
 
 fn main() -> Result<()> {
     // `GENERATE_PROTO` 환경 변수가 설정되어 있을 때만 .proto 파일을 컴파일합니다.
@@ -34,8 +53,30 @@ fn main() -> Result<()> {
             .collect();
 
         // prost로 .proto 파일 컴파일
-        prost_build::Config::new()
+        // serde feature 를 켜면 모든 메시지·enum·oneof 가 JSON 으로도 오간다 (빠진 필드는 기본값)
+        let mut config = prost_build::Config::new();
+        for field in U64_FIELDS {
+            config.field_attribute(
+                field,
+                r#"#[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]"#,
+            );
+        }
+        for field in REPEATED_U64_FIELDS {
+            config.field_attribute(
+                field,
+                r#"#[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string_vec"))]"#,
+            );
+        }
+        config
             .out_dir(OUT_DIR)
+            .message_attribute(
+                ".",
+                r#"#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]"#,
+            )
+            .enum_attribute(
+                ".",
+                r#"#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]"#,
+            )
             .compile_protos(&proto_files, &[PROTO_DIR])?;
 
         // mod.rs 파일 생성
@@ -48,6 +89,16 @@ fn main() -> Result<()> {
         for module_name in &module_names {
             writeln!(lib_file, "pub mod {};", module_name)?;
         }
+        writeln!(lib_file, "\n#[cfg(feature = \"serde\")]")?;
+        writeln!(lib_file, "pub mod json64;")?;
+
+        // 프로토콜 버전 상수 (Hello·HelloAck 의 protocol_version)
+        let version: u32 = std::fs::read_to_string(VERSION_FILE)?
+            .trim()
+            .parse()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        writeln!(lib_file, "\n/// 프로토콜 버전 ({})", VERSION_FILE)?;
+        writeln!(lib_file, "pub const PROTOCOL_VERSION: u32 = {};", version)?;
 
         println!("cargo:rerun-if-changed={}/", PROTO_DIR);
         println!("Generated mod.rs with {} modules", module_names.len());
@@ -1,13 +1,17 @@
 // This file is @generated by prost-build.
 /// 바둑, 오목 비트보드를 그대로 직렬화한 상태
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BadukBoardState {
     #[prost(fixed64, repeated, tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string_vec"))]
     pub black: ::prost::alloc::vec::Vec<u64>,
     #[prost(fixed64, repeated, tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string_vec"))]
     pub white: ::prost::alloc::vec::Vec<u64>,
 }
 /// * 플레이어 시간 정보
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct PlayerTimeInfo {
     #[prost(uint32, tag = "1")]
@@ -20,6 +24,7 @@ pub struct PlayerTimeInfo {
     pub overtime: u32,
 }
 /// * 보드, 시간, 턴 정보
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BadukBoardData {
     #[prost(message, optional, tag = "1")]
@@ -28,27 +33,69 @@ pub struct BadukBoardData {
     pub black_time: ::core::option::Option<PlayerTimeInfo>,
     #[prost(message, optional, tag = "3")]
     pub white_time: ::core::option::Option<PlayerTimeInfo>,
+    /// * 마지막 수. 아직 둔 수가 없으면 없음
+    #[prost(message, optional, tag = "4")]
+    pub last_move: ::core::option::Option<MoveUpdate>,
+}
+/// * 마지막 수와 그 결과
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MoveUpdate {
+    #[prost(enumeration = "Color", tag = "1")]
+    pub color: i32,
+    /// * 정수 좌표 (y * 판 크기 + x). 없으면 패스
+    #[prost(uint32, optional, tag = "2")]
+    pub coordinate: ::core::option::Option<u32>,
+    /// * 이 수로 따낸 돌의 좌표
+    #[prost(uint32, repeated, tag = "3")]
+    pub captured: ::prost::alloc::vec::Vec<u32>,
+    /// * 몇 번째 수인지 (1부터, 패스 포함)
+    #[prost(uint32, tag = "4")]
+    pub move_number: u32,
+    /// * 흑이 따낸 백돌 수 (누적)
+    #[prost(uint32, tag = "5")]
+    pub black_caught_stone: u32,
+    /// * 백이 따낸 흑돌 수 (누적)
+    #[prost(uint32, tag = "6")]
+    pub white_caught_stone: u32,
+    /// * 패 때문에 지금 둘 수 없는 자리. 없으면 패 아님
+    #[prost(uint32, optional, tag = "7")]
+    pub ko_point: ::core::option::Option<u32>,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ChaksuRequest {
     #[prost(uint32, tag = "1")]
     pub coordinate: u32,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct DrawOfferRequest {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ResignRequest {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct PassTurnRequest {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct GameStartRequest {}
+/// * 상대가 접속 유예 시간이 지나도록 돌아오지 않았을 때 승리 요청
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ClaimWinRequest {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BadukBoardClient {
-    #[prost(oneof = "baduk_board_client::Payload", tags = "100, 101, 102, 103, 104")]
+    #[prost(
+        oneof = "baduk_board_client::Payload",
+        tags = "100, 101, 102, 103, 104, 105"
+    )]
     pub payload: ::core::option::Option<baduk_board_client::Payload>,
 }
 /// Nested message and enum types in `BadukBoardClient`.
 pub mod baduk_board_client {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Oneof)]
     pub enum Payload {
         #[prost(message, tag = "100")]
@@ -61,8 +108,11 @@ pub mod baduk_board_client {
         PassTurn(super::PassTurnRequest),
         #[prost(message, tag = "104")]
         Gamestart(super::GameStartRequest),
+        #[prost(message, tag = "105")]
+        ClaimWin(super::ClaimWinRequest),
     }
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UserInfo {
     #[prost(string, tag = "1")]
@@ -70,6 +120,7 @@ pub struct UserInfo {
     #[prost(uint32, tag = "2")]
     pub rating: u32,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UsersInfo {
     #[prost(message, optional, tag = "1")]
@@ -77,20 +128,109 @@ pub struct UsersInfo {
     #[prost(message, optional, tag = "2")]
     pub white: ::core::option::Option<UserInfo>,
 }
+/// * 대국 종료 시 레이팅 변화
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RatingChange {
+    #[prost(int32, tag = "1")]
+    pub before: i32,
+    #[prost(int32, tag = "2")]
+    pub after: i32,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RatingResult {
+    #[prost(message, optional, tag = "1")]
+    pub black: ::core::option::Option<RatingChange>,
+    #[prost(message, optional, tag = "2")]
+    pub white: ::core::option::Option<RatingChange>,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ChaksuResponse {
     #[prost(bool, tag = "1")]
     pub success: bool,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ResignResponse {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct DrawOfferResponse {
     #[prost(string, tag = "1")]
     pub user_name: ::prost::alloc::string::String,
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct PassTurnResponse {}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ClaimWinResponse {}
+/// * 자리에 앉은 플레이어의 접속 상태
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SeatPresence {
+    #[prost(bool, tag = "1")]
+    pub black_connected: bool,
+    #[prost(bool, tag = "2")]
+    pub white_connected: bool,
+    /// * 접속이 끊긴 플레이어의 유예 시간이 끝나는 서버 시각 (Unix ms). 0 이면 유예 중이 아님
+    #[prost(uint64, tag = "3")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub grace_deadline_ms: u64,
+    /// * 유예 시간이 지나 접속해 있는 플레이어가 승리를 요청할 수 있으면 true
+    #[prost(bool, tag = "4")]
+    pub claimable: bool,
+}
+/// * 기보의 한 수
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MoveRecord {
+    #[prost(enumeration = "Color", tag = "1")]
+    pub color: i32,
+    /// * 정수 좌표 (y * 판 크기 + x). 없으면 패스
+    #[prost(uint32, optional, tag = "2")]
+    pub coordinate: ::core::option::Option<u32>,
+    /// * 이 수를 둔 직후 남은 시간(ms). 초읽기 중이면 한 번의 초읽기 시간
+    #[prost(uint32, optional, tag = "3")]
+    pub time_left_ms: ::core::option::Option<u32>,
+    /// * 초읽기 중일 때 남은 초읽기 횟수
+    #[prost(uint32, optional, tag = "4")]
+    pub periods_left: ::core::option::Option<u32>,
+}
+/// * 입장(재접속) 시 보내는 전체 국면
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GameSync {
+    #[prost(enumeration = "GamePhase", tag = "1")]
+    pub phase: i32,
+    /// * 판과 두 플레이어의 시간 (차례 시작 시점 기준)
+    #[prost(message, optional, tag = "2")]
+    pub state: ::core::option::Option<BadukBoardData>,
+    /// * 첫 수부터 지금까지의 수순
+    #[prost(message, repeated, tag = "3")]
+    pub moves: ::prost::alloc::vec::Vec<MoveRecord>,
+    /// * 흑이 따낸 백돌 수
+    #[prost(uint32, tag = "4")]
+    pub black_captures: u32,
+    /// * 백이 따낸 흑돌 수
+    #[prost(uint32, tag = "5")]
+    pub white_captures: u32,
+    /// * 이 메시지를 만든 서버 시각 (Unix ms)
+    #[prost(uint64, tag = "6")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub server_time_ms: u64,
+    /// * 지금 차례인 플레이어가 차례를 시작한 서버 시각 (Unix ms)
+    #[prost(uint64, tag = "7")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub turn_started_at_ms: u64,
+    /// * 무승부 신청 중인지
+    #[prost(bool, tag = "8")]
+    pub black_draw_offer: bool,
+    #[prost(bool, tag = "9")]
+    pub white_draw_offer: bool,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct BadukBoardServer {
     #[prost(enumeration = "Color", tag = "1")]
@@ -101,11 +241,25 @@ pub struct BadukBoardServer {
     pub game_state: ::core::option::Option<BadukBoardData>,
     #[prost(message, optional, tag = "5")]
     pub users_info: ::core::option::Option<UsersInfo>,
-    #[prost(oneof = "baduk_board_server::Payload", tags = "100, 101, 102, 103")]
+    #[prost(message, optional, tag = "6")]
+    pub rating_result: ::core::option::Option<RatingResult>,
+    /// * 현재 관전자 수
+    #[prost(uint32, tag = "7")]
+    pub spectators: u32,
+    /// * 관전 딜레이(수). 0 이 아니면 이 메시지의 판은 그만큼 전 국면
+    #[prost(uint32, tag = "8")]
+    pub spectator_delay: u32,
+    #[prost(message, optional, tag = "9")]
+    pub presence: ::core::option::Option<SeatPresence>,
+    #[prost(
+        oneof = "baduk_board_server::Payload",
+        tags = "100, 101, 102, 103, 104, 105"
+    )]
     pub payload: ::core::option::Option<baduk_board_server::Payload>,
 }
 /// Nested message and enum types in `BadukBoardServer`.
 pub mod baduk_board_server {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
     pub enum Payload {
         #[prost(message, tag = "100")]
@@ -116,9 +270,14 @@ pub mod baduk_board_server {
         DrawOffer(super::DrawOfferResponse),
         #[prost(message, tag = "103")]
         PassTurn(super::PassTurnResponse),
+        #[prost(message, tag = "104")]
+        Sync(super::GameSync),
+        #[prost(message, tag = "105")]
+        ClaimWin(super::ClaimWinResponse),
     }
 }
 /// * 바둑판 턴 정보
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum Color {
@@ -151,3 +310,34 @@ impl Color {
         }
     }
 }
+/// * 대국 단계
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum GamePhase {
+    Waiting = 0,
+    Playing = 1,
+    Finished = 2,
+}
+impl GamePhase {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Waiting => "GAME_PHASE_WAITING",
+            Self::Playing => "GAME_PHASE_PLAYING",
+            Self::Finished => "GAME_PHASE_FINISHED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "GAME_PHASE_WAITING" => Some(Self::Waiting),
+            "GAME_PHASE_PLAYING" => Some(Self::Playing),
+            "GAME_PHASE_FINISHED" => Some(Self::Finished),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,109 @@
 // This file is @generated by prost-build.
+/// * 요청을 보낸 연결에만 보내는 에러
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Error {
+    #[prost(enumeration = "ErrorCode", tag = "1")]
+    pub code: i32,
+    /// * 사람이 읽을 수 있는 설명
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+}
+/// * 채팅 보내기. 채널은 보낸 사람의 자리(플레이어·관전자)로 정해진다
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ChatRequest {
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ChatMessage {
+    #[prost(enumeration = "ChatChannel", tag = "1")]
+    pub channel: i32,
+    #[prost(uint64, tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub user_id: u64,
+    #[prost(string, tag = "3")]
+    pub user_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub text: ::prost::alloc::string::String,
+    /// * 보낸 시점까지 둔 수 (기보 C\[\] 를 붙이는 수)
+    #[prost(uint32, tag = "5")]
+    pub move_number: u32,
+    /// * 보낸 시각 (Unix ms)
+    #[prost(uint64, tag = "6")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub sent_at_ms: u64,
+}
+/// * 연결 확인·시계 맞춤. 받은 쪽은 Pong 으로 sent_at_ms 를 그대로 돌려준다
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Ping {
+    /// * 보낸 쪽 시각 (Unix ms)
+    #[prost(uint64, tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub sent_at_ms: u64,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Pong {
+    /// * 받은 Ping 의 sent_at_ms
+    #[prost(uint64, tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub ping_sent_at_ms: u64,
+    /// * Pong 을 보낸 쪽 시각 (Unix ms)
+    #[prost(uint64, tag = "2")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub sent_at_ms: u64,
+    /// * 서버가 잰 이 연결의 왕복 지연(ms). 서버가 보낼 때만
+    #[prost(uint32, tag = "3")]
+    pub rtt_ms: u32,
+}
+/// * 놓친 방 메시지를 다시 요청. from_seq 다음 메시지부터 다시 받는다
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Resync {
+    /// * 마지막으로 받은 방 메시지의 seq. 0 이면 처음부터(남아 있는 만큼)
+    #[prost(uint64, tag = "1")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub from_seq: u64,
+}
+/// *
+/// 연결 직후 클라이언트가 보내는 첫 메시지. 보내지 않는 예전 클라이언트는 버전 1, 기능 없음으로 본다.
+/// 버전은 doljabiproto/PROTOCOL_VERSION (생성된 crate 의 PROTOCOL_VERSION)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Hello {
+    #[prost(uint32, tag = "1")]
+    pub protocol_version: u32,
+    /// * 클라이언트가 쓸 수 있는 기능 ("delta", "resync", "chat")
+    #[prost(string, repeated, tag = "2")]
+    pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// * 비공개 방 비밀번호. URL 에 싣지 않도록 첫 메시지로 보낸다
+    #[prost(string, optional, tag = "3")]
+    pub password: ::core::option::Option<::prost::alloc::string::String>,
+    /// * 비공개 방 초대 토큰
+    #[prost(string, optional, tag = "4")]
+    pub invite_token: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// * Hello 에 대한 답. accepted 가 false 면 에러를 보내고 연결을 끊는다
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct HelloAck {
+    /// * 서버 프로토콜 버전
+    #[prost(uint32, tag = "1")]
+    pub protocol_version: u32,
+    /// * 서버가 받는 가장 낮은 버전
+    #[prost(uint32, tag = "2")]
+    pub min_protocol_version: u32,
+    /// * 이 연결에서 쓰기로 한 기능 (클라이언트가 보낸 것 중 서버가 아는 것)
+    #[prost(string, repeated, tag = "3")]
+    pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bool, tag = "4")]
+    pub accepted: bool,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ServerToClient {
     #[prost(bool, tag = "1")]
@@ -7,34 +112,78 @@ pub struct ServerToClient {
     pub running: ::core::option::Option<bool>,
     #[prost(enumeration = "GameType", tag = "3")]
     pub game_type: i32,
-    #[prost(oneof = "server_to_client::GameData", tags = "101, 102")]
+    /// * 방 안에서 1씩 늘어나는 메시지 순번. 0 이면 이 연결에만 보낸 순번 없는 메시지(Ping·Pong 등)
+    #[prost(uint64, tag = "4")]
+    #[cfg_attr(feature = "serde", serde(with = "crate::json64::u64_string"))]
+    pub seq: u64,
+    #[prost(
+        oneof = "server_to_client::GameData",
+        tags = "101, 102, 201, 202, 203, 204, 205"
+    )]
     pub game_data: ::core::option::Option<server_to_client::GameData>,
 }
 /// Nested message and enum types in `ServerToClient`.
 pub mod server_to_client {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
     pub enum GameData {
         #[prost(message, tag = "101")]
         Baduk(super::super::badukboard::BadukBoardServer),
         #[prost(message, tag = "102")]
         Omok(super::super::badukboard::BadukBoardServer),
+        /// * 서버가 주기적으로 보내는 연결 확인. 클라이언트는 Pong 으로 답한다
+        #[prost(message, tag = "201")]
+        Ping(super::Ping),
+        /// * 클라이언트 Ping 에 대한 답 (서버 시각·왕복 지연)
+        #[prost(message, tag = "202")]
+        Pong(super::Pong),
+        /// * 요청 거절 (요청을 보낸 연결에만)
+        #[prost(message, tag = "203")]
+        Error(super::Error),
+        /// * 같은 채널(플레이어·관전자)의 채팅
+        #[prost(message, tag = "204")]
+        Chat(super::ChatMessage),
+        /// * Hello 에 대한 답
+        #[prost(message, tag = "205")]
+        Hello(super::HelloAck),
     }
 }
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ClientToServer {
-    #[prost(oneof = "client_to_server::GameData", tags = "101, 102")]
+    #[prost(
+        oneof = "client_to_server::GameData",
+        tags = "101, 102, 201, 202, 203, 204, 205"
+    )]
     pub game_data: ::core::option::Option<client_to_server::GameData>,
 }
 /// Nested message and enum types in `ClientToServer`.
 pub mod client_to_server {
-    #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Oneof)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
     pub enum GameData {
         #[prost(message, tag = "101")]
         Baduk(super::super::badukboard::BadukBoardClient),
         #[prost(message, tag = "102")]
         Omok(super::super::badukboard::BadukBoardClient),
+        /// * 서버 시각을 묻는 연결 확인
+        #[prost(message, tag = "201")]
+        Ping(super::Ping),
+        /// * 서버 Ping 에 대한 답
+        #[prost(message, tag = "202")]
+        Pong(super::Pong),
+        /// * 놓친 방 메시지 다시 받기
+        #[prost(message, tag = "203")]
+        Resync(super::Resync),
+        /// * 채팅
+        #[prost(message, tag = "204")]
+        Chat(super::ChatRequest),
+        /// * 연결 직후 버전·기능 알리기 (첫 메시지로만)
+        #[prost(message, tag = "205")]
+        Hello(super::Hello),
     }
 }
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum GameType {
@@ -64,3 +213,96 @@ impl GameType {
         }
     }
 }
+/// * 요청이 거절된 이유
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ErrorCode {
+    Unspecified = 0,
+    /// * 내 차례가 아님
+    NotYourTurn = 1,
+    /// * 이미 돌이 있는 자리
+    Occupied = 2,
+    /// * 금수 (자충수, 오목 금수)
+    Forbidden = 3,
+    /// * 패 (바로 되따내기·반복수)
+    Ko = 4,
+    /// * 대국 중이 아님 (시작 전·종료 후)
+    GameNotStarted = 5,
+    /// * 자리에 앉지 않은 유저(관전자)의 요청
+    NotSeated = 6,
+    /// * 요청이 너무 잦음
+    RateLimited = 7,
+    /// * 알 수 없거나 지금 할 수 없는 요청 (판 밖 좌표 등)
+    InvalidRequest = 8,
+    /// * 서버가 지원하지 않는 프로토콜 버전 (Hello)
+    UnsupportedVersion = 9,
+    /// * 비공개 방: 비밀번호·초대 토큰이 없거나 틀림 (틀린 시도가 많으면 잠시 모두 거절)
+    AccessDenied = 10,
+}
+impl ErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "ERROR_CODE_UNSPECIFIED",
+            Self::NotYourTurn => "ERROR_CODE_NOT_YOUR_TURN",
+            Self::Occupied => "ERROR_CODE_OCCUPIED",
+            Self::Forbidden => "ERROR_CODE_FORBIDDEN",
+            Self::Ko => "ERROR_CODE_KO",
+            Self::GameNotStarted => "ERROR_CODE_GAME_NOT_STARTED",
+            Self::NotSeated => "ERROR_CODE_NOT_SEATED",
+            Self::RateLimited => "ERROR_CODE_RATE_LIMITED",
+            Self::InvalidRequest => "ERROR_CODE_INVALID_REQUEST",
+            Self::UnsupportedVersion => "ERROR_CODE_UNSUPPORTED_VERSION",
+            Self::AccessDenied => "ERROR_CODE_ACCESS_DENIED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ERROR_CODE_UNSPECIFIED" => Some(Self::Unspecified),
+            "ERROR_CODE_NOT_YOUR_TURN" => Some(Self::NotYourTurn),
+            "ERROR_CODE_OCCUPIED" => Some(Self::Occupied),
+            "ERROR_CODE_FORBIDDEN" => Some(Self::Forbidden),
+            "ERROR_CODE_KO" => Some(Self::Ko),
+            "ERROR_CODE_GAME_NOT_STARTED" => Some(Self::GameNotStarted),
+            "ERROR_CODE_NOT_SEATED" => Some(Self::NotSeated),
+            "ERROR_CODE_RATE_LIMITED" => Some(Self::RateLimited),
+            "ERROR_CODE_INVALID_REQUEST" => Some(Self::InvalidRequest),
+            "ERROR_CODE_UNSUPPORTED_VERSION" => Some(Self::UnsupportedVersion),
+            "ERROR_CODE_ACCESS_DENIED" => Some(Self::AccessDenied),
+            _ => None,
+        }
+    }
+}
+/// * 채팅 채널. 플레이어와 관전자의 채팅은 서로 보이지 않는다
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChatChannel {
+    Players = 0,
+    Spectators = 1,
+}
+impl ChatChannel {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Players => "CHAT_CHANNEL_PLAYERS",
+            Self::Spectators => "CHAT_CHANNEL_SPECTATORS",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CHAT_CHANNEL_PLAYERS" => Some(Self::Players),
+            "CHAT_CHANNEL_SPECTATORS" => Some(Self::Spectators),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! proto3 JSON 매핑: 64비트 정수(uint64·fixed64)는 JavaScript 숫자로 정확히 담을 수 없어서
+//! 10진수 문자열로 쓴다. 읽을 때는 문자열과 숫자를 모두 받는다.
+//!
+//! build.rs 가 64비트 필드마다 `serde(with = ...)` 로 붙인다.
+
+use serde::{Deserialize, Deserializer, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum U64Json {
+    String(String),
+    Number(u64),
+}
+
+impl U64Json {
+    fn value<E: serde::de::Error>(self) -> Result<u64, E> {
+        match self {
+            U64Json::String(s) => s.parse().map_err(E::custom),
+            U64Json::Number(n) => Ok(n),
+        }
+    }
+}
+
+/// `uint64`, `fixed64` 필드
+pub mod u64_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        U64Json::deserialize(deserializer)?.value()
+    }
+}
+
+/// `repeated uint64`, `repeated fixed64` 필드
+pub mod u64_string_vec {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(values: &[u64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(u64::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u64>, D::Error> {
+        Vec::<U64Json>::deserialize(deserializer)?
+            .into_iter()
+            .map(U64Json::value)
+            .collect()
+    }
+}
@@ -3,3 +3,9 @@
 
 pub mod badukboard;
 pub mod common;
+
+#[cfg(feature = "serde")]
+pub mod json64;
+
+/// 프로토콜 버전 (doljabiproto/PROTOCOL_VERSION)
+pub const PROTOCOL_VERSION: u32 = 2;
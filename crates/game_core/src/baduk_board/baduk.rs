@@ -15,6 +15,8 @@ pub struct Baduk {
 
     // 패 해소를 위한 패 위치 저장 (정확히 1개의 돌을 잡았을 때 발생)
     ko_position: Option<u16>,
+    // 마지막 착수로 따낸 돌 좌표
+    last_captured: Vec<u16>,
     pub winner: Option<Color>,
 }
 
@@ -38,6 +40,7 @@ impl Baduk {
 
             previous_board_state: None,
             ko_position: None,
+            last_captured: Vec::new(),
 
             winner: None,
         }
@@ -178,11 +181,12 @@ impl Baduk {
         }
     }
 
+    /// 착수 후 따낼 돌을 따내고 자살수를 검사. 성공하면 따낸 돌 좌표를 돌려준다
     pub fn resolve_after_move(
         &mut self,
         coordinate: u16,
         color: Color,
-    ) -> Result<(), BadukBoardError> {
+    ) -> Result<Vec<u16>, BadukBoardError> {
         let opponent_color = match color {
             Color::Black => Color::White,
             Color::White => Color::Black,
@@ -191,8 +195,7 @@ impl Baduk {
 
         let mut checked_opponent = HashSet::<u16>::new();
         let mut captured_any = false;
-        let mut captured_single_stone_position: Option<u16> = None;
-        let mut total_captured_stones = 0;
+        let mut captured = Vec::<u16>::new();
 
         for neighbor in self.adjacent_coordinates(coordinate) {
             if self.board.is_color(neighbor) != opponent_color {
@@ -211,29 +214,12 @@ impl Baduk {
                 // 제거 전에 linked_stone_set에서도 삭제
                 self.remove_from_linked_stone_set(&opponent_group, opponent_color);
 
-                // 패 위치 감지: 정확히 1개의 돌을 잡았을 때만 패 발생
-                if opponent_group.len() == 1 {
-                    captured_single_stone_position = opponent_group.iter().next().copied();
-                    total_captured_stones = 1;
-                } else {
-                    // 여러 개의 돌을 잡았거나, 이미 다른 돌을 잡았으면 패가 아님
-                    captured_single_stone_position = None;
-                    total_captured_stones += opponent_group.len();
-                }
-
+                captured.extend(&opponent_group);
                 self.remove_group(&opponent_group, opponent_color);
                 captured_any = true;
             }
         }
 
-        // 패 위치 설정: 정확히 1개의 돌만 잡았을 때 패 발생
-        if total_captured_stones == 1 {
-            self.ko_position = captured_single_stone_position;
-        } else {
-            // 여러 개의 돌을 잡았거나 잡지 않았으면 패가 아님
-            self.ko_position = None;
-        }
-
         let (own_group, own_liberties) = self.collect_group_state(coordinate, color);
 
         // 중국 규칙 자살수 검사: 상대 돌을 잡을 수 있으면 자살수 허용
@@ -247,10 +233,18 @@ impl Baduk {
             return Err(BadukBoardError::BannedChaksu);
         }
 
+        // 패 위치 설정: 정확히 1개의 돌을 잡았고, 둔 돌이 혼자서 잡은 자리 하나만 활로로 가질 때 패 발생
+        // 여러 개의 돌을 잡았거나 잡지 않았으면 패가 아님 (다른 곳에 두면 패 해소)
+        self.ko_position = match captured.as_slice() {
+            [position] if own_group.len() == 1 && own_liberties.len() == 1 => Some(*position),
+            _ => None,
+        };
+
         // 자신의 그룹을 linked_stone_set에 저장
         self.update_linked_stone_set(&own_group, color);
 
-        Ok(())
+        captured.sort_unstable();
+        Ok(captured)
     }
 
     /// 빈 공간 그룹을 찾고, 그 경계를 확인하여 집을 판별
@@ -349,6 +343,11 @@ impl Baduk {
         self.ko_position
     }
 
+    /// 마지막 착수로 따낸 돌 좌표
+    pub fn last_captured(&self) -> &[u16] {
+        &self.last_captured
+    }
+
     /// 착수 시도 실패 시 Err 출력
     /// 중국 규칙 적용: 자살수는 상대 돌을 잡을 수 있으면 허용, 반복수는 금지, 패 해소 지원
     pub fn chaksu(&mut self, coordinate: u16) -> Result<(), BadukBoardError> {
@@ -366,7 +365,7 @@ impl Baduk {
                 // 패 위치에 돌을 두려고 함 - 패 해소가 되었는지 확인
                 // 패 해소는 상대방이 다른 곳에 수를 두면 자동으로 해소됨
                 // 하지만 여전히 패 위치에 바로 두는 것은 금지 (반복수)
-                return Err(BadukBoardError::BannedChaksu);
+                return Err(BadukBoardError::Ko);
             }
         }
 
@@ -374,10 +373,8 @@ impl Baduk {
         self.board.push_stone(coordinate, color);
 
         // 자살수 및 기타 규칙 검사 (돌을 놓은 상태에서 검사)
-        if let Err(err) = self.resolve_after_move(coordinate, color) {
-            // 에러 발생 시 돌 제거는 resolve_after_move에서 처리됨
-            return Err(err);
-        }
+        // 에러 발생 시 돌 제거는 resolve_after_move에서 처리됨
+        let captured = self.resolve_after_move(coordinate, color)?;
 
         // 착수 후 보드 상태 확인 (반복수 검사용)
         let after_state = self.get_board_state();
@@ -390,26 +387,24 @@ impl Baduk {
             // resolve_after_move에서 추가된 linked_stone_set도 정리 필요
             let (own_group, _) = self.collect_group_state(coordinate, color);
             self.remove_from_linked_stone_set(&own_group, color);
-            return Err(BadukBoardError::BannedChaksu);
-        }
-
-        // 패 해소: 패 위치가 아닌 곳에 수를 두면 패 해소
-        // 패 해소는 착수 성공 후에 확인 (다른 곳에 수를 두면 패 해소)
-        if let Some(ko_pos) = self.ko_position {
-            if coordinate != ko_pos {
-                // 패 위치가 아닌 곳에 수를 두면 패 해소
-                self.ko_position = None;
-            }
-            // 패 위치에 수를 두려고 했지만 위에서 이미 검사했으므로 여기서는 처리하지 않음
+            return Err(BadukBoardError::Ko);
         }
 
         // 착수 성공 시 현재 보드 상태를 다음 턴의 이전 상태로 저장
         self.previous_board_state = Some(after_state);
+        self.last_captured = captured;
 
         self.board.switch_turn();
         Ok(())
     }
 
+    /// 착수 없이 차례 넘김. 패와 마지막으로 따낸 돌은 이 수로 풀린다
+    pub fn pass(&mut self) {
+        self.ko_position = None;
+        self.last_captured.clear();
+        self.board.switch_turn();
+    }
+
     // 집 판별(살아있는 집) - identify_territory, calculate_territory, calculate_score 함수로 구현됨
 
     /// 집 수 계산 및 계가
@@ -435,4 +430,79 @@ impl Baduk {
     }
 }
 
-// TODO: 수 넘김 처리
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xy(x: u16, y: u16) -> u16 {
+        y * board_size(BoardType::Baduk) + x
+    }
+
+    fn play(game: &mut Baduk, moves: &[(u16, u16)]) {
+        for &(x, y) in moves {
+            game.chaksu(xy(x, y)).unwrap();
+        }
+    }
+
+    #[test]
+    fn resolve_after_move_captured_test() {
+        let mut game = Baduk::new();
+        // 백 두 점을 흑이 에워싸고 마지막 활로에 둔다
+        game.board.push_stone(xy(0, 0), Color::White);
+        game.board.push_stone(xy(1, 0), Color::White);
+        game.board.push_stone(xy(0, 1), Color::Black);
+        game.board.push_stone(xy(1, 1), Color::Black);
+        game.board.push_stone(xy(2, 0), Color::Black);
+
+        let captured = game.resolve_after_move(xy(2, 0), Color::Black).unwrap();
+        assert_eq!(captured, vec![xy(0, 0), xy(1, 0)]);
+        assert_eq!(game.get_captured_stones(), (2, 0));
+        assert!(game.board.is_free(xy(0, 0)) && game.board.is_free(xy(1, 0)));
+        // 두 점을 따내면 패가 아니다
+        assert_eq!(game.get_ko_position(), None);
+
+        // 아무것도 따내지 못한 수
+        game.board.push_stone(xy(10, 10), Color::White);
+        let captured = game.resolve_after_move(xy(10, 10), Color::White).unwrap();
+        assert!(captured.is_empty());
+    }
+
+    #[test]
+    fn ko_test() {
+        let mut game = Baduk::new();
+        // . 흑 백 .
+        // 흑 백 . 백
+        // . 흑 백 .
+        play(
+            &mut game,
+            &[
+                (1, 0),
+                (2, 0),
+                (0, 1),
+                (3, 1),
+                (1, 2),
+                (2, 2),
+                (10, 10),
+                (1, 1),
+            ],
+        );
+        play(&mut game, &[(2, 1)]);
+        assert_eq!(game.last_captured(), &[xy(1, 1)]);
+        assert_eq!(game.get_ko_position(), Some(xy(1, 1)));
+
+        // 따낸 자리에 바로 되따낼 수 없다 (차례도 그대로)
+        assert_eq!(game.chaksu(xy(1, 1)), Err(BadukBoardError::Ko));
+        assert_eq!(game.board.is_turn(), Color::White);
+        assert!(game.board.is_free(xy(1, 1)));
+
+        // 패스하면 패가 풀린다
+        game.pass();
+        assert_eq!(game.get_ko_position(), None);
+        assert!(game.last_captured().is_empty());
+        assert_eq!(game.board.is_turn(), Color::Black);
+
+        // 다른 곳에 두고 나면 되따낼 수 있다
+        play(&mut game, &[(15, 15), (1, 1)]);
+        assert_eq!(game.last_captured(), &[xy(2, 1)]);
+    }
+}
@@ -23,6 +23,8 @@ pub enum BadukBoardError {
     OutOfBoard,
     OverLap,
     BannedChaksu,
+    /// 패: 따낸 자리에 바로 되따내기, 또는 직전 국면으로 되돌아가는 반복수
+    Ko,
     InvalidArgument,
 }
 
@@ -233,6 +235,18 @@ impl Player {
         );
     }
 
+    // 이번 차례를 시작한 뒤 지난 시간
+    pub fn turn_elapsed(&self) -> std::time::Duration {
+        tokio::time::Instant::now() - self.turn_start_time
+    }
+
+    // 네트워크 지연만큼 메인 시간을 돌려줌 (초읽기 중에는 돌려주지 않음)
+    pub fn credit_lag(&mut self, lag_ms: u32) {
+        if self.main_time > 0 {
+            self.main_time += lag_ms;
+        }
+    }
+
     pub fn sub_remain_overtime(&mut self) {
         self.remaining_overtime -= 1;
     }
@@ -476,7 +490,7 @@ impl Players {
     }
 }
 
-#[derive(Deserialize, Serialize, ToSchema, Clone, Copy)]
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug)]
 pub struct BadukBoardGameConfig {
     main_time: u32,
     fischer_time: u32,
@@ -520,3 +534,22 @@ impl BadukBoardGameConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credit_lag_test() {
+        let mut player = Player::new(UserID(1));
+        player.set_player(BadukBoardGameConfig::new(60_000, 0, 3, 30_000));
+        player.credit_lag(300);
+        assert_eq!(player.main_time(), 60_300);
+
+        // 초읽기 중에는 돌려주지 않는다
+        player.set_player(BadukBoardGameConfig::new(0, 0, 2, 30_000));
+        player.credit_lag(300);
+        assert_eq!(player.main_time(), 0);
+        assert_eq!(player.overtime(), 30_000);
+    }
+}
@@ -1,9 +1,20 @@
 // 실행 방법: cargo run --bin main
 use axum::Router;
 use game_server::{
-    game_logic::{RoomManagement, create_room_router, timer::ServerTimer},
-    network::socket::web_socket_upgrade_router,
-    soyul::{game_record::sgf_router, session::SessionStore, soyul_login::login_router},
+    game_logic::{
+        RoomManagement, create_room_router,
+        lobby::lobby_router,
+        matchmaking::{MatchmakingManagement, matchmaking_router},
+        timer::ServerTimer,
+    },
+    network::{
+        bot_api::{BotApi, bot_router},
+        socket::web_socket_upgrade_router,
+    },
+    soyul::{
+        game_record::sgf_router, leaderboard::leaderboard_router, session::SessionStore,
+        soyul_db::init_schema, soyul_login::login_router,
+    },
     utility::admin_page::admin_page_router,
 };
 use std::{fs, sync::Arc};
@@ -31,9 +42,16 @@ fn add_openapi_info(openapi_doc: &mut OpenApi) {
 
 #[tokio::main]
 async fn main() {
+    // DB 스키마 준비 (조회 요청마다 DDL 을 돌리지 않도록 시작할 때 한 번)
+    let conn = rusqlite::Connection::open("mydb.db").expect("Failed to open database");
+    init_schema(&conn).expect("Failed to initialize database schema");
+    drop(conn);
+
     let session_manager = SessionStore::default();
     let room_manager = Arc::new(Mutex::new(RoomManagement::new()));
+    let lobby = room_manager.lock().await.lobby();
     let timer_manager = ServerTimer::run().await;
+    let matchmaker = MatchmakingManagement::run(room_manager.clone(), timer_manager.clone());
 
     // 최종 라우터 생성
     let router_list = OpenApiRouter::new()
@@ -42,7 +60,15 @@ async fn main() {
         .merge(
             web_socket_upgrade_router().with_state((room_manager.clone(), session_manager.clone())),
         )
+        .merge(lobby_router().with_state(lobby))
+        .merge(matchmaking_router().with_state((matchmaker, session_manager.clone())))
         .merge(sgf_router().with_state(session_manager.clone()))
+        .merge(leaderboard_router().with_state(session_manager.clone()))
+        .merge(bot_router().with_state(BotApi::new(
+            room_manager.clone(),
+            timer_manager.clone(),
+            session_manager.clone(),
+        )))
         .merge(admin_page_router());
 
     // openapi 명세와 라우터 분리
@@ -6,25 +6,13 @@ fn main() -> Result<()> {
     let mut conn = Connection::open("mydb.db")?;
 
     // 2. 일단 테이블이 아예 없다면 최신 스키마로 생성 (기존 데이터가 없는 경우)
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS users (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            login_id TEXT UNIQUE NOT NULL,
-            password_hash TEXT NOT NULL,
-            username TEXT UNIQUE,
-            rating INTEGER DEFAULT 1500,
-            win INTEGER DEFAULT 0,
-            lose INTEGER DEFAULT 0,
-            draw INTEGER DEFAULT 0
-        )",
-        [],
-    )?;
+    game_server::soyul::soyul_db::init_users_table(&conn)?;
 
     // 3. 스키마 동기화 실행
     sync_users_schema(&mut conn)?;
 
-    // 4. games 테이블 생성 (없으면) + 조회용 인덱스 (soyul_db와 동일 정의 재사용)
-    game_server::soyul::soyul_db::init_games_table(&conn)?;
+    // 4. games·전적 테이블 생성 (없으면) + 조회용 인덱스 (서버 시작 때와 같은 정의 재사용)
+    game_server::soyul::soyul_db::init_schema(&conn)?;
 
     println!("DB Schema synchronized successfully.");
     Ok(())
@@ -42,6 +30,7 @@ fn sync_users_schema(conn: &mut Connection) -> Result<()> {
     target_schema.insert("win".to_string(), "INTEGER DEFAULT 0");
     target_schema.insert("lose".to_string(), "INTEGER DEFAULT 0");
     target_schema.insert("draw".to_string(), "INTEGER DEFAULT 0");
+    target_schema.insert("is_bot".to_string(), "INTEGER NOT NULL DEFAULT 0");
 
     // --- B. 현재 DB 스키마 확인 ---
     // stmt의 생명주기를 명시적으로 제한하기 위해 스코프 사용
@@ -1,17 +1,23 @@
 use crate::{
     game_logic::{
-        GameLogic, UserID,
-        baduk_board::{EndReason, color_i32, timeout_event::*},
+        Audience, ConnectionID, GameLogic, RoomSettings, UserID,
+        baduk_board::{EndReason, GraceOutcome, SeatConnections, color_i32, timeout_event::*},
+        chat::RoomChat,
+        lobby::RoomStatus,
         timer::GameInterrupter,
     },
-    soyul::kibo::SgfGame,
+    soyul::{kibo::SgfGame, rating::RatingResult},
 };
 use doljabiproto::{
-    badukboard::BadukBoardServer,
-    common::{ClientToServer, ServerToClient, server_to_client::GameData},
+    badukboard::{
+        BadukBoardServer, ClaimWinResponse, GameSync, SeatPresence, baduk_board_server::Payload,
+    },
+    common::{ClientToServer, ErrorCode, ServerToClient, server_to_client::GameData},
 };
 use game_core::baduk_board::{BadukBoardGameConfig, Color, Players, baduk::Baduk};
+use rusqlite::Connection;
 use std::{
+    collections::HashSet,
     sync::{Arc, atomic::AtomicU16},
     time::Duration,
 };
@@ -26,10 +32,34 @@ pub struct BadukRoom {
     interrupter: GameInterrupter,
     timeout_event: Arc<AtomicU16>,
     kibo: SgfGame,
+    /// 대국 시작 여부 (로비 표시용)
+    started: bool,
+    /// 자리에 앉지 못하고 들어온 유저 (관전자)
+    spectators: HashSet<UserID>,
+    /// 관전 딜레이(수). 0 이면 관전자도 실시간으로 본다
+    spectator_delay: u32,
+    /// 플레이어 연결과 대국 중 접속이 끊긴 플레이어의 자리 유지
+    connections: SeatConnections,
+    /// 레이팅 대국 여부. 친선 대국은 기보만 저장하고 레이팅·전적에 반영하지 않는다
+    rated: bool,
+    /// 대국이 끝나 레이팅이 반영되면 Some
+    rating_result: Option<RatingResult>,
+    /// end_game 을 이미 거쳤으면 true (레이팅·전적이 두 번 반영되지 않도록)
+    ended: bool,
+    /// 순번 없이 보낼 메시지: 특정 유저·연결, 채팅 (게임 노드가 take_direct 로 꺼내 간다)
+    direct: Vec<(Audience, ServerToClient)>,
+    /// 플레이어·관전자 채팅
+    chat: RoomChat,
 }
 impl BadukRoom {
-    pub fn new(game_config: BadukBoardGameConfig, game_event_manager: GameInterrupter) -> Self {
+    pub fn new(
+        game_config: BadukBoardGameConfig,
+        settings: RoomSettings,
+        game_event_manager: GameInterrupter,
+    ) -> Self {
         let timeout_event = game_event_manager.register(Duration::from_secs(30), BRACK_GAME);
+        let mut kibo = SgfGame::baduk();
+        kibo.set_time_limit(game_config);
         Self {
             game: Baduk::new(),
             game_config: game_config,
@@ -37,7 +67,16 @@ impl BadukRoom {
             pass_turn: false,
             interrupter: game_event_manager,
             timeout_event: timeout_event,
-            kibo: SgfGame::baduk(),
+            kibo,
+            started: false,
+            spectators: HashSet::new(),
+            spectator_delay: settings.spectator_delay,
+            connections: SeatConnections::new(settings.disconnect_grace, settings.lag_compensation),
+            rated: settings.rated,
+            rating_result: None,
+            ended: false,
+            direct: Vec::new(),
+            chat: RoomChat::default(),
         }
     }
 
@@ -63,140 +102,83 @@ impl BadukRoom {
     }
 
     fn end_game(&mut self, winner: Color, reason: EndReason) {
-        let result = super::sgf_result(winner, reason);
-        self.game.set_winner(winner);
-        self.record_winner(winner);
-        self.save_kibo(&result);
-        self.interrupter.game_closer();
+        let mut conn = super::open_game_db();
+        self.end_game_with(conn.as_mut(), winner, reason);
     }
 
-    /// 누적된 기보(SgfGame)에 결과·플레이어 이름을 채워 games 테이블에 저장
-    fn save_kibo(&mut self, result: &str) {
-        use crate::soyul::soyul_db::save_finished_game;
-        use crate::soyul::soyul_login::get_user_profile_by_id;
-        use rusqlite::Connection;
-
-        // 정상적으로 두 명이 플레이한 게임만 저장
-        let (black_id, white_id) = match (
-            self.players.black_player.as_ref().map(|p| p.user_id()),
-            self.players.white_player.as_ref().map(|p| p.user_id()),
-        ) {
-            (Some(b), Some(w)) => (b, w),
-            _ => return,
-        };
-
-        let conn = match Connection::open("mydb.db") {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("기보 저장 실패(DB 오픈): {}", e);
-                return;
-            }
-        };
-
-        let name_of = |id| {
-            get_user_profile_by_id(&conn, id)
-                .ok()
-                .flatten()
-                .and_then(|p| p.username)
-                .unwrap_or_default()
-        };
-        let black_name = name_of(black_id);
-        let white_name = name_of(white_id);
-
-        self.kibo.set_players(&black_name, &white_name);
-        self.kibo.set_result(result);
-
-        let sgf = self.kibo.to_sgf_string();
-        let board_size = self.kibo.board_size;
-
-        if let Err(e) = save_finished_game(
-            &conn,
-            i64::from(black_id),
-            i64::from(white_id),
-            "baduk",
-            board_size,
-            result,
-            &sgf,
-        ) {
-            eprintln!("기보 저장 실패(INSERT): {}", e);
-        } else {
-            #[cfg(debug_assertions)]
-            println!("✅ 기보 저장 성공: result={}", result);
+    /// 대국 종료 처리. DB 를 열지 못했으면 conn 이 None 이고, 저장 없이 대국만 끝낸다
+    fn end_game_with(&mut self, conn: Option<&mut Connection>, winner: Color, reason: EndReason) {
+        // 시간 초과와 기권이 겹치는 등 두 번 불려도 결과는 처음 한 번만 반영
+        if self.ended {
+            return;
         }
-    }
-
-    pub fn add_move(&mut self, color: Color, coordinate: u16) {
-        let size = self.game.board.is_boardsize();
-        self.kibo
-            .add_move(color, (coordinate % size) as u8, (coordinate / size) as u8);
-    }
-
-    pub fn user_info(&self, color: Color) -> Option<doljabiproto::badukboard::UserInfo> {
-        use crate::soyul::soyul_login::get_user_profile_by_id;
-        use rusqlite::Connection;
+        self.ended = true;
 
-        let conn = match Connection::open("mydb.db") {
-            Ok(conn) => conn,
-            Err(e) => {
-                eprintln!("game_user_info 출력 에러: DB 불러오기 실패: {}", e);
-                return None;
-            }
-        };
-
-        match color {
-            Color::Black => {
-                if let Some(player) = &self.players.black_player {
-                    if let Ok(Some(user_profile)) = get_user_profile_by_id(&conn, player.user_id())
-                    {
-                        Some(user_profile.convert_session2proto())
-                    } else {
-                        eprintln!("user_info: 존재하지 않는 유저 정보!");
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            Color::White => {
-                if let Some(player) = &self.players.white_player {
-                    if let Ok(Some(user_profile)) = get_user_profile_by_id(&conn, player.user_id())
-                    {
-                        Some(user_profile.convert_session2proto())
-                    } else {
-                        eprintln!("user_info: 존재하지 않는 유저 정보!");
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
+        self.game.set_winner(winner);
+        self.rating_result = super::finish_kibo(
+            conn,
+            &mut self.kibo,
+            &self.players,
+            self.chat.lines(),
+            self.rated,
+            winner,
+            reason,
+        );
+        self.interrupter.game_closer();
     }
 
     fn users_info(&self) -> doljabiproto::badukboard::UsersInfo {
-        doljabiproto::badukboard::UsersInfo {
-            black: self.user_info(Color::Black),
-            white: self.user_info(Color::White),
-        }
+        super::users_info(&self.players, &self.kibo)
     }
 
-    fn user_io(&self, result: bool) -> ServerToClient {
+    /// 입장·퇴장 알림. 대국 중이면 판·시간도 담아 새로 들어온 관전자가 바로 맞출 수 있게 한다
+    fn user_io(&self, result: bool, payload: Option<Payload>) -> ServerToClient {
         let running = if result { Some(false) } else { None };
+        let (turn, game_state) = match self.started {
+            true => (
+                color_i32(self.game.board.is_turn()),
+                Some(self.badukboard_status()),
+            ),
+            false => (doljabiproto::badukboard::Color::Free as i32, None),
+        };
         ServerToClient {
             response_type: result,
             running: running,
             game_type: GAME_TYPE_BADUK,
+            seq: 0,
             game_data: Some(GameData::Baduk(BadukBoardServer {
-                turn: doljabiproto::badukboard::Color::Free as i32,
-                the_winner: None,
-                game_state: None,
+                turn,
+                game_state,
                 users_info: Some(self.users_info()),
-                payload: None,
+                presence: Some(self.seat_presence()),
+                payload,
+                ..self.board_server()
             })),
         }
     }
 
+    fn seat_presence(&self) -> SeatPresence {
+        self.connections.presence(&self.players)
+    }
+
+    /// 대국이 진행 중인지 (시작했고 승부가 나지 않음)
+    fn in_game(&self) -> bool {
+        self.started && self.game.winner().is_none()
+    }
+
+    /// 입장(재접속)한 클라이언트가 다음 수를 기다리지 않고 바로 그릴 수 있는 전체 국면
+    fn game_sync(&self) -> GameSync {
+        super::game_sync(
+            self.started,
+            self.game.winner(),
+            self.game.board.is_turn(),
+            &self.players,
+            self.badukboard_status(),
+            &self.kibo,
+            self.game.get_captured_stones(),
+        )
+    }
+
     pub fn black_player_time_info(&self) -> doljabiproto::badukboard::PlayerTimeInfo {
         use doljabiproto::badukboard::PlayerTimeInfo;
 
@@ -234,109 +216,72 @@ impl BadukRoom {
             board: Some(self.baduk_board_state()),
             black_time: Some(self.black_player_time_info()),
             white_time: Some(self.white_player_time_info()),
+            last_move: self.last_move_update(),
         }
     }
 
-    fn record_winner(&mut self, color: Color) {
-        use crate::soyul::soyul_login::{record_game_draw, record_game_lose, record_game_win};
-
-        let black_player_id = self
-            .players
-            .black_player
-            .as_ref()
-            .map(|bp| bp.user_id())
-            .unwrap_or(UserID(u64::MAX));
-        let white_player_id = self
-            .players
-            .white_player
-            .as_ref()
-            .map(|wp| wp.user_id())
-            .unwrap_or(UserID(u64::MAX));
-
-        match color {
-            Color::Black => {
-                if let Err(e) = record_game_win(black_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-                if let Err(e) = record_game_lose(white_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-            }
-            Color::White => {
-                if let Err(e) = record_game_lose(black_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-                if let Err(e) = record_game_win(white_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-            }
-            Color::Free => {
-                if let Err(e) = record_game_draw(black_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-                if let Err(e) = record_game_draw(white_player_id) {
-                    eprintln!("DB 저장 에러: {}", e);
-                };
-            }
-            _ => {}
-        }
+    /// 방에서 보내는 BadukBoardServer 의 기본값 (`..self.board_server()`)
+    fn board_server(&self) -> BadukBoardServer {
+        super::board_server(
+            self.game.board.is_turn(),
+            self.badukboard_status(),
+            self.rating_result,
+            self.spectators.len(),
+        )
     }
 
-    fn set_timer_time(&mut self) -> tokio::time::Duration {
-        use tokio::time::Duration;
-        let turn_player = self.players.turn_player(self.game.board.is_turn());
-
-        match turn_player {
-            Some(p) => {
-                if p.main_time() > 0 {
-                    Duration::from_millis(p.main_time() as u64)
-                } else if p.remain_time() > 0 {
-                    Duration::from_millis(p.overtime() as u64)
-                } else {
-                    let winner = self.game.board.is_turn().reverse();
-                    self.end_game(winner, EndReason::Timeout);
-                    Duration::from_secs(86400)
-                }
-            }
-            None => {
-                self.interrupter.game_closer();
-                Duration::from_secs(86400)
-            }
-        }
+    /// 마지막 수·따낸 돌·수 번호·패 자리
+    fn last_move_update(&self) -> Option<doljabiproto::badukboard::MoveUpdate> {
+        super::move_update(
+            &self.kibo,
+            self.kibo.moves.len(),
+            self.game.last_captured(),
+            self.game.get_captured_stones(),
+            self.game.get_ko_position(),
+        )
     }
 
     fn set_timer(&mut self, event: u16) {
-        let duration = self.set_timer_time();
-        let drop_timer = std::mem::replace(
+        let turn = self.game.board.is_turn();
+        if let Some(winner) = super::set_turn_timer(
+            &self.interrupter,
             &mut self.timeout_event,
-            self.interrupter.register(duration, event),
-        );
-        drop_timer.store(NONE, std::sync::atomic::Ordering::Relaxed);
+            &self.players,
+            turn,
+            event,
+        ) {
+            self.end_game(winner, EndReason::Timeout);
+        }
     }
 
     fn game_start(&mut self) -> ServerToClient {
         self.set_players_time(self.game_config);
+        self.kibo.set_date_today();
         self.set_timer(PLAYER_TIMEOUT);
+        self.started = true;
 
         ServerToClient {
             response_type: true,
             running: Some(true),
             game_type: GAME_TYPE_BADUK,
+            seq: 0,
             game_data: Some(GameData::Baduk(BadukBoardServer {
-                turn: color_i32(self.game.board.is_turn()),
-                the_winner: None,
-                game_state: Some(self.badukboard_status()),
                 users_info: Some(self.users_info()),
-                payload: None,
+                ..self.board_server()
             })),
         }
     }
 }
 
 impl GameLogic for BadukRoom {
-    fn enter_user(&mut self, user_id: UserID) -> ServerToClient {
+    fn enter_user(&mut self, user_id: UserID, connection: ConnectionID) -> ServerToClient {
         use std::sync::atomic::Ordering::Relaxed;
         let result = self.players.push_user(user_id);
+        if !result && self.players.check_id_to_color(user_id) == Color::Free {
+            self.spectators.insert(user_id);
+        }
+        // 끊겼던 플레이어는 같은 자리(색·남은 시간)로 돌아온다
+        self.connections.connect(&self.players, user_id);
         match self.timeout_event.load(Relaxed) {
             BRACK_GAME => {
                 self.timeout_event.store(NONE, Relaxed);
@@ -351,17 +296,60 @@ impl GameLogic for BadukRoom {
             }
             _ => {}
         }
-        self.user_io(result)
+        // 전체 국면은 방금 들어온 연결에만 (관전자면 관전 딜레이 국면으로)
+        let sync = self.user_io(result, Some(Payload::Sync(self.game_sync())));
+        let sync = match self.spectators.contains(&user_id) {
+            true => self.spectator_view(&sync).unwrap_or(sync),
+            false => sync,
+        };
+        self.direct.push((Audience::Connection(connection), sync));
+        self.user_io(result, None)
     }
 
     fn leave_user(&mut self, user_id: UserID) -> ServerToClient {
+        // 같은 유저의 다른 연결이 남아 있으면 자리를 유지한다
+        if !self.connections.disconnect(user_id) {
+            return self.user_io(false, None);
+        }
+        self.spectators.remove(&user_id);
+
+        // 대국 중에는 자리를 비우지 않고 유예 시간 동안 재접속을 기다린다
+        if self.in_game() && self.players.check_id_to_color(user_id) != Color::Free {
+            #[cfg(debug_assertions)]
+            println!("대국 중 접속 끊김: user_id={}, 유예 시작", user_id);
+            self.connections.start_grace(&self.interrupter);
+            return self.user_io(false, None);
+        }
+
         let result = self.players.pop_user(user_id);
         if self.players.check_empty_room() {
             #[cfg(debug_assertions)]
             println!("빈 방 제거 요청 보내기");
             self.interrupter.game_closer();
         }
-        self.user_io(result)
+        self.user_io(result, None)
+    }
+
+    fn spectator_view(&self, message: &ServerToClient) -> Option<ServerToClient> {
+        let finished = self.game.winner().is_some();
+        super::delayed_spectator_view(&self.kibo, self.spectator_delay, finished, message)
+    }
+
+    fn update_latency(&mut self, user_id: UserID, rtt_ms: u32) {
+        self.connections.set_rtt(user_id, rtt_ms);
+    }
+
+    fn take_direct(&mut self) -> Vec<(Audience, ServerToClient)> {
+        std::mem::take(&mut self.direct)
+    }
+
+    fn room_status(&self) -> RoomStatus {
+        RoomStatus {
+            black: self.players.user_id(Color::Black),
+            white: self.players.user_id(Color::White),
+            started: self.started,
+            spectators: self.spectators.len() as u32,
+        }
     }
 
     fn timer_interrupt(&mut self, event: u16) -> ServerToClient {
@@ -369,6 +357,7 @@ impl GameLogic for BadukRoom {
             response_type: false,
             running: None,
             game_type: GAME_TYPE_BADUK,
+            seq: 0,
             game_data: None,
         };
 
@@ -377,6 +366,30 @@ impl GameLogic for BadukRoom {
                 self.interrupter.game_closer();
                 error_response
             }
+            DISCONNECT_GRACE => {
+                if !self.in_game() {
+                    return error_response;
+                }
+                match self.connections.grace_expired(&self.players) {
+                    GraceOutcome::Returned => error_response,
+                    GraceOutcome::Adjourned => {
+                        self.end_game(Color::Free, EndReason::Adjourned);
+
+                        ServerToClient {
+                            response_type: true,
+                            running: Some(true),
+                            game_type: GAME_TYPE_BADUK,
+                            seq: 0,
+                            game_data: Some(GameData::Baduk(BadukBoardServer {
+                                the_winner: Some(color_i32(Color::Free)),
+                                presence: Some(self.seat_presence()),
+                                ..self.board_server()
+                            })),
+                        }
+                    }
+                    GraceOutcome::Claimable => self.user_io(true, None),
+                }
+            }
             PLAYER_TIMEOUT => {
                 let turn_player = self.players.turn_player_mut(self.game.board.is_turn());
                 let is_turn = color_i32(self.game.board.is_turn());
@@ -391,12 +404,10 @@ impl GameLogic for BadukRoom {
                                 response_type: true,
                                 running: Some(true),
                                 game_type: GAME_TYPE_BADUK,
+                                seq: 0,
                                 game_data: Some(GameData::Baduk(BadukBoardServer {
                                     turn: is_turn,
-                                    the_winner: None,
-                                    game_state: Some(self.badukboard_status()),
-                                    users_info: None,
-                                    payload: None,
+                                    ..self.board_server()
                                 })),
                             }
                         } else if p.remain_time() > 1 {
@@ -407,12 +418,10 @@ impl GameLogic for BadukRoom {
                                 response_type: true,
                                 running: Some(true),
                                 game_type: GAME_TYPE_BADUK,
+                                seq: 0,
                                 game_data: Some(GameData::Baduk(BadukBoardServer {
                                     turn: is_turn,
-                                    the_winner: None,
-                                    game_state: Some(self.badukboard_status()),
-                                    users_info: None,
-                                    payload: None,
+                                    ..self.board_server()
                                 })),
                             }
                         } else {
@@ -423,12 +432,11 @@ impl GameLogic for BadukRoom {
                                 response_type: true,
                                 running: Some(true),
                                 game_type: GAME_TYPE_BADUK,
+                                seq: 0,
                                 game_data: Some(GameData::Baduk(BadukBoardServer {
                                     turn: is_turn,
                                     the_winner: self.game.winner().map(|w| color_i32(w.clone())),
-                                    game_state: Some(self.badukboard_status()),
-                                    users_info: None,
-                                    payload: None,
+                                    ..self.board_server()
                                 })),
                             }
                         }
@@ -437,12 +445,10 @@ impl GameLogic for BadukRoom {
                         response_type: false,
                         running: None,
                         game_type: GAME_TYPE_BADUK,
+                        seq: 0,
                         game_data: Some(GameData::Baduk(BadukBoardServer {
-                            turn: color_i32(self.game.board.is_turn()),
                             game_state: None,
-                            users_info: None,
-                            the_winner: None,
-                            payload: None,
+                            ..self.board_server()
                         })),
                     },
                 }
@@ -451,19 +457,45 @@ impl GameLogic for BadukRoom {
         }
     }
 
-    fn send(&mut self, user_id: UserID, message: ClientToServer) -> ServerToClient {
+    fn send(
+        &mut self,
+        user_id: UserID,
+        message: ClientToServer,
+    ) -> Result<Option<ServerToClient>, ErrorCode> {
         use doljabiproto::badukboard::baduk_board_client::Payload as PayloadForClient;
-        use doljabiproto::badukboard::baduk_board_server::Payload;
         use doljabiproto::common::client_to_server::GameData as GameDataForClient;
 
-        let mut response = ServerToClient {
-            response_type: false,
-            running: None,
-            game_type: GAME_TYPE_BADUK,
-            game_data: None,
-        };
+        let response;
+
+        // 채팅은 관전자도 보낼 수 있다. 같은 채널에만 순번 없이 보낸다
+        if let Some(GameDataForClient::Chat(chat)) = &message.game_data {
+            let spectator = self.players.check_id_to_color(user_id) == Color::Free;
+            let moves = self.kibo.moves.len();
+            let chat = self.chat.post(user_id, spectator, &chat.text, moves)?;
+            self.direct.push(chat);
+            return Ok(None);
+        }
+
+        // 관전자(자리에 앉지 않은 유저)의 요청은 받지 않는다
+        if self.players.check_id_to_color(user_id) == Color::Free {
+            #[cfg(debug_assertions)]
+            println!("❌ 관전자의 요청: user_id={}", user_id);
+            return Err(ErrorCode::NotSeated);
+        }
 
         if let Some(GameDataForClient::Baduk(message)) = message.game_data {
+            // 대국 중에만 할 수 있는 요청
+            if matches!(
+                message.payload,
+                Some(PayloadForClient::Coordinate(_))
+                    | Some(PayloadForClient::PassTurn(_))
+                    | Some(PayloadForClient::Resign(_))
+                    | Some(PayloadForClient::DrawOffer(_))
+            ) && !self.in_game()
+            {
+                return Err(ErrorCode::GameNotStarted);
+            }
+
             match message.payload {
                 Some(PayloadForClient::Coordinate(chaksu_request)) => {
                     use doljabiproto::badukboard::ChaksuResponse;
@@ -484,22 +516,23 @@ impl GameLogic for BadukRoom {
                     if player_color != turn {
                         #[cfg(debug_assertions)]
                         println!("❌ 차례가 아닙니다!");
-                        return response;
+                        return Err(ErrorCode::NotYourTurn);
                     }
 
                     // 착수 시도
-                    let success = match self.game.chaksu(coordinate) {
+                    match self.game.chaksu(coordinate) {
                         Ok(_) => {
-                            self.add_move(player_color, coordinate);
+                            super::add_kibo_move(&mut self.kibo, player_color, coordinate);
                             self.pass_turn = false;
                             self.players
                                 .switch_turn(self.game.board.is_turn().reverse());
+                            self.connections.credit_lag(&mut self.players, player_color);
+                            super::record_clock(&mut self.kibo, &self.players, player_color);
 
                             self.set_timer(PLAYER_TIMEOUT);
 
                             #[cfg(debug_assertions)]
                             println!("✅ 착수 성공! 턴 변경됨");
-                            true
                         }
                         Err(_e) => {
                             #[cfg(debug_assertions)]
@@ -514,7 +547,7 @@ impl GameLogic for BadukRoom {
                                     println!("❌ 착수 실패: {:?}", _e);
                                 }
                             }
-                            false
+                            return Err(super::chaksu_error(&_e));
                         }
                     };
 
@@ -530,12 +563,11 @@ impl GameLogic for BadukRoom {
                         response_type: true,
                         running: Some(true),
                         game_type: GAME_TYPE_BADUK,
+                        seq: 0,
                         game_data: Some(GameData::Baduk(BadukBoardServer {
-                            turn: color_i32(self.game.is_board().is_turn()),
                             the_winner: the_winner,
-                            game_state: Some(self.badukboard_status()),
-                            users_info: None,
-                            payload: Some(Payload::Coordinate(ChaksuResponse { success: success })),
+                            payload: Some(Payload::Coordinate(ChaksuResponse { success: true })),
+                            ..self.board_server()
                         })),
                     }
                 }
@@ -551,12 +583,11 @@ impl GameLogic for BadukRoom {
                         response_type: true,
                         running: Some(true),
                         game_type: GAME_TYPE_BADUK,
+                        seq: 0,
                         game_data: Some(GameData::Baduk(BadukBoardServer {
-                            turn: color_i32(self.game.is_board().is_turn()),
                             the_winner: Some(color_i32(winner)),
-                            game_state: Some(self.badukboard_status()),
-                            users_info: None,
                             payload: Some(Payload::Resign(ResignResponse {})),
+                            ..self.board_server()
                         })),
                     }
                 }
@@ -591,12 +622,11 @@ impl GameLogic for BadukRoom {
                         response_type: true,
                         running: Some(true),
                         game_type: GAME_TYPE_BADUK,
+                        seq: 0,
                         game_data: Some(GameData::Baduk(BadukBoardServer {
-                            turn: color_i32(self.game.is_board().is_turn()),
                             the_winner: winner,
-                            game_state: Some(self.badukboard_status()),
-                            users_info: None,
                             payload: Some(Payload::DrawOffer(draw_offer_response)),
+                            ..self.board_server()
                         })),
                     }
                 }
@@ -615,10 +645,11 @@ impl GameLogic for BadukRoom {
                             turn
                         );
 
-                        return response;
+                        return Err(ErrorCode::NotYourTurn);
                     }
 
                     if self.pass_turn {
+                        self.kibo.add_pass(turn);
                         let determined_winner = self.game.determine_winner();
                         self.end_game(determined_winner, EndReason::Immediate);
 
@@ -626,23 +657,25 @@ impl GameLogic for BadukRoom {
                             response_type: true,
                             running: Some(true),
                             game_type: GAME_TYPE_BADUK,
+                            seq: 0,
                             game_data: Some(GameData::Baduk(BadukBoardServer {
-                                turn: color_i32(self.game.is_board().is_turn()) as i32,
                                 the_winner: Some(color_i32(determined_winner)),
-                                game_state: Some(self.badukboard_status()),
-                                users_info: None,
                                 payload: Some(Payload::PassTurn(PassTurnResponse {})),
+                                ..self.board_server()
                             })),
                         };
 
-                        return response;
+                        return Ok(Some(response));
                     } else {
                         self.pass_turn = true;
                     }
 
                     // turn 변경
                     self.players.switch_turn(turn);
-                    self.game.board.switch_turn();
+                    self.game.pass();
+                    self.kibo.add_pass(turn);
+                    self.connections.credit_lag(&mut self.players, turn);
+                    super::record_clock(&mut self.kibo, &self.players, turn);
 
                     self.set_timer(PLAYER_TIMEOUT);
 
@@ -650,25 +683,192 @@ impl GameLogic for BadukRoom {
                         response_type: true,
                         running: None,
                         game_type: GAME_TYPE_BADUK,
+                        seq: 0,
                         game_data: Some(GameData::Baduk(BadukBoardServer {
-                            turn: color_i32(self.game.is_board().is_turn()),
-                            the_winner: None,
-                            game_state: Some(self.badukboard_status()),
-                            users_info: None,
                             payload: Some(Payload::PassTurn(PassTurnResponse {})),
+                            ..self.board_server()
                         })),
                     };
                 }
 
                 Some(PayloadForClient::Gamestart(_)) => {
-                    if self.players.full_players() {
-                        response = self.game_start();
+                    if self.started || !self.players.full_players() {
+                        return Err(ErrorCode::InvalidRequest);
+                    }
+                    response = self.game_start();
+                }
+
+                // 접속이 끊긴 상대가 유예 시간 안에 돌아오지 않음 → 승리 요청
+                Some(PayloadForClient::ClaimWin(_)) => {
+                    let color = self.players.check_id_to_color(user_id);
+                    if !self.in_game() {
+                        return Err(ErrorCode::GameNotStarted);
+                    }
+                    if !self.connections.claim_win(&self.players, color) {
+                        #[cfg(debug_assertions)]
+                        println!("❌ 승리 요청 불가: user_id={}", user_id);
+                        return Err(ErrorCode::InvalidRequest);
+                    }
+                    self.end_game(color, EndReason::Forfeit);
+
+                    response = ServerToClient {
+                        response_type: true,
+                        running: Some(true),
+                        game_type: GAME_TYPE_BADUK,
+                        seq: 0,
+                        game_data: Some(GameData::Baduk(BadukBoardServer {
+                            the_winner: Some(color_i32(color)),
+                            presence: Some(self.seat_presence()),
+                            payload: Some(Payload::ClaimWin(ClaimWinResponse {})),
+                            ..self.board_server()
+                        })),
                     }
                 }
 
-                _ => {}
+                _ => return Err(ErrorCode::InvalidRequest),
             }
+        } else {
+            return Err(ErrorCode::InvalidRequest);
+        }
+        Ok(Some(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::soyul_db::init_schema;
+    use tokio::sync::mpsc;
+
+    fn rated_room() -> BadukRoom {
+        let interrupter = GameInterrupter {
+            sender: mpsc::unbounded_channel().0,
+            receiver: mpsc::channel(8).0,
+        };
+        let settings = RoomSettings {
+            rated: true,
+            spectator_delay: 0,
+            disconnect_grace: Duration::from_secs(60),
+            lag_compensation: false,
+        };
+        let mut room = BadukRoom::new(
+            BadukBoardGameConfig::new(600_000, 0, 3, 30_000),
+            settings,
+            interrupter,
+        );
+        room.players.push_user(UserID(1));
+        room.players.push_user(UserID(2));
+        room
+    }
+
+    fn chaksu(coordinate: u32) -> ClientToServer {
+        use doljabiproto::badukboard::{
+            BadukBoardClient, ChaksuRequest, baduk_board_client::Payload,
+        };
+        use doljabiproto::common::client_to_server::GameData;
+
+        ClientToServer {
+            game_data: Some(GameData::Baduk(BadukBoardClient {
+                payload: Some(Payload::Coordinate(ChaksuRequest { coordinate })),
+            })),
         }
-        response
+    }
+
+    #[test]
+    fn game_sync_test() {
+        use doljabiproto::badukboard::GamePhase;
+
+        let mut room = rated_room();
+        assert_eq!(room.game_sync().phase, GamePhase::Waiting as i32);
+        assert_eq!(room.game_sync().turn_started_at_ms, 0);
+
+        room.game_start();
+        for (user_id, coordinate) in [(1, 60), (2, 300), (1, 62)] {
+            room.send(UserID(user_id), chaksu(coordinate)).unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(20));
+
+        let sync = room.game_sync();
+        assert_eq!(sync.phase, GamePhase::Playing as i32);
+        let moves: Vec<_> = sync.moves.iter().map(|m| (m.color, m.coordinate)).collect();
+        assert_eq!(
+            moves,
+            [
+                (color_i32(Color::Black), Some(60)),
+                (color_i32(Color::White), Some(300)),
+                (color_i32(Color::Black), Some(62)),
+            ]
+        );
+        // 각 수에 둔 직후의 남은 시간이 붙는다
+        assert!(sync.moves.iter().all(|m| m.time_left_ms.is_some()));
+        // 백이 차례를 시작한 시각은 마지막 수를 둔 때
+        let elapsed = sync.server_time_ms - sync.turn_started_at_ms;
+        assert!((20..1000).contains(&elapsed));
+        assert!(!sync.black_draw_offer && !sync.white_draw_offer);
+    }
+
+    #[test]
+    fn rejected_request_test() {
+        let mut room = rated_room();
+        // 시작 전 착수, 자리에 앉지 않은 유저의 요청
+        assert_eq!(
+            room.send(UserID(1), chaksu(60)).unwrap_err(),
+            ErrorCode::GameNotStarted
+        );
+        assert_eq!(
+            room.send(UserID(3), chaksu(60)).unwrap_err(),
+            ErrorCode::NotSeated
+        );
+
+        room.game_start();
+        assert_eq!(
+            room.send(UserID(2), chaksu(60)).unwrap_err(),
+            ErrorCode::NotYourTurn
+        );
+        room.send(UserID(1), chaksu(60)).unwrap();
+        assert_eq!(
+            room.send(UserID(2), chaksu(60)).unwrap_err(),
+            ErrorCode::Occupied
+        );
+
+        // 흑 (3,1) 이 백 (2,1) 을 따낸 직후 백이 바로 되따내면 패
+        for (user_id, coordinate) in [(2, 3), (1, 2), (2, 41), (1, 40), (2, 23), (1, 20)] {
+            room.send(UserID(user_id), chaksu(coordinate)).unwrap();
+        }
+        room.send(UserID(2), chaksu(21)).unwrap();
+        room.send(UserID(1), chaksu(22)).unwrap();
+        assert_eq!(room.send(UserID(2), chaksu(21)).unwrap_err(), ErrorCode::Ko);
+        // 거절된 요청은 기보에 남지 않는다
+        assert_eq!(room.kibo.moves.len(), 9);
+    }
+
+    #[test]
+    fn end_game_once_test() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash, username)
+             VALUES (1, 'black', '', '흑'), (2, 'white', '', '백');",
+        )
+        .unwrap();
+
+        let mut room = rated_room();
+        room.end_game_with(Some(&mut conn), Color::Black, EndReason::Resign);
+        // 시간 초과가 뒤늦게 들어와도 결과는 바뀌지 않는다
+        room.end_game_with(Some(&mut conn), Color::White, EndReason::Timeout);
+
+        assert_eq!(room.game.winner(), Some(Color::Black));
+        let row: (i32, i32, i32) = conn
+            .query_row(
+                "SELECT rating, win, lose FROM user_game_stats WHERE user_id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(row, (1520, 1, 0));
+        let games: i64 = conn
+            .query_row("SELECT COUNT(*) FROM games", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(games, 1);
     }
 }
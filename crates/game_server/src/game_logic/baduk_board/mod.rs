@@ -19,10 +19,26 @@ pub fn color_i32(color: game_core::baduk_board::Color) -> i32 {
     convert_game2proto_color(color) as i32
 }
 
+/// game_core 착수 에러 → 착수한 연결에 보낼 에러 코드
+pub fn chaksu_error(
+    error: &game_core::baduk_board::BadukBoardError,
+) -> doljabiproto::common::ErrorCode {
+    use doljabiproto::common::ErrorCode;
+    use game_core::baduk_board::BadukBoardError;
+
+    match error {
+        BadukBoardError::OverLap => ErrorCode::Occupied,
+        BadukBoardError::BannedChaksu => ErrorCode::Forbidden,
+        BadukBoardError::Ko => ErrorCode::Ko,
+        BadukBoardError::OutOfBoard | BadukBoardError::InvalidArgument => ErrorCode::InvalidRequest,
+    }
+}
+
 pub(crate) mod timeout_event {
     pub const NONE: u16 = 0;
     pub const BRACK_GAME: u16 = 1;
     pub const PLAYER_TIMEOUT: u16 = 2;
+    pub const DISCONNECT_GRACE: u16 = 3;
 }
 
 /// SGF 결과(RE[]) 표기용: 색 → "B" / "W" (그 외에는 빈 문자열)
@@ -42,15 +58,1103 @@ pub enum EndReason {
     Immediate,  // 착수 즉시 승리(오목 5목 등): {승자}+
     Score(i32), // 계가(바둑): {승자}+점수차
     Draw,       // 무승부:     Draw
+    Forfeit,    // 접속 끊김 후 상대의 승리 요청: {승자}+F
+    Adjourned,  // 양쪽 모두 접속 끊김으로 중단: Void
 }
 
 /// 종료 사유 → SGF RE[] 문자열
 pub fn sgf_result(winner: game_core::baduk_board::Color, reason: EndReason) -> String {
     match reason {
         EndReason::Draw => "Draw".to_string(),
+        EndReason::Adjourned => "Void".to_string(),
+        EndReason::Forfeit => format!("{}+F", sgf_color_char(winner)),
         EndReason::Resign => format!("{}+R", sgf_color_char(winner)),
         EndReason::Timeout => format!("{}+T", sgf_color_char(winner)),
         EndReason::Immediate => format!("{}+", sgf_color_char(winner)),
         EndReason::Score(diff) => format!("{}+{}", sgf_color_char(winner), diff),
     }
 }
+
+/// 종료 사유 → 기보 마지막 수에 남길 코멘트(SGF C[]). 결과만으로 충분한 경우 None
+pub fn sgf_end_comment(
+    winner: game_core::baduk_board::Color,
+    reason: &EndReason,
+) -> Option<String> {
+    if let EndReason::Adjourned = reason {
+        return Some("양쪽 접속 끊김으로 대국 중단".to_string());
+    }
+    let loser = match sgf_color_char(winner.reverse()) {
+        "B" => "흑",
+        "W" => "백",
+        _ => return None,
+    };
+    match reason {
+        EndReason::Resign => Some(format!("{} 기권", loser)),
+        EndReason::Timeout => Some(format!("{} 시간패", loser)),
+        EndReason::Forfeit => Some(format!("{} 접속 끊김", loser)),
+        _ => None,
+    }
+}
+
+/// 레이팅 대국으로 인정하는 시간 설정인지 검사 (ms 단위 설정값)
+///
+/// 메인 시간이 너무 짧거나 길지 않고, 초읽기·피셔 시간이 표준 범위 안이어야 한다.
+/// 친선 대국은 검사하지 않는다.
+pub fn check_rated_time_control(
+    game_kind: crate::soyul::kibo::GameKind,
+    config: game_core::baduk_board::BadukBoardGameConfig,
+) -> Result<(), String> {
+    use crate::soyul::kibo::GameKind;
+
+    const SECOND: u32 = 1000;
+    const MINUTE: u32 = 60 * SECOND;
+
+    // (메인 시간 범위, 초읽기 범위)
+    let (main_range, overtime_range) = match game_kind {
+        GameKind::Baduk => (5 * MINUTE..=180 * MINUTE, 10 * SECOND..=120 * SECOND),
+        GameKind::Omok => (MINUTE..=60 * MINUTE, 5 * SECOND..=60 * SECOND),
+    };
+    let (main_time, fischer_time, remaining_overtime, overtime) = config.output();
+
+    if !main_range.contains(&main_time) {
+        return Err(format!(
+            "레이팅 대국 메인 시간은 {}~{}분이어야 합니다",
+            main_range.start() / MINUTE,
+            main_range.end() / MINUTE
+        ));
+    }
+    if fischer_time > MINUTE {
+        return Err("레이팅 대국 피셔 시간은 60초 이하여야 합니다".to_string());
+    }
+    if overtime != 0 {
+        if !overtime_range.contains(&overtime) {
+            return Err(format!(
+                "레이팅 대국 초읽기는 {}~{}초여야 합니다",
+                overtime_range.start() / SECOND,
+                overtime_range.end() / SECOND
+            ));
+        }
+        if !(1..=5).contains(&remaining_overtime) {
+            return Err("레이팅 대국 초읽기 횟수는 1~5회여야 합니다".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// 방에서 보내는 BadukBoardServer 의 기본값: 차례·판·레이팅 결과·관전자 수.
+/// 나머지는 `..board_server(..)` 앞에 채우고, 관전 딜레이는 delayed_spectator_view 가 채운다
+pub fn board_server(
+    turn: game_core::baduk_board::Color,
+    game_state: doljabiproto::badukboard::BadukBoardData,
+    rating_result: Option<crate::soyul::rating::RatingResult>,
+    spectators: usize,
+) -> doljabiproto::badukboard::BadukBoardServer {
+    doljabiproto::badukboard::BadukBoardServer {
+        turn: color_i32(turn),
+        the_winner: None,
+        game_state: Some(game_state),
+        users_info: None,
+        rating_result: rating_result.map(crate::soyul::rating::RatingResult::to_proto),
+        spectators: spectators as u32,
+        spectator_delay: 0,
+        presence: None,
+        payload: None,
+    }
+}
+
+/// 관전 딜레이: 플레이어에게 보낸 메시지를 spectator_delay 수 전 국면으로 바꾼 관전자용 메시지.
+/// 딜레이가 없거나 대국이 끝났으면 None (관전자도 같은 메시지를 받는다)
+///
+/// 판·차례·남은 시간을 늦추고, 착수·패스 응답(payload)은 지금 수를 드러내므로 뺀다.
+/// 입장 동기화(GameSync)는 같은 국면까지의 수순으로 줄여서 보낸다.
+pub fn delayed_spectator_view(
+    kibo: &crate::soyul::kibo::SgfGame,
+    spectator_delay: u32,
+    finished: bool,
+    message: &doljabiproto::common::ServerToClient,
+) -> Option<doljabiproto::common::ServerToClient> {
+    use doljabiproto::{
+        badukboard::baduk_board_server::Payload, common::server_to_client::GameData,
+    };
+
+    if spectator_delay == 0 || finished {
+        return None;
+    }
+    let mut view = message.clone();
+    let data = match view.game_data.as_mut() {
+        Some(GameData::Baduk(data)) | Some(GameData::Omok(data)) => data,
+        _ => return Some(view),
+    };
+
+    let count = kibo.moves.len().saturating_sub(spectator_delay as usize);
+    // 기보를 다시 두지 못하면 판을 숨긴다
+    let position = kibo.position_at(count).ok();
+    let board = position.as_ref().map(position_board_state);
+    // 마지막 수도 늦춘 국면 기준. 판을 보내지 않는 업데이트는 따낸 돌 좌표로 돌을 지우므로
+    // 기보를 다시 둔 국면의 따낸 돌·패 자리를 싣는다
+    let last_move = position.as_ref().and_then(|position| {
+        let caught = (position.black_captures, position.white_captures);
+        move_update(kibo, count, &position.captured, caught, position.ko_point)
+    });
+    // 남은 시간은 늦춘 국면까지 각자 마지막으로 둔 수의 시계
+    let black_time = delayed_time_info(kibo, count, game_core::baduk_board::Color::Black);
+    let white_time = delayed_time_info(kibo, count, game_core::baduk_board::Color::White);
+    if let Some(state) = data.game_state.as_mut() {
+        state.board = board.clone();
+        state.last_move = last_move.clone();
+        state.black_time = black_time;
+        state.white_time = white_time;
+    }
+    if let Some(position) = &position {
+        let turn = match position.last_move {
+            Some(last_move) => last_move.color.reverse(),
+            None => game_core::baduk_board::Color::Black,
+        };
+        data.turn = color_i32(turn);
+    }
+    // 입장 동기화는 수순·따낸 돌도 같은 국면으로 맞춘다
+    data.payload = match data.payload.take() {
+        Some(Payload::Sync(mut sync)) => {
+            if let Some(state) = sync.state.as_mut() {
+                state.board = board;
+                state.last_move = last_move;
+                state.black_time = black_time;
+                state.white_time = white_time;
+            }
+            sync.moves = move_records(kibo, count);
+            if let Some(position) = &position {
+                sync.black_captures = position.black_captures as u32;
+                sync.white_captures = position.white_captures as u32;
+            }
+            // 늦춘 국면 뒤에 수가 있으면 그 국면의 시계는 이미 멈췄고,
+            // 무승부 신청은 다음 수에 풀리므로 지금 신청은 그 국면에 없던 것이다
+            if count < kibo.moves.len() {
+                sync.turn_started_at_ms = 0;
+                sync.black_draw_offer = false;
+                sync.white_draw_offer = false;
+            }
+            Some(Payload::Sync(sync))
+        }
+        _ => None,
+    };
+    data.spectator_delay = spectator_delay;
+    Some(view)
+}
+
+/// 기보의 첫 count수까지 color 가 마지막으로 둔 수의 시계 → 남은 시간.
+/// 아직 두지 않았으면 처음 시간, 시간 설정이 없으면 None
+fn delayed_time_info(
+    kibo: &crate::soyul::kibo::SgfGame,
+    count: usize,
+    color: game_core::baduk_board::Color,
+) -> Option<doljabiproto::badukboard::PlayerTimeInfo> {
+    let (main_time, fischer_time, remaining_overtime, overtime) = kibo.time_limit?.output();
+    let clock = kibo
+        .moves
+        .iter()
+        .take(count)
+        .filter(|m| m.color == color)
+        .filter_map(|m| m.clock)
+        .next_back();
+    let (main_time, remaining_overtime) = match clock {
+        Some(clock) => match clock.periods_left {
+            Some(periods_left) => (0, periods_left),
+            None => (clock.time_left_ms, remaining_overtime),
+        },
+        None => (main_time, remaining_overtime),
+    };
+    Some(doljabiproto::badukboard::PlayerTimeInfo {
+        main_time,
+        fischer_time,
+        remaining_overtime: remaining_overtime as u32,
+        overtime,
+    })
+}
+
+/// 기보 국면 → 비트보드 상태 (BadukBoard 와 같은 배치)
+fn position_board_state(
+    position: &crate::soyul::kibo::Position,
+) -> doljabiproto::badukboard::BadukBoardState {
+    use game_core::baduk_board::{Color, coordinatde_value, coordinate_index};
+
+    let (mut black, mut white) = ([0u64; 6], [0u64; 6]);
+    for (coordinate, color) in position.stones.iter().enumerate() {
+        let coordinate = coordinate as u16;
+        match color {
+            Color::Black => black[coordinate_index(coordinate)] |= coordinatde_value(coordinate),
+            Color::White => white[coordinate_index(coordinate)] |= coordinatde_value(coordinate),
+            _ => {}
+        }
+    }
+    doljabiproto::badukboard::BadukBoardState {
+        black: black.to_vec(),
+        white: white.to_vec(),
+    }
+}
+
+/// 입장(재접속)한 클라이언트가 다음 수를 기다리지 않고 바로 그릴 수 있는 전체 국면.
+/// 따낸 돌은 (흑이 따낸 수, 백이 따낸 수)
+pub fn game_sync(
+    started: bool,
+    winner: Option<game_core::baduk_board::Color>,
+    turn: game_core::baduk_board::Color,
+    players: &game_core::baduk_board::Players,
+    state: doljabiproto::badukboard::BadukBoardData,
+    kibo: &crate::soyul::kibo::SgfGame,
+    (black_captures, white_captures): (u16, u16),
+) -> doljabiproto::badukboard::GameSync {
+    use doljabiproto::badukboard::{GamePhase, GameSync};
+
+    let phase = match (started, winner) {
+        (_, Some(_)) => GamePhase::Finished,
+        (true, None) => GamePhase::Playing,
+        (false, None) => GamePhase::Waiting,
+    };
+    let server_time_ms = unix_millis();
+    // 대국 중일 때만 차례 시작 시각이 의미 있다
+    let turn_started_at_ms = match phase {
+        GamePhase::Playing => players
+            .turn_player(turn)
+            .map(|p| server_time_ms.saturating_sub(p.turn_elapsed().as_millis() as u64))
+            .unwrap_or(0),
+        _ => 0,
+    };
+    let draw_offer = |player: &Option<game_core::baduk_board::Player>| {
+        player.as_ref().is_some_and(|p| p.check_draw_offer())
+    };
+
+    GameSync {
+        phase: phase as i32,
+        state: Some(state),
+        moves: move_records(kibo, kibo.moves.len()),
+        black_captures: black_captures as u32,
+        white_captures: white_captures as u32,
+        server_time_ms,
+        turn_started_at_ms,
+        black_draw_offer: draw_offer(&players.black_player),
+        white_draw_offer: draw_offer(&players.white_player),
+    }
+}
+
+/// 현재 서버 시각 (Unix ms)
+pub fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 기보의 첫 count수 → 동기화 메시지의 수순
+pub fn move_records(
+    kibo: &crate::soyul::kibo::SgfGame,
+    count: usize,
+) -> Vec<doljabiproto::badukboard::MoveRecord> {
+    kibo.moves
+        .iter()
+        .take(count)
+        .map(|m| doljabiproto::badukboard::MoveRecord {
+            color: color_i32(m.color),
+            coordinate: move_coordinate(kibo, m),
+            time_left_ms: m.clock.map(|clock| clock.time_left_ms),
+            periods_left: m.clock.and_then(|clock| clock.periods_left.map(u32::from)),
+        })
+        .collect()
+}
+
+/// 랙 보정으로 한 수에 돌려주는 최대 시간(ms)
+const MAX_LAG_CREDIT_MS: u32 = 1000;
+
+/// 유저별 열린 연결 수와 잰 왕복 지연.
+/// 재접속할 때 이전 연결이 닫히기 전에 새 연결이 먼저 들어올 수 있다
+#[derive(Default)]
+pub struct Connections {
+    counts: std::collections::HashMap<crate::game_logic::UserID, u32>,
+    rtt_ms: std::collections::HashMap<crate::game_logic::UserID, u32>,
+}
+impl Connections {
+    /// 연결 추가
+    pub fn connect(&mut self, user_id: crate::game_logic::UserID) {
+        *self.counts.entry(user_id).or_default() += 1;
+    }
+
+    /// 연결 제거. 이 유저의 마지막 연결이었으면 true
+    pub fn disconnect(&mut self, user_id: crate::game_logic::UserID) -> bool {
+        match self.counts.get_mut(&user_id) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                false
+            }
+            Some(_) => {
+                self.counts.remove(&user_id);
+                self.rtt_ms.remove(&user_id);
+                true
+            }
+            None => true,
+        }
+    }
+
+    pub fn is_connected(&self, user_id: crate::game_logic::UserID) -> bool {
+        self.counts.contains_key(&user_id)
+    }
+
+    /// 소켓이 잰 최근 왕복 지연
+    pub fn set_rtt(&mut self, user_id: crate::game_logic::UserID, rtt_ms: u32) {
+        if self.is_connected(user_id) {
+            self.rtt_ms.insert(user_id, rtt_ms);
+        }
+    }
+
+    /// 랙 보정으로 돌려줄 시간: 편도 지연(왕복의 절반), 최대 MAX_LAG_CREDIT_MS
+    pub fn lag_credit(&self, user_id: crate::game_logic::UserID) -> u32 {
+        self.rtt_ms
+            .get(&user_id)
+            .map_or(0, |rtt| (rtt / 2).min(MAX_LAG_CREDIT_MS))
+    }
+}
+
+/// 대국 중 접속이 끊긴 플레이어의 자리 유지 상태.
+///
+/// 끊긴 플레이어는 자리와 시간을 그대로 둔 채 유예 시간 동안 재접속을 기다린다.
+/// 유예 시간이 지나면 상대가 승리를 요청할 수 있고, 양쪽 다 끊겨 있으면 대국을 중단한다.
+pub struct DisconnectGrace {
+    duration: std::time::Duration,
+    event: std::sync::Arc<std::sync::atomic::AtomicU16>,
+    /// 유예 시간이 끝나는 서버 시각 (Unix ms). 유예 중이 아니면 None
+    deadline_ms: Option<u64>,
+    /// 유예 시간이 지나 접속해 있는 플레이어가 승리를 요청할 수 있는지
+    claimable: bool,
+}
+impl DisconnectGrace {
+    pub fn new(duration: std::time::Duration) -> Self {
+        Self {
+            duration,
+            event: std::sync::Arc::new(std::sync::atomic::AtomicU16::new(timeout_event::NONE)),
+            deadline_ms: None,
+            claimable: false,
+        }
+    }
+
+    /// 유예 타이머 시작. 이미 유예 중이거나 승리 요청이 가능한 상태면 그대로 둔다
+    pub fn start(&mut self, interrupter: &crate::game_logic::timer::GameInterrupter) {
+        if self.deadline_ms.is_some() || self.claimable {
+            return;
+        }
+        self.event = interrupter.register(self.duration, timeout_event::DISCONNECT_GRACE);
+        self.deadline_ms = Some(unix_millis() + self.duration.as_millis() as u64);
+    }
+
+    /// 끊긴 플레이어가 모두 돌아왔을 때 유예 취소
+    pub fn cancel(&mut self) {
+        self.event
+            .store(timeout_event::NONE, std::sync::atomic::Ordering::Relaxed);
+        self.deadline_ms = None;
+        self.claimable = false;
+    }
+
+    /// 유예 시간 만료: 이제 상대가 승리를 요청할 수 있다
+    pub fn expire(&mut self) {
+        self.deadline_ms = None;
+        self.claimable = true;
+    }
+
+    pub fn claimable(&self) -> bool {
+        self.claimable
+    }
+
+    pub fn presence(
+        &self,
+        black_connected: bool,
+        white_connected: bool,
+    ) -> doljabiproto::badukboard::SeatPresence {
+        doljabiproto::badukboard::SeatPresence {
+            black_connected,
+            white_connected,
+            grace_deadline_ms: self.deadline_ms.unwrap_or(0),
+            claimable: self.claimable,
+        }
+    }
+}
+
+/// 접속 유예 시간이 끝났을 때 할 일
+#[derive(Debug, PartialEq)]
+pub enum GraceOutcome {
+    /// 유예 시간 안에 모두 돌아옴
+    Returned,
+    /// 양쪽 다 끊김: 대국 중단
+    Adjourned,
+    /// 한쪽만 끊김: 남은 플레이어가 승리를 요청할 수 있다
+    Claimable,
+}
+
+/// 플레이어 연결과 접속 유예, 랙 보정. 바둑·오목 방이 같이 쓴다
+pub struct SeatConnections {
+    connections: Connections,
+    grace: DisconnectGrace,
+    /// 랙 보정 여부
+    lag_compensation: bool,
+}
+impl SeatConnections {
+    pub fn new(grace: std::time::Duration, lag_compensation: bool) -> Self {
+        Self {
+            connections: Connections::default(),
+            grace: DisconnectGrace::new(grace),
+            lag_compensation,
+        }
+    }
+
+    /// 연결 추가 (자리에 앉힌 뒤). 끊겼던 플레이어가 돌아와 둘 다 접속해 있으면 유예 취소
+    pub fn connect(
+        &mut self,
+        players: &game_core::baduk_board::Players,
+        user_id: crate::game_logic::UserID,
+    ) {
+        use game_core::baduk_board::Color;
+
+        self.connections.connect(user_id);
+        if self.is_connected(players, Color::Black) && self.is_connected(players, Color::White) {
+            self.grace.cancel();
+        }
+    }
+
+    /// 연결 제거. 이 유저의 마지막 연결이었으면 true
+    pub fn disconnect(&mut self, user_id: crate::game_logic::UserID) -> bool {
+        self.connections.disconnect(user_id)
+    }
+
+    /// 대국 중 플레이어의 마지막 연결이 끊김: 유예 시작
+    pub fn start_grace(&mut self, interrupter: &crate::game_logic::timer::GameInterrupter) {
+        self.grace.start(interrupter);
+    }
+
+    /// 이 색 자리에 앉은 플레이어가 접속해 있는지
+    pub fn is_connected(
+        &self,
+        players: &game_core::baduk_board::Players,
+        color: game_core::baduk_board::Color,
+    ) -> bool {
+        players
+            .user_id(color)
+            .is_some_and(|user_id| self.connections.is_connected(user_id))
+    }
+
+    pub fn presence(
+        &self,
+        players: &game_core::baduk_board::Players,
+    ) -> doljabiproto::badukboard::SeatPresence {
+        use game_core::baduk_board::Color;
+
+        self.grace.presence(
+            self.is_connected(players, Color::Black),
+            self.is_connected(players, Color::White),
+        )
+    }
+
+    /// 유예 타이머 만료 (대국 중일 때만 부른다)
+    pub fn grace_expired(&mut self, players: &game_core::baduk_board::Players) -> GraceOutcome {
+        use game_core::baduk_board::Color;
+
+        match (
+            self.is_connected(players, Color::Black),
+            self.is_connected(players, Color::White),
+        ) {
+            (true, true) => {
+                self.grace.cancel();
+                GraceOutcome::Returned
+            }
+            (false, false) => {
+                self.grace.cancel();
+                GraceOutcome::Adjourned
+            }
+            _ => {
+                self.grace.expire();
+                GraceOutcome::Claimable
+            }
+        }
+    }
+
+    /// color 플레이어의 승리 요청. 유예 시간이 지났고 상대가 아직 끊겨 있을 때만 true
+    pub fn claim_win(
+        &mut self,
+        players: &game_core::baduk_board::Players,
+        color: game_core::baduk_board::Color,
+    ) -> bool {
+        if !self.grace.claimable() || self.is_connected(players, color.reverse()) {
+            return false;
+        }
+        self.grace.cancel();
+        true
+    }
+
+    /// 소켓이 잰 최근 왕복 지연
+    pub fn set_rtt(&mut self, user_id: crate::game_logic::UserID, rtt_ms: u32) {
+        self.connections.set_rtt(user_id, rtt_ms);
+    }
+
+    /// 랙 보정: 방금 둔 플레이어에게 잰 네트워크 지연(편도)만큼 메인 시간을 돌려준다
+    pub fn credit_lag(
+        &self,
+        players: &mut game_core::baduk_board::Players,
+        color: game_core::baduk_board::Color,
+    ) {
+        if !self.lag_compensation {
+            return;
+        }
+        let Some(user_id) = players.user_id(color) else {
+            return;
+        };
+        let lag_ms = self.connections.lag_credit(user_id);
+        if let Some(player) = players.turn_player_mut(color) {
+            player.credit_lag(lag_ms);
+        }
+    }
+}
+
+/// 차례인 플레이어의 시간 초과 타이머를 다시 건다 (이전 타이머는 무효).
+/// 차례인 플레이어의 시간이 이미 다 떨어졌으면 이긴 색(상대)을 돌려준다
+pub fn set_turn_timer(
+    interrupter: &crate::game_logic::timer::GameInterrupter,
+    timeout_event: &mut std::sync::Arc<std::sync::atomic::AtomicU16>,
+    players: &game_core::baduk_board::Players,
+    turn: game_core::baduk_board::Color,
+    event: u16,
+) -> Option<game_core::baduk_board::Color> {
+    use std::time::Duration;
+
+    let mut timed_out = None;
+    let duration = match players.turn_player(turn) {
+        Some(p) if p.main_time() > 0 => Duration::from_millis(p.main_time() as u64),
+        Some(p) if p.remain_time() > 0 => Duration::from_millis(p.overtime() as u64),
+        Some(_) => {
+            timed_out = Some(turn.reverse());
+            Duration::from_secs(86400)
+        }
+        None => {
+            interrupter.game_closer();
+            Duration::from_secs(86400)
+        }
+    };
+    let drop_timer = std::mem::replace(timeout_event, interrupter.register(duration, event));
+    drop_timer.store(timeout_event::NONE, std::sync::atomic::Ordering::Relaxed);
+    timed_out
+}
+
+/// 대국 결과를 저장할 DB 연결. 열지 못하면 None (저장 없이 대국만 끝낸다)
+pub fn open_game_db() -> Option<rusqlite::Connection> {
+    match rusqlite::Connection::open("mydb.db") {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            eprintln!("❌ DB 오픈 실패: {}", e);
+            None
+        }
+    }
+}
+
+/// 기보에 정수 좌표(y * 판 크기 + x)의 수를 추가
+pub fn add_kibo_move(
+    kibo: &mut crate::soyul::kibo::SgfGame,
+    color: game_core::baduk_board::Color,
+    coordinate: u16,
+) {
+    let size = kibo.board_size as u16;
+    kibo.add_move(color, (coordinate % size) as u8, (coordinate / size) as u8);
+}
+
+/// 방금 둔 플레이어의 남은 시간을 기보의 마지막 수에 기록 (SGF BL/WL, OB/OW)
+pub fn record_clock(
+    kibo: &mut crate::soyul::kibo::SgfGame,
+    players: &game_core::baduk_board::Players,
+    color: game_core::baduk_board::Color,
+) {
+    use crate::soyul::kibo::MoveClock;
+
+    let clock = match players.turn_player(color) {
+        Some(p) if p.main_time() > 0 => MoveClock {
+            time_left_ms: p.main_time(),
+            periods_left: None,
+        },
+        Some(p) => MoveClock {
+            time_left_ms: p.overtime(),
+            periods_left: Some(p.remain_time()),
+        },
+        None => return,
+    };
+    kibo.set_last_clock(clock);
+}
+
+/// 두 자리의 유저 정보 (이 게임 종류·판 크기의 레이팅)
+pub fn users_info(
+    players: &game_core::baduk_board::Players,
+    kibo: &crate::soyul::kibo::SgfGame,
+) -> doljabiproto::badukboard::UsersInfo {
+    use crate::soyul::soyul_login::get_user_profile_by_id;
+    use game_core::baduk_board::Color;
+
+    let conn = match rusqlite::Connection::open("mydb.db") {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            eprintln!("game_user_info 출력 에러: DB 불러오기 실패: {}", e);
+            None
+        }
+    };
+    let user_info = |color| {
+        let user_id = players.user_id(color)?;
+        match get_user_profile_by_id(conn.as_ref()?, user_id) {
+            Ok(Some(user_profile)) => Some(
+                user_profile.convert_session2proto(kibo.game_kind.game_type(), kibo.board_size),
+            ),
+            _ => {
+                eprintln!("user_info: 존재하지 않는 유저 정보!");
+                None
+            }
+        }
+    };
+    doljabiproto::badukboard::UsersInfo {
+        black: user_info(Color::Black),
+        white: user_info(Color::White),
+    }
+}
+
+/// 대국이 끝난 기보 처리: 종료 코멘트를 달고, 레이팅 대국이면 승패를 반영한 뒤 games 테이블에 저장.
+/// DB 를 열지 못했으면 conn 이 None 이고 아무것도 저장하지 않는다. 반영된 레이팅 변화를 돌려준다
+pub fn finish_kibo(
+    conn: Option<&mut rusqlite::Connection>,
+    kibo: &mut crate::soyul::kibo::SgfGame,
+    players: &game_core::baduk_board::Players,
+    chat: &[crate::soyul::kibo::ChatLine],
+    rated: bool,
+    winner: game_core::baduk_board::Color,
+    reason: EndReason,
+) -> Option<crate::soyul::rating::RatingResult> {
+    if let Some(comment) = sgf_end_comment(winner, &reason) {
+        kibo.set_end_comment(&comment);
+    }
+    // 중단된 대국은 레이팅·전적에 반영하지 않는다
+    let adjourned = matches!(reason, EndReason::Adjourned);
+    let result = sgf_result(winner, reason);
+    let conn = conn?;
+    let rating_result = match rated && !adjourned {
+        true => record_winner(conn, kibo, players, winner),
+        false => None,
+    };
+    save_kibo(conn, kibo, players, rating_result.as_ref(), chat, &result);
+    rating_result
+}
+
+/// 승패를 두 플레이어의 전적·레이팅에 한 트랜잭션으로 반영
+fn record_winner(
+    conn: &mut rusqlite::Connection,
+    kibo: &crate::soyul::kibo::SgfGame,
+    players: &game_core::baduk_board::Players,
+    winner: game_core::baduk_board::Color,
+) -> Option<crate::soyul::rating::RatingResult> {
+    use crate::soyul::rating::apply_rated_result;
+    use game_core::baduk_board::Color;
+
+    // 두 명이 모두 있는 게임만 반영 (save_kibo 와 같은 기준)
+    let (black_id, white_id) = (
+        players.user_id(Color::Black)?,
+        players.user_id(Color::White)?,
+    );
+    match apply_rated_result(
+        conn,
+        black_id,
+        white_id,
+        winner,
+        kibo.game_kind.game_type(),
+        kibo.board_size,
+    ) {
+        Ok(ratings) => ratings,
+        Err(e) => {
+            eprintln!("DB 저장 에러: {}", e);
+            None
+        }
+    }
+}
+
+/// 누적된 기보(SgfGame)에 결과·플레이어 이름·레이팅을 채워 games 테이블에 저장
+fn save_kibo(
+    conn: &rusqlite::Connection,
+    kibo: &mut crate::soyul::kibo::SgfGame,
+    players: &game_core::baduk_board::Players,
+    rating_result: Option<&crate::soyul::rating::RatingResult>,
+    chat: &[crate::soyul::kibo::ChatLine],
+    result: &str,
+) {
+    use crate::soyul::soyul_db::{
+        save_finished_game, save_game_chat, update_game_ratings, update_game_sgf,
+    };
+    use crate::soyul::soyul_login::get_user_profile_by_id;
+    use game_core::baduk_board::Color;
+
+    // 정상적으로 두 명이 플레이한 게임만 저장
+    let (Some(black_id), Some(white_id)) =
+        (players.user_id(Color::Black), players.user_id(Color::White))
+    else {
+        return;
+    };
+
+    let game_type = kibo.game_kind.game_type();
+    let board_size = kibo.board_size;
+    let profile_of = |id| {
+        get_user_profile_by_id(conn, id)
+            .ok()
+            .flatten()
+            .map(|p| {
+                let rating = p.rating_for(game_type, board_size);
+                (p.username.unwrap_or_default(), rating)
+            })
+            .unwrap_or_default()
+    };
+    let (black_name, black_rating) = profile_of(black_id);
+    let (white_name, white_rating) = profile_of(white_id);
+
+    // BR/WR 은 대국 전 레이팅
+    let (black_rating, white_rating) = match rating_result {
+        Some(r) => (r.black.before, r.white.before),
+        None => (black_rating, white_rating),
+    };
+    kibo.set_players(&black_name, &white_name);
+    kibo.set_ratings(black_rating, white_rating);
+    kibo.set_result(result);
+
+    match save_finished_game(
+        conn,
+        i64::from(black_id),
+        i64::from(white_id),
+        game_type,
+        board_size,
+        result,
+        &kibo.to_sgf_string(),
+    ) {
+        Ok(game_id) => {
+            // INSERT 로 받은 게임 번호를 GN[] 에 넣어 다시 기록
+            kibo.set_game_id(game_id);
+            if let Err(e) = update_game_sgf(conn, game_id, &kibo.to_sgf_string()) {
+                eprintln!("기보 저장 실패(GN 갱신): {}", e);
+            }
+            if let Some(ratings) = rating_result
+                && let Err(e) = update_game_ratings(conn, game_id, ratings)
+            {
+                eprintln!("기보 저장 실패(레이팅 기록): {}", e);
+            }
+            if let Err(e) = save_game_chat(conn, game_id, chat) {
+                eprintln!("기보 저장 실패(채팅): {}", e);
+            }
+            #[cfg(debug_assertions)]
+            println!("✅ 기보 저장 성공: game_id={}, result={}", game_id, result);
+        }
+        Err(e) => eprintln!("기보 저장 실패(INSERT): {}", e),
+    }
+}
+
+/// 기보의 수 → 정수 좌표 (y * 판 크기 + x). 패스면 None
+fn move_coordinate(
+    kibo: &crate::soyul::kibo::SgfGame,
+    m: &crate::soyul::kibo::Move,
+) -> Option<u32> {
+    let size = kibo.board_size as u32;
+    m.point.map(|(x, y)| y as u32 * size + x as u32)
+}
+
+/// 기보의 count번째 수 → 보드 업데이트에 싣는 마지막 수 정보. 아직 둔 수가 없으면 None
+///
+/// 따낸 돌·누적 따낸 돌 수·패 자리는 그 수를 둔 직후 국면의 값을 넘긴다.
+pub fn move_update(
+    kibo: &crate::soyul::kibo::SgfGame,
+    count: usize,
+    captured: &[u16],
+    (black_caught_stone, white_caught_stone): (u16, u16),
+    ko_point: Option<u16>,
+) -> Option<doljabiproto::badukboard::MoveUpdate> {
+    let last_move = kibo.moves.get(count.checked_sub(1)?)?;
+    let coordinate = move_coordinate(kibo, last_move);
+    Some(doljabiproto::badukboard::MoveUpdate {
+        color: color_i32(last_move.color),
+        coordinate,
+        // 패스는 돌을 따내지 않는다
+        captured: match coordinate {
+            Some(_) => captured.iter().map(|&c| c as u32).collect(),
+            None => Vec::new(),
+        },
+        move_number: count as u32,
+        black_caught_stone: black_caught_stone as u32,
+        white_caught_stone: white_caught_stone as u32,
+        ko_point: ko_point.map(u32::from),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::kibo::GameKind;
+    use game_core::baduk_board::BadukBoardGameConfig;
+
+    #[test]
+    fn delayed_spectator_view_test() {
+        use crate::soyul::kibo::{MoveClock, SgfGame};
+        use doljabiproto::{
+            badukboard::{BadukBoardData, GameSync, baduk_board_server::Payload},
+            common::{ServerToClient, server_to_client::GameData},
+        };
+        use game_core::baduk_board::Color;
+
+        let mut kibo = SgfGame::baduk();
+        kibo.set_time_limit(BadukBoardGameConfig::new(600_000, 0, 3, 30_000));
+        let clock = |time_left_ms, periods_left| MoveClock {
+            time_left_ms,
+            periods_left,
+        };
+        for (color, x, y, clock) in [
+            (Color::Black, 3, 3, clock(590_000, None)),
+            (Color::White, 15, 15, clock(580_000, None)),
+            (Color::Black, 3, 15, clock(30_000, Some(2))),
+            (Color::White, 15, 3, clock(570_000, None)),
+        ] {
+            kibo.add_move(color, x, y);
+            kibo.set_last_clock(clock);
+        }
+
+        // 지금 국면: 백이 4수째를 두고 흑이 무승부를 신청한 상태
+        let live = board_server(Color::Black, BadukBoardData::default(), None, 1);
+        let message = ServerToClient {
+            game_data: Some(GameData::Baduk(
+                doljabiproto::badukboard::BadukBoardServer {
+                    payload: Some(Payload::Sync(GameSync {
+                        state: Some(BadukBoardData::default()),
+                        moves: move_records(&kibo, 4),
+                        turn_started_at_ms: 1234,
+                        black_draw_offer: true,
+                        ..Default::default()
+                    })),
+                    ..live
+                },
+            )),
+            ..Default::default()
+        };
+        assert!(delayed_spectator_view(&kibo, 0, false, &message).is_none());
+        assert!(delayed_spectator_view(&kibo, 2, true, &message).is_none());
+
+        let view = |delay| match delayed_spectator_view(&kibo, delay, false, &message)
+            .and_then(|view| view.game_data)
+        {
+            Some(GameData::Baduk(data)) => data,
+            _ => panic!("관전자용 메시지가 없음"),
+        };
+
+        // 2수 늦추면 둘 다 첫 수를 둔 직후의 시계
+        let data = view(2);
+        assert_eq!(data.spectator_delay, 2);
+        assert_eq!(data.turn, color_i32(Color::Black));
+        let state = data.game_state.unwrap();
+        assert_eq!(state.black_time.unwrap().main_time, 590_000);
+        assert_eq!(state.white_time.unwrap().main_time, 580_000);
+        let Some(Payload::Sync(sync)) = data.payload else {
+            panic!("입장 동기화가 빠짐");
+        };
+        assert_eq!(sync.moves.len(), 2);
+        assert_eq!(sync.state.unwrap().white_time.unwrap().main_time, 580_000);
+        // 그 국면에는 없던 무승부 신청과 흐르는 시계는 보내지 않는다
+        assert!(!sync.black_draw_offer);
+        assert_eq!(sync.turn_started_at_ms, 0);
+
+        // 아직 두지 않은 쪽은 처음 시간, 초읽기에 들어간 쪽은 남은 횟수
+        let white_time = view(3).game_state.unwrap().white_time.unwrap();
+        assert_eq!(white_time.main_time, 600_000);
+        assert_eq!(white_time.remaining_overtime, 3);
+        let black_time = view(1).game_state.unwrap().black_time.unwrap();
+        assert_eq!(black_time.main_time, 0);
+        assert_eq!(black_time.remaining_overtime, 2);
+        assert_eq!(black_time.overtime, 30_000);
+    }
+
+    #[test]
+    fn delayed_spectator_capture_test() {
+        use crate::soyul::kibo::SgfGame;
+        use doljabiproto::{
+            badukboard::BadukBoardData,
+            common::{ServerToClient, server_to_client::GameData},
+        };
+        use game_core::baduk_board::Color;
+
+        // 9수째 흑(3,1)이 백(2,1)을 따내고 패가 생긴다. 10수째는 멀리 둔 백
+        let mut kibo = SgfGame::baduk();
+        for (i, (x, y)) in [
+            (1, 1),
+            (3, 0),
+            (2, 0),
+            (3, 2),
+            (2, 2),
+            (4, 1),
+            (10, 10),
+            (2, 1),
+            (3, 1),
+            (15, 15),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let color = if i % 2 == 0 {
+                Color::Black
+            } else {
+                Color::White
+            };
+            kibo.add_move(color, x, y);
+        }
+
+        let message = ServerToClient {
+            game_data: Some(GameData::Baduk(board_server(
+                Color::Black,
+                BadukBoardData::default(),
+                None,
+                1,
+            ))),
+            ..Default::default()
+        };
+        let view = |delay| match delayed_spectator_view(&kibo, delay, false, &message)
+            .and_then(|view| view.game_data)
+        {
+            Some(GameData::Baduk(data)) => data.game_state.unwrap().last_move.unwrap(),
+            _ => panic!("관전자용 메시지가 없음"),
+        };
+
+        // 1수 늦춘 관전자도 따낸 돌을 지울 수 있어야 한다
+        let last_move = view(1);
+        assert_eq!(last_move.move_number, 9);
+        assert_eq!(last_move.captured, [19 + 2]);
+        assert_eq!(last_move.black_caught_stone, 1);
+        assert_eq!(last_move.ko_point, Some(19 + 2));
+
+        // 따낸 돌이 없는 수
+        let last_move = view(2);
+        assert!(last_move.captured.is_empty());
+        assert_eq!(last_move.ko_point, None);
+    }
+
+    #[test]
+    fn lag_credit_test() {
+        use crate::game_logic::UserID;
+
+        let mut connections = Connections::default();
+        // 연결이 없는 유저의 지연은 기록하지 않는다
+        connections.set_rtt(UserID(1), 200);
+        assert_eq!(connections.lag_credit(UserID(1)), 0);
+
+        connections.connect(UserID(1));
+        connections.set_rtt(UserID(1), 200);
+        assert_eq!(connections.lag_credit(UserID(1)), 100);
+        // 편도 지연이 MAX_LAG_CREDIT_MS 를 넘으면 잘라낸다
+        connections.set_rtt(UserID(1), 5000);
+        assert_eq!(connections.lag_credit(UserID(1)), MAX_LAG_CREDIT_MS);
+
+        // 마지막 연결이 닫히면 잰 지연도 지운다
+        assert!(connections.disconnect(UserID(1)));
+        connections.connect(UserID(1));
+        assert_eq!(connections.lag_credit(UserID(1)), 0);
+    }
+
+    #[test]
+    fn seat_connections_test() {
+        use crate::game_logic::{UserID, timer::GameInterrupter};
+        use game_core::baduk_board::{Color, Players};
+        use tokio::sync::mpsc;
+
+        let interrupter = GameInterrupter {
+            sender: mpsc::unbounded_channel().0,
+            receiver: mpsc::channel(8).0,
+        };
+        let mut players = Players::new();
+        players.push_user(UserID(1));
+        players.push_user(UserID(2));
+        let mut seats = SeatConnections::new(std::time::Duration::from_secs(60), false);
+        seats.connect(&players, UserID(1));
+        seats.connect(&players, UserID(2));
+        assert_eq!(seats.presence(&players).grace_deadline_ms, 0);
+
+        // 백이 끊김 → 유예 시작. 유예 중에는 승리를 요청할 수 없다
+        assert!(seats.disconnect(UserID(2)));
+        seats.start_grace(&interrupter);
+        let presence = seats.presence(&players);
+        assert!(presence.black_connected && !presence.white_connected);
+        assert!(presence.grace_deadline_ms > unix_millis());
+        assert!(!seats.claim_win(&players, Color::Black));
+
+        // 돌아오면 유예 취소
+        seats.connect(&players, UserID(2));
+        assert_eq!(seats.presence(&players).grace_deadline_ms, 0);
+        assert_eq!(seats.grace_expired(&players), GraceOutcome::Returned);
+
+        // 유예 시간이 지나도록 안 돌아오면 흑이 승리를 요청할 수 있다
+        seats.disconnect(UserID(2));
+        seats.start_grace(&interrupter);
+        assert_eq!(seats.grace_expired(&players), GraceOutcome::Claimable);
+        assert!(seats.presence(&players).claimable);
+        // 끊긴 쪽은 요청할 수 없다
+        assert!(!seats.claim_win(&players, Color::White));
+        assert!(seats.claim_win(&players, Color::Black));
+        assert!(!seats.presence(&players).claimable);
+
+        // 양쪽 다 끊기면 대국 중단
+        seats.disconnect(UserID(1));
+        seats.start_grace(&interrupter);
+        assert_eq!(seats.grace_expired(&players), GraceOutcome::Adjourned);
+        assert!(!seats.claim_win(&players, Color::Black));
+    }
+
+    #[test]
+    fn disconnect_grace_test() {
+        use crate::game_logic::timer::GameInterrupter;
+        use std::sync::atomic::Ordering::Relaxed;
+        use tokio::sync::mpsc;
+
+        let interrupter = GameInterrupter {
+            sender: mpsc::unbounded_channel().0,
+            receiver: mpsc::channel(8).0,
+        };
+        let mut grace = DisconnectGrace::new(std::time::Duration::from_secs(60));
+        grace.start(&interrupter);
+        let event = grace.event.clone();
+        assert_eq!(event.load(Relaxed), timeout_event::DISCONNECT_GRACE);
+        // 유예 중에 다시 시작해도 타이머는 그대로
+        grace.start(&interrupter);
+        assert!(std::sync::Arc::ptr_eq(&event, &grace.event));
+
+        // 취소하면 등록한 타이머가 울려도 무시된다
+        grace.cancel();
+        assert_eq!(event.load(Relaxed), timeout_event::NONE);
+        assert!(!grace.claimable());
+
+        grace.start(&interrupter);
+        grace.expire();
+        assert!(grace.claimable());
+        assert_eq!(grace.presence(true, false).grace_deadline_ms, 0);
+        // 승리 요청이 가능한 동안에는 새 유예를 시작하지 않는다
+        grace.start(&interrupter);
+        assert!(grace.claimable());
+        assert!(grace.deadline_ms.is_none());
+    }
+
+    #[test]
+    fn rated_time_control_test() {
+        let config = |main_min: u32, fischer_sec: u32, count: u8, overtime_sec: u32| {
+            BadukBoardGameConfig::new(
+                main_min * 60_000,
+                fischer_sec * 1000,
+                count,
+                overtime_sec * 1000,
+            )
+        };
+
+        assert!(check_rated_time_control(GameKind::Baduk, config(30, 0, 3, 30)).is_ok());
+        assert!(check_rated_time_control(GameKind::Baduk, config(10, 10, 0, 0)).is_ok());
+        // 메인 시간 범위는 게임마다 다르다
+        assert!(check_rated_time_control(GameKind::Baduk, config(3, 0, 3, 30)).is_err());
+        assert!(check_rated_time_control(GameKind::Omok, config(3, 0, 3, 30)).is_ok());
+        assert!(check_rated_time_control(GameKind::Omok, config(61, 0, 0, 0)).is_err());
+        // 피셔 시간 60초 초과
+        assert!(check_rated_time_control(GameKind::Baduk, config(30, 61, 0, 0)).is_err());
+        // 초읽기 길이·횟수
+        assert!(check_rated_time_control(GameKind::Baduk, config(30, 0, 3, 5)).is_err());
+        assert!(check_rated_time_control(GameKind::Baduk, config(30, 0, 0, 30)).is_err());
+        assert!(check_rated_time_control(GameKind::Baduk, config(30, 0, 6, 30)).is_err());
+    }
+}
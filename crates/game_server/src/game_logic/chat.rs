@@ -0,0 +1,160 @@
+// chat.rs
+//
+// 대국 방 채팅. 플레이어 채팅은 플레이어끼리, 관전자 채팅은 관전자끼리만 보인다.
+//  - 한 번에 MAX_CHAT_CHARS 글자까지, 유저마다 CHAT_WINDOW 동안 CHAT_LIMIT 번까지
+//  - 오간 채팅은 보낸 시점의 수와 함께 남겨 두었다가, 방이 기보를 저장할 때 game_chat 에 같이 저장한다
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use doljabiproto::common::{
+    ChatChannel, ChatMessage, ErrorCode, ServerToClient, server_to_client::GameData,
+};
+use game_core::UserID;
+
+use crate::{
+    game_logic::{Audience, baduk_board::unix_millis},
+    soyul::kibo::ChatLine,
+};
+
+/// 채팅 한 번의 최대 글자 수
+pub const MAX_CHAT_CHARS: usize = 200;
+/// 유저 한 명이 CHAT_WINDOW 동안 보낼 수 있는 채팅 수
+pub const CHAT_LIMIT: usize = 5;
+pub const CHAT_WINDOW: Duration = Duration::from_secs(10);
+
+/// 방 하나의 채팅 기록과 유저별 횟수 제한
+#[derive(Default)]
+pub struct RoomChat {
+    lines: Vec<ChatLine>,
+    /// 유저별 최근 CHAT_WINDOW 안에 보낸 시각
+    recent: HashMap<UserID, VecDeque<Instant>>,
+    /// 유저 이름 (DB 조회 캐시)
+    names: HashMap<UserID, String>,
+}
+
+impl RoomChat {
+    /// 채팅 한 줄을 받아 남기고, 같은 채널(플레이어·관전자)에 보낼 메시지를 돌려준다
+    pub fn post(
+        &mut self,
+        user_id: UserID,
+        spectator: bool,
+        text: &str,
+        move_number: usize,
+    ) -> Result<(Audience, ServerToClient), ErrorCode> {
+        let text = self.check(user_id, text, Instant::now())?;
+        let line = ChatLine {
+            move_number,
+            spectator,
+            user_id: i64::from(user_id),
+            user_name: self.name_of(user_id),
+            text,
+            sent_at_ms: unix_millis(),
+        };
+        let (audience, channel) = match spectator {
+            true => (Audience::Spectators, ChatChannel::Spectators),
+            false => (Audience::Players, ChatChannel::Players),
+        };
+        let message = ServerToClient {
+            game_data: Some(GameData::Chat(ChatMessage {
+                channel: channel as i32,
+                user_id: u64::from(user_id),
+                user_name: line.user_name.clone(),
+                text: line.text.clone(),
+                move_number: move_number as u32,
+                sent_at_ms: line.sent_at_ms,
+            })),
+            ..Default::default()
+        };
+        self.lines.push(line);
+        Ok((audience, message))
+    }
+
+    /// 지금까지 오간 채팅 (보낸 순서대로)
+    pub fn lines(&self) -> &[ChatLine] {
+        &self.lines
+    }
+
+    /// 길이·횟수 제한 검사. 통과하면 앞뒤 공백을 뗀 본문
+    fn check(&mut self, user_id: UserID, text: &str, now: Instant) -> Result<String, ErrorCode> {
+        let text = text.trim();
+        if text.is_empty() || text.chars().count() > MAX_CHAT_CHARS {
+            return Err(ErrorCode::InvalidRequest);
+        }
+
+        let recent = self.recent.entry(user_id).or_default();
+        while recent
+            .front()
+            .is_some_and(|&sent| now.duration_since(sent) >= CHAT_WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= CHAT_LIMIT {
+            return Err(ErrorCode::RateLimited);
+        }
+        recent.push_back(now);
+        Ok(text.to_string())
+    }
+
+    /// 유저 이름. 처음 한 번만 DB 를 읽고, 못 읽으면 "user {id}"
+    fn name_of(&mut self, user_id: UserID) -> String {
+        use crate::soyul::soyul_login::get_user_profile_by_id;
+
+        self.names
+            .entry(user_id)
+            .or_insert_with(|| {
+                rusqlite::Connection::open("mydb.db")
+                    .and_then(|conn| get_user_profile_by_id(&conn, user_id))
+                    .unwrap_or_else(|e| {
+                        eprintln!("채팅: 유저 정보 조회 실패: {}", e);
+                        None
+                    })
+                    .and_then(|profile| profile.username)
+                    .unwrap_or_else(|| format!("user {}", user_id))
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_length_limit() {
+        let mut chat = RoomChat::default();
+        let now = Instant::now();
+        assert_eq!(
+            chat.check(UserID(1), "  안녕하세요  ", now),
+            Ok("안녕하세요".to_string())
+        );
+        assert_eq!(
+            chat.check(UserID(1), "   ", now),
+            Err(ErrorCode::InvalidRequest)
+        );
+        let long = "가".repeat(MAX_CHAT_CHARS + 1);
+        assert_eq!(
+            chat.check(UserID(1), &long, now),
+            Err(ErrorCode::InvalidRequest)
+        );
+    }
+
+    #[test]
+    fn chat_rate_limit() {
+        let mut chat = RoomChat::default();
+        let now = Instant::now();
+        for _ in 0..CHAT_LIMIT {
+            assert!(chat.check(UserID(1), "ㅎㅇ", now).is_ok());
+        }
+        assert_eq!(
+            chat.check(UserID(1), "ㅎㅇ", now),
+            Err(ErrorCode::RateLimited)
+        );
+        // 다른 유저는 따로 센다
+        assert!(chat.check(UserID(2), "ㅎㅇ", now).is_ok());
+        // CHAT_WINDOW 가 지나면 다시 보낼 수 있다
+        assert!(chat.check(UserID(1), "ㅎㅇ", now + CHAT_WINDOW).is_ok());
+    }
+}
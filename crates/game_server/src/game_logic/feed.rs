@@ -0,0 +1,298 @@
+// feed.rs
+//
+// 방 메시지 순번·델타.
+//  - 게임 노드가 메시지를 낼 때마다 방 안에서 1씩 늘어나는 seq 를 붙인다.
+//    플레이어용·관전자용으로 나뉜 메시지는 같은 seq 라서, 각 연결이 받는 seq 는 빈틈없이 이어진다
+//  - 판이 그대로이거나 한 수만 늘어난 메시지는 바둑판(BadukBoardState)을 뺀 델타도 만든다.
+//    클라이언트는 game_state.last_move(좌표·따낸 돌)로 판을 고친다. SNAPSHOT_INTERVAL 번마다는 항상 전체
+//  - 최근 HISTORY_LEN 개를 남겨 두고, 클라이언트가 Resync 로 놓친 메시지를 다시 받을 수 있게 한다
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use doljabiproto::{
+    badukboard::BadukBoardState,
+    common::{ServerToClient, server_to_client::GameData},
+};
+
+use tokio::sync::broadcast;
+
+use crate::game_logic::{Audience, Recipient, RoomMessage};
+
+/// 이 간격(seq)마다는 델타 없이 전체 판만 보낸다
+pub const SNAPSHOT_INTERVAL: u64 = 20;
+/// Resync 를 위해 남겨 두는 최근 메시지 수
+pub const HISTORY_LEN: usize = 128;
+
+/// 게임 노드와 소켓이 함께 보는 최근 방 메시지
+pub type FeedHistory = Arc<Mutex<VecDeque<Arc<RoomMessage>>>>;
+
+/// 받는 쪽(플레이어·관전자)이 마지막으로 받은 판
+#[derive(Default)]
+struct BoardTrack {
+    board: Option<BadukBoardState>,
+    move_number: u32,
+}
+impl BoardTrack {
+    /// 이 판을 직전 판에서 델타로 보낼 수 있는지 (판이 그대로이거나 한 수만 늘었을 때)
+    fn follows(&self, board: &BadukBoardState, move_number: u32) -> bool {
+        match &self.board {
+            Some(last) if move_number == self.move_number => last == board,
+            Some(_) => move_number == self.move_number + 1,
+            None => false,
+        }
+    }
+}
+
+/// 메시지의 판과 수 번호
+fn board_of(message: &ServerToClient) -> Option<(&BadukBoardState, u32)> {
+    let (Some(GameData::Baduk(server)) | Some(GameData::Omok(server))) = &message.game_data else {
+        return None;
+    };
+    let state = server.game_state.as_ref()?;
+    let move_number = state.last_move.as_ref().map_or(0, |m| m.move_number);
+    Some((state.board.as_ref()?, move_number))
+}
+
+/// 바둑판을 뺀 델타 메시지
+fn strip_board(message: &ServerToClient) -> ServerToClient {
+    let mut delta = message.clone();
+    if let Some(GameData::Baduk(server)) | Some(GameData::Omok(server)) = &mut delta.game_data
+        && let Some(state) = server.game_state.as_mut()
+    {
+        state.board = None;
+    }
+    delta
+}
+
+/// 방 하나의 메시지 순번·델타·기록
+pub struct RoomFeed {
+    seq: u64,
+    output: broadcast::Sender<Arc<RoomMessage>>,
+    history: FeedHistory,
+    players: BoardTrack,
+    spectators: BoardTrack,
+}
+
+impl RoomFeed {
+    pub fn new(output: broadcast::Sender<Arc<RoomMessage>>, history: FeedHistory) -> Self {
+        Self {
+            seq: 0,
+            output,
+            history,
+            players: BoardTrack::default(),
+            spectators: BoardTrack::default(),
+        }
+    }
+
+    /// 한 번의 처리 결과(대상별 메시지)를 방의 모든 연결에 보낸다.
+    /// 기록에 먼저 남겨야 Resync 로 다시 받는 메시지와 순서가 맞는다
+    pub fn publish(&mut self, outputs: Vec<(Audience, ServerToClient)>) {
+        for output in self.record(outputs) {
+            if let Err(e) = self.output.send(output) {
+                eprintln!("게임 메시지 전송 에러!!! {}", e);
+            };
+        }
+    }
+
+    /// 특정 유저·연결에만 보내는 메시지. 순번을 붙이지 않고(seq 0) 기록에도 남기지 않는다
+    pub fn send_direct(&self, audience: Audience, message: ServerToClient) {
+        let output = Arc::new(RoomMessage {
+            audience,
+            message,
+            delta: None,
+        });
+        if let Err(e) = self.output.send(output) {
+            eprintln!("게임 메시지 전송 에러!!! {}", e);
+        };
+    }
+
+    /// 다음 seq 를 붙이고 델타를 만들어 기록에 남긴다
+    fn record(&mut self, outputs: Vec<(Audience, ServerToClient)>) -> Vec<Arc<RoomMessage>> {
+        self.seq += 1;
+        let snapshot = self.seq.is_multiple_of(SNAPSHOT_INTERVAL);
+
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        outputs
+            .into_iter()
+            .map(|(audience, mut message)| {
+                message.seq = self.seq;
+                let tracks: Vec<&mut BoardTrack> = match audience {
+                    Audience::All => vec![&mut self.players, &mut self.spectators],
+                    Audience::Players => vec![&mut self.players],
+                    Audience::Spectators => vec![&mut self.spectators],
+                    // 특정 유저·연결 메시지는 send_direct 로 보내서 여기로 오지 않는다
+                    Audience::User(_) | Audience::Connection(_) => Vec::new(),
+                };
+                let delta = match board_of(&message) {
+                    Some((board, move_number)) => {
+                        let follows = !tracks.is_empty()
+                            && tracks.iter().all(|t| t.follows(board, move_number));
+                        for track in tracks {
+                            track.board = Some(board.clone());
+                            track.move_number = move_number;
+                        }
+                        (follows && !snapshot).then(|| strip_board(&message))
+                    }
+                    None => None,
+                };
+
+                let output = Arc::new(RoomMessage {
+                    audience,
+                    message,
+                    delta,
+                });
+                if history.len() == HISTORY_LEN {
+                    history.pop_front();
+                }
+                history.push_back(output.clone());
+                output
+            })
+            .collect()
+    }
+}
+
+/// from_seq 다음부터 이 연결이 받아야 할 메시지.
+/// 기록이 이미 지나갔거나 from_seq 가 이상하면 가장 최근 메시지의 전체 판만 준다
+pub fn replay(
+    history: &FeedHistory,
+    from_seq: u64,
+    recipient: &Recipient,
+    delta: bool,
+) -> Vec<ServerToClient> {
+    let history = history.lock().unwrap_or_else(|e| e.into_inner());
+    let mine: Vec<&Arc<RoomMessage>> = history.iter().filter(|m| m.is_for(recipient)).collect();
+    let (Some(first), Some(last)) = (mine.first(), mine.last()) else {
+        return Vec::new();
+    };
+
+    if from_seq == last.message.seq {
+        Vec::new()
+    } else if from_seq < last.message.seq && from_seq + 1 >= first.message.seq {
+        mine.iter()
+            .filter(|m| m.message.seq > from_seq)
+            .map(|m| m.message_for(delta).clone())
+            .collect()
+    } else {
+        vec![last.message.clone()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::ConnectionID;
+    use doljabiproto::badukboard::{BadukBoardData, BadukBoardServer, MoveUpdate};
+    use game_core::UserID;
+
+    fn board_message(black: u64, move_number: u32) -> ServerToClient {
+        ServerToClient {
+            game_data: Some(GameData::Baduk(BadukBoardServer {
+                game_state: Some(BadukBoardData {
+                    board: Some(BadukBoardState {
+                        black: vec![black],
+                        white: vec![],
+                    }),
+                    last_move: (move_number > 0).then(|| MoveUpdate {
+                        move_number,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    const PLAYER: Recipient = Recipient {
+        user_id: UserID(1),
+        connection: ConnectionID(1),
+        seated: true,
+    };
+    const SPECTATOR: Recipient = Recipient {
+        user_id: UserID(2),
+        connection: ConnectionID(2),
+        seated: false,
+    };
+
+    fn feed() -> (RoomFeed, FeedHistory) {
+        let history = FeedHistory::default();
+        let (output, _) = broadcast::channel(4);
+        (RoomFeed::new(output, history.clone()), history)
+    }
+
+    #[test]
+    fn seq_increases_and_deltas_follow_moves() {
+        let (mut feed, _) = feed();
+        let first = feed.record(vec![(Audience::All, board_message(0, 0))]);
+        assert_eq!(first[0].message.seq, 1);
+        assert!(first[0].delta.is_none(), "처음 판은 전체로");
+
+        // 판이 그대로
+        let same = feed.record(vec![(Audience::All, board_message(0, 0))]);
+        assert_eq!(same[0].message.seq, 2);
+        assert!(same[0].delta.is_some());
+
+        // 한 수 늘어남
+        let next = feed.record(vec![(Audience::All, board_message(1, 1))]);
+        let delta = next[0].delta.as_ref().unwrap();
+        assert!(board_of(delta).is_none());
+        assert!(board_of(&next[0].message).is_some());
+
+        // 수가 건너뛰면 전체
+        let jump = feed.record(vec![(Audience::All, board_message(3, 3))]);
+        assert!(jump[0].delta.is_none());
+    }
+
+    #[test]
+    fn snapshot_interval_sends_full_board() {
+        let (mut feed, _) = feed();
+        for seq in 1..=SNAPSHOT_INTERVAL {
+            let output = feed.record(vec![(Audience::All, board_message(0, 0))]);
+            assert_eq!(
+                output[0].delta.is_none(),
+                seq == 1 || seq == SNAPSHOT_INTERVAL
+            );
+        }
+    }
+
+    #[test]
+    fn split_audiences_share_seq() {
+        let (mut feed, _) = feed();
+        let outputs = feed.record(vec![
+            (Audience::Players, board_message(1, 1)),
+            (Audience::Spectators, board_message(0, 0)),
+        ]);
+        assert_eq!(outputs[0].message.seq, outputs[1].message.seq);
+    }
+
+    #[test]
+    fn replay_from_seq() {
+        let (mut feed, history) = feed();
+        for n in 0..5 {
+            feed.record(vec![(Audience::All, board_message(n, n as u32))]);
+        }
+        let seqs = |v: Vec<ServerToClient>| v.iter().map(|m| m.seq).collect::<Vec<_>>();
+        assert_eq!(seqs(replay(&history, 2, &PLAYER, true)), vec![3, 4, 5]);
+        assert_eq!(
+            seqs(replay(&history, 0, &SPECTATOR, true)),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(replay(&history, 5, &PLAYER, true).is_empty());
+
+        // 델타 연결에는 델타로, 아니면 전체로
+        assert!(board_of(&replay(&history, 4, &PLAYER, true)[0]).is_none());
+        assert!(board_of(&replay(&history, 4, &PLAYER, false)[0]).is_some());
+
+        // 기록이 지나간 seq 는 최근 전체 판만
+        for n in 5..(HISTORY_LEN as u64 + 5) {
+            feed.record(vec![(Audience::All, board_message(n, n as u32))]);
+        }
+        let latest = replay(&history, 1, &PLAYER, true);
+        assert_eq!(latest.len(), 1);
+        assert!(board_of(&latest[0]).is_some());
+    }
+}
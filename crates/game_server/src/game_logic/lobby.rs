@@ -0,0 +1,332 @@
+// lobby.rs
+//
+// 공개 로비: 열려 있는 방(대기 중·대국 중)의 요약 정보.
+//  - GET /api/rooms : 지금 열려 있는 방 목록
+//  - GET /ws/lobby  : 방 생성·변경·종료 이벤트(JSON)를 실시간으로 받는 WebSocket
+//
+// 각 방의 게임 노드(run_game_node)가 메시지를 처리할 때마다 RoomStatus 를 비교해서,
+// 바뀌었을 때만 요약을 다시 만들어(유저 이름·레이팅은 DB 조회) 로비에 알린다.
+
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{
+        State,
+        ws::{self, WebSocketUpgrade},
+    },
+    http::StatusCode,
+    response::IntoResponse,
+};
+use futures_util::{SinkExt, StreamExt};
+use game_core::{UserID, baduk_board::BadukBoardGameConfig};
+use serde::Serialize;
+use tokio::sync::{RwLock, broadcast};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::game_logic::CreateRoomRequestForm;
+
+/// 방 상태 중 로비에 보이는 부분. 게임 노드가 메시지마다 비교하므로 값싼 값만 담는다
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct RoomStatus {
+    pub black: Option<UserID>,
+    pub white: Option<UserID>,
+    pub started: bool,
+    pub spectators: u32,
+}
+
+/// 자리에 앉은 플레이어
+#[derive(Serialize, ToSchema, Clone, Debug)]
+pub struct SeatSummary {
+    pub user_id: i64,
+    pub username: Option<String>,
+    /// 이 방 게임 종류·판 크기의 레이팅
+    pub rating: i32,
+}
+
+/// 로비에 공개하는 방 요약
+#[derive(Serialize, ToSchema, Clone, Debug)]
+pub struct RoomSummary {
+    pub enter_code: String,
+    /// "baduk" | "omok"
+    pub game_type: String,
+    pub board_size: u8,
+    pub rated: bool,
+    pub game_config: BadukBoardGameConfig,
+    pub black: Option<SeatSummary>,
+    pub white: Option<SeatSummary>,
+    /// 대국이 시작됐으면 true, 대기 중이면 false
+    pub started: bool,
+    pub spectators: u32,
+    /// 최대 관전자 수. null 이면 제한 없음, 0 이면 관전 불가
+    pub max_spectators: Option<u32>,
+    /// 관전 딜레이(수)
+    pub spectator_delay: u32,
+}
+
+/// 로비 WebSocket 이벤트
+#[derive(Serialize, ToSchema, Clone, Debug)]
+#[serde(tag = "event")]
+pub enum LobbyEvent {
+    #[serde(rename = "room_created")]
+    RoomCreated { room: RoomSummary },
+    #[serde(rename = "room_updated")]
+    RoomUpdated { room: RoomSummary },
+    #[serde(rename = "room_closed")]
+    RoomClosed { enter_code: String },
+}
+
+/// 열려 있는 방 요약 목록 + 변경 이벤트 채널
+#[derive(Clone)]
+pub struct Lobby {
+    rooms: Arc<RwLock<HashMap<String, RoomSummary>>>,
+    events: broadcast::Sender<LobbyEvent>,
+}
+
+impl Default for Lobby {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lobby {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(64);
+        Self {
+            rooms: Arc::new(RwLock::new(HashMap::new())),
+            events,
+        }
+    }
+
+    /// 방 목록 (대기 중인 방 먼저, 그다음 입장 코드 순)
+    pub async fn list(&self) -> Vec<RoomSummary> {
+        let mut rooms: Vec<RoomSummary> = self.rooms.read().await.values().cloned().collect();
+        rooms.sort_by(|a, b| (a.started, &a.enter_code).cmp(&(b.started, &b.enter_code)));
+        rooms
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LobbyEvent> {
+        self.events.subscribe()
+    }
+
+    async fn publish(&self, room: RoomSummary) {
+        let created = self
+            .rooms
+            .write()
+            .await
+            .insert(room.enter_code.clone(), room.clone())
+            .is_none();
+        // 구독자가 없으면 에러지만 무시해도 된다
+        let _ = self.events.send(if created {
+            LobbyEvent::RoomCreated { room }
+        } else {
+            LobbyEvent::RoomUpdated { room }
+        });
+    }
+
+    async fn close(&self, enter_code: &str) {
+        if self.rooms.write().await.remove(enter_code).is_some() {
+            let _ = self.events.send(LobbyEvent::RoomClosed {
+                enter_code: enter_code.to_string(),
+            });
+        }
+    }
+}
+
+/// 방 하나의 로비 게시자. 방이 만들어질 때 정해지는 정보를 들고 있다
+/// 비공개 방은 로비에 올리지 않는다
+pub struct LobbyRoom {
+    lobby: Lobby,
+    enter_code: String,
+    form: CreateRoomRequestForm,
+    last: Option<RoomStatus>,
+}
+
+impl LobbyRoom {
+    pub fn new(lobby: Lobby, enter_code: String, form: CreateRoomRequestForm) -> Self {
+        Self {
+            lobby,
+            enter_code,
+            form,
+            last: None,
+        }
+    }
+
+    /// 상태가 바뀌었으면 요약을 다시 만들어 로비에 알린다
+    pub async fn update(&mut self, status: RoomStatus) {
+        if self.form.is_private() || self.last == Some(status) {
+            return;
+        }
+        self.last = Some(status);
+
+        let game_kind = self.form.game().game_kind();
+        let (game_type, board_size) = (game_kind.game_type(), game_kind.board_size());
+        let seat = |user_id: Option<UserID>| {
+            user_id.map(|user_id| seat_summary(user_id, game_type, board_size))
+        };
+        let room = RoomSummary {
+            enter_code: self.enter_code.clone(),
+            game_type: game_type.to_string(),
+            board_size,
+            rated: self.form.rated(),
+            game_config: self.form.game().game_config(),
+            black: seat(status.black),
+            white: seat(status.white),
+            started: status.started,
+            spectators: status.spectators,
+            max_spectators: self.form.max_spectators(),
+            spectator_delay: self.form.spectator_delay(),
+        };
+        self.lobby.publish(room).await;
+    }
+
+    pub async fn close(&self) {
+        self.lobby.close(&self.enter_code).await;
+    }
+}
+
+/// 유저 이름·레이팅 조회. DB 를 못 읽으면 이름 없이 기본 레이팅
+fn seat_summary(user_id: UserID, game_type: &str, board_size: u8) -> SeatSummary {
+    use crate::soyul::{rating::DEFAULT_RATING, soyul_login::get_user_profile_by_id};
+
+    let profile = rusqlite::Connection::open("mydb.db")
+        .and_then(|conn| get_user_profile_by_id(&conn, user_id))
+        .unwrap_or_else(|e| {
+            eprintln!("로비 방 정보: 유저 정보 조회 실패: {}", e);
+            None
+        });
+    SeatSummary {
+        user_id: i64::from(user_id),
+        rating: profile
+            .as_ref()
+            .map(|p| p.rating_for(game_type, board_size))
+            .unwrap_or(DEFAULT_RATING),
+        username: profile.and_then(|p| p.username),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/rooms",
+    tag = "lobby",
+    responses(
+        (status = 200, description = "열려 있는 방 목록", body = Vec<RoomSummary>),
+    )
+)]
+pub async fn get_rooms(State(lobby): State<Lobby>) -> impl IntoResponse {
+    (StatusCode::OK, Json(lobby.list().await)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws/lobby",
+    tag = "lobby",
+    responses(
+        (status = 101, description = "WebSocket 연결 성공: LobbyEvent(JSON)를 계속 받는다"),
+    )
+)]
+pub async fn lobby_socket(ws: WebSocketUpgrade, State(lobby): State<Lobby>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_lobby_socket(socket, lobby))
+}
+
+async fn handle_lobby_socket(socket: ws::WebSocket, lobby: Lobby) {
+    use broadcast::error::RecvError;
+    use ws::Message;
+
+    let (mut ws_tx, mut ws_rx) = socket.split();
+    let mut events = lobby.subscribe();
+
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    // 밀린 이벤트는 건너뛴다 (클라이언트는 /api/rooms 로 다시 맞출 수 있다)
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(json) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if ws_tx.send(Message::Text(json.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = ws_rx.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+}
+
+pub fn lobby_router() -> OpenApiRouter<Lobby> {
+    OpenApiRouter::new()
+        .routes(routes!(get_rooms))
+        .routes(routes!(lobby_socket))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::RoomGameForm;
+
+    fn form() -> CreateRoomRequestForm {
+        let config = BadukBoardGameConfig::new(1_800_000, 0, 3, 30_000);
+        CreateRoomRequestForm::new(RoomGameForm::Baduk(config), true)
+    }
+
+    fn event_name(event: &LobbyEvent) -> &'static str {
+        match event {
+            LobbyEvent::RoomCreated { .. } => "created",
+            LobbyEvent::RoomUpdated { .. } => "updated",
+            LobbyEvent::RoomClosed { .. } => "closed",
+        }
+    }
+
+    #[tokio::test]
+    async fn lobby_room_update_test() {
+        let lobby = Lobby::new();
+        let mut events = lobby.subscribe();
+        let mut room = LobbyRoom::new(lobby.clone(), "123456".to_string(), form());
+
+        let waiting = RoomStatus::default();
+        room.update(waiting).await;
+        // 바뀌지 않은 상태는 다시 알리지 않는다
+        room.update(waiting).await;
+        room.update(RoomStatus {
+            spectators: 1,
+            ..waiting
+        })
+        .await;
+        assert_eq!(lobby.list().await[0].spectators, 1);
+        room.close().await;
+
+        let mut names = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            names.push(event_name(&event));
+        }
+        assert_eq!(names, ["created", "updated", "closed"]);
+        assert!(lobby.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn private_room_test() {
+        let lobby = Lobby::new();
+        let mut events = lobby.subscribe();
+        let mut room = LobbyRoom::new(lobby.clone(), "654321".to_string(), form().to_private());
+
+        room.update(RoomStatus::default()).await;
+        room.update(RoomStatus {
+            started: true,
+            ..RoomStatus::default()
+        })
+        .await;
+        room.close().await;
+
+        // 비공개 방은 목록에도 이벤트에도 나오지 않는다
+        assert!(lobby.list().await.is_empty());
+        assert!(events.try_recv().is_err());
+    }
+}
@@ -0,0 +1,520 @@
+// matchmaking.rs
+//
+// 자동 매칭 대기열.
+// 유저가 게임 종류·시간 설정(판 크기는 게임 종류로 정해진다)을 골라 대기열에 들어오면,
+// 같은 설정을 고른 유저 중 레이팅이 비슷한 상대와 짝을 지어 비공개 방을 만들고
+// 두 사람에게 입장 코드와 초대 토큰을 알린다.
+// 처음에는 레이팅 차 BASE_WINDOW 안에서만 찾고, 기다린 시간만큼 허용 범위를 넓힌다.
+//  - POST   /api/matchmaking/session/{session_key} : 대기열 참가
+//  - GET    /api/matchmaking/session/{session_key} : 매칭 상태 조회 (폴링)
+//  - DELETE /api/matchmaking/session/{session_key} : 대기열 취소
+//  - GET    /ws/matchmaking/session/{session_key}  : WebSocket 으로 참가 후 매칭되면 바로 알림
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use axum::{
+    Json,
+    extract::{
+        Path, State,
+        ws::{self, WebSocketUpgrade},
+    },
+    http::StatusCode,
+    response::IntoResponse,
+};
+use futures_util::{SinkExt, StreamExt};
+use game_core::UserID;
+use serde::Serialize;
+use tokio::{
+    sync::{Mutex, watch},
+    time::Instant,
+};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    game_logic::{CreateRoomRequestForm, RoomManager, open_room, timer::TimerManager},
+    soyul::session::{SessionStore, get_user_id_by_session},
+};
+
+/// 처음 허용하는 레이팅 차
+const BASE_WINDOW: i32 = 100;
+/// 기다린 1초마다 넓히는 레이팅 차
+const WINDOW_PER_SECOND: i32 = 10;
+/// 레이팅 차 상한
+const MAX_WINDOW: i32 = 800;
+/// 짝 찾기 주기
+const MATCH_INTERVAL: Duration = Duration::from_secs(1);
+/// 매칭·취소 결과를 조회할 수 있게 남겨두는 시간
+const FINISHED_KEEP: Duration = Duration::from_secs(60);
+
+/// 기다린 시간에 따른 허용 레이팅 차
+pub fn rating_window(waited: Duration) -> i32 {
+    (BASE_WINDOW + WINDOW_PER_SECOND * waited.as_secs() as i32).min(MAX_WINDOW)
+}
+
+/// 매칭 상태 (REST 응답·WebSocket 메시지 공용)
+#[derive(Serialize, ToSchema, Clone, Debug, PartialEq)]
+#[serde(tag = "status")]
+pub enum MatchStatus {
+    /// 대기 중
+    #[serde(rename = "waiting")]
+    Waiting {
+        waited_secs: u64,
+        rating_window: i32,
+    },
+    /// 매칭 완료: enter_code 비공개 방에 invite_token 으로 입장
+    #[serde(rename = "matched")]
+    Matched {
+        enter_code: String,
+        invite_token: String,
+        opponent_id: i64,
+    },
+    /// 취소됨
+    #[serde(rename = "cancelled")]
+    Cancelled,
+}
+
+/// 대기열의 한 유저
+pub struct MatchTicket {
+    user_id: UserID,
+    rating: i32,
+    form: CreateRoomRequestForm,
+    joined_at: Instant,
+    status: watch::Sender<MatchStatus>,
+}
+impl MatchTicket {
+    fn waiting_status(&self, now: Instant) -> MatchStatus {
+        let waited = now - self.joined_at;
+        MatchStatus::Waiting {
+            waited_secs: waited.as_secs(),
+            rating_window: rating_window(waited),
+        }
+    }
+
+    /// 같은 설정을 골랐고 서로의 허용 범위 안에 있는지
+    fn can_match(&self, other: &MatchTicket, now: Instant) -> bool {
+        let window = rating_window(now - self.joined_at).min(rating_window(now - other.joined_at));
+        self.user_id != other.user_id
+            && self.form.rated() == other.form.rated()
+            && self.form.game().same_settings(&other.form.game())
+            && (self.rating - other.rating).abs() <= window
+    }
+}
+
+/// 오래 기다린 순으로, 짝이 될 수 있는 상대 중 레이팅이 가장 가까운 유저와 묶는다
+pub fn find_pairs(queue: &[MatchTicket], now: Instant) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..queue.len()).collect();
+    order.sort_by_key(|&i| queue[i].joined_at);
+
+    let mut paired = vec![false; queue.len()];
+    let mut pairs = Vec::new();
+    for &i in &order {
+        if paired[i] {
+            continue;
+        }
+        let opponent = order
+            .iter()
+            .copied()
+            .filter(|&j| j != i && !paired[j] && queue[i].can_match(&queue[j], now))
+            .min_by_key(|&j| (queue[i].rating - queue[j].rating).abs());
+        if let Some(j) = opponent {
+            paired[i] = true;
+            paired[j] = true;
+            pairs.push((i, j));
+        }
+    }
+    pairs
+}
+
+pub struct MatchmakingManagement {
+    queue: Vec<MatchTicket>,
+    /// 매칭·취소가 끝난 유저의 마지막 상태 (FINISHED_KEEP 뒤 삭제)
+    finished: HashMap<UserID, (watch::Sender<MatchStatus>, Instant)>,
+}
+
+pub type Matchmaker = Arc<Mutex<MatchmakingManagement>>;
+
+impl MatchmakingManagement {
+    fn new() -> Self {
+        Self {
+            queue: Vec::new(),
+            finished: HashMap::new(),
+        }
+    }
+
+    /// 대기열 참가. 이미 있으면 새 설정으로 다시 줄을 선다
+    pub fn join(
+        &mut self,
+        user_id: UserID,
+        rating: i32,
+        form: CreateRoomRequestForm,
+    ) -> watch::Receiver<MatchStatus> {
+        self.queue.retain(|t| t.user_id != user_id);
+        self.finished.remove(&user_id);
+
+        let now = Instant::now();
+        let (status, receiver) = watch::channel(MatchStatus::Waiting {
+            waited_secs: 0,
+            rating_window: rating_window(Duration::ZERO),
+        });
+        self.queue.push(MatchTicket {
+            user_id,
+            rating,
+            form,
+            joined_at: now,
+            status,
+        });
+        receiver
+    }
+
+    /// 대기열 취소. 대기 중이 아니었으면 false
+    pub fn cancel(&mut self, user_id: UserID) -> bool {
+        match self.queue.iter().position(|t| t.user_id == user_id) {
+            Some(index) => {
+                let ticket = self.queue.remove(index);
+                self.finish(ticket, MatchStatus::Cancelled);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 현재 상태. 대기열에도 최근 결과에도 없으면 None
+    pub fn status(&self, user_id: UserID) -> Option<MatchStatus> {
+        if let Some(ticket) = self.queue.iter().find(|t| t.user_id == user_id) {
+            return Some(ticket.waiting_status(Instant::now()));
+        }
+        self.finished
+            .get(&user_id)
+            .map(|(status, _)| status.borrow().clone())
+    }
+
+    fn finish(&mut self, ticket: MatchTicket, status: MatchStatus) {
+        ticket.status.send_replace(status);
+        self.finished
+            .insert(ticket.user_id, (ticket.status, Instant::now()));
+    }
+
+    /// 짝이 지어진 티켓들을 대기열에서 꺼낸다
+    fn take_pairs(&mut self, now: Instant) -> Vec<(MatchTicket, MatchTicket)> {
+        let pairs = find_pairs(&self.queue, now);
+        let mut slots: Vec<Option<MatchTicket>> = self.queue.drain(..).map(Some).collect();
+
+        let taken = pairs
+            .into_iter()
+            .filter_map(|(a, b)| Some((slots[a].take()?, slots[b].take()?)))
+            .collect();
+        self.queue = slots.into_iter().flatten().collect();
+        taken
+    }
+
+    /// 짝 찾기 → 방 생성 → 두 유저에게 입장 코드 알림
+    async fn run_once(&mut self, room_manager: &RoomManager, timer_manager: &TimerManager) {
+        let now = Instant::now();
+        self.finished
+            .retain(|_, (_, finished_at)| now - *finished_at < FINISHED_KEEP);
+
+        for (a, b) in self.take_pairs(now) {
+            // 매칭된 두 사람만 들어올 수 있도록 비공개 방으로 연다
+            match open_room(room_manager, timer_manager, a.form.to_private()).await {
+                Some(room) => {
+                    #[cfg(debug_assertions)]
+                    println!(
+                        "✅ 매칭 성공: {}({}) vs {}({}) → {}",
+                        a.user_id, a.rating, b.user_id, b.rating, room.enter_code
+                    );
+                    let invite_token = room.invite_token.unwrap_or_default();
+                    let (a_id, b_id) = (i64::from(a.user_id), i64::from(b.user_id));
+                    self.finish(
+                        a,
+                        MatchStatus::Matched {
+                            enter_code: room.enter_code.clone(),
+                            invite_token: invite_token.clone(),
+                            opponent_id: b_id,
+                        },
+                    );
+                    self.finish(
+                        b,
+                        MatchStatus::Matched {
+                            enter_code: room.enter_code,
+                            invite_token,
+                            opponent_id: a_id,
+                        },
+                    );
+                }
+                None => {
+                    // 방을 못 만들면 다음 주기에 다시 시도
+                    eprintln!("매칭 방 생성 실패: EnterCode 생성 실패");
+                    self.queue.push(a);
+                    self.queue.push(b);
+                }
+            }
+        }
+    }
+
+    /// 매칭 루프를 띄우고 대기열 핸들을 돌려준다
+    pub fn run(room_manager: RoomManager, timer_manager: TimerManager) -> Matchmaker {
+        let matchmaker = Arc::new(Mutex::new(Self::new()));
+
+        let handle = matchmaker.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(MATCH_INTERVAL);
+            loop {
+                interval.tick().await;
+                handle
+                    .lock()
+                    .await
+                    .run_once(&room_manager, &timer_manager)
+                    .await;
+            }
+        });
+
+        matchmaker
+    }
+}
+
+/// 게임 종류·판 크기에 맞는 유저 레이팅
+fn user_rating(user_id: UserID, form: &CreateRoomRequestForm) -> rusqlite::Result<i32> {
+    use crate::soyul::{rating::DEFAULT_RATING, soyul_login::get_user_profile_by_id};
+
+    let game_kind = form.game().game_kind();
+    let conn = rusqlite::Connection::open("mydb.db")?;
+    Ok(get_user_profile_by_id(&conn, user_id)?
+        .map(|p| p.rating_for(game_kind.game_type(), game_kind.board_size()))
+        .unwrap_or(DEFAULT_RATING))
+}
+
+/// 대기열 참가 공통 처리 (REST·WebSocket)
+async fn join_queue(
+    matchmaker: &Matchmaker,
+    user_id: UserID,
+    form: CreateRoomRequestForm,
+) -> Result<watch::Receiver<MatchStatus>, (StatusCode, String)> {
+    form.check().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let rating = user_rating(user_id, &form).map_err(|e| {
+        eprintln!("⚠️ 매칭 참가(레이팅 조회 실패): {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "레이팅 조회 실패".to_string(),
+        )
+    })?;
+
+    #[cfg(debug_assertions)]
+    println!("매칭 대기열 참가: user_id={}, rating={}", user_id, rating);
+    Ok(matchmaker.lock().await.join(user_id, rating, form))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/matchmaking/session/{session_key}",
+    tag = "matchmaking",
+    params(
+        ("session_key" = String, Path, description = "세션 키")
+    ),
+    request_body = CreateRoomRequestForm,
+    responses(
+        (status = 202, description = "대기열 참가 성공", body = MatchStatus),
+        (status = 400, description = "세션 키 또는 게임 설정이 올바르지 않음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn join_matchmaking(
+    State((matchmaker, session_store)): State<(Matchmaker, SessionStore)>,
+    Path(session_key): Path<String>,
+    Json(payload): Json<CreateRoomRequestForm>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    match join_queue(&matchmaker, user_id, payload).await {
+        Ok(status) => (StatusCode::ACCEPTED, Json(status.borrow().clone())).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/matchmaking/session/{session_key}",
+    tag = "matchmaking",
+    params(
+        ("session_key" = String, Path, description = "세션 키")
+    ),
+    responses(
+        (status = 200, description = "매칭 상태 조회 성공", body = MatchStatus),
+        (status = 400, description = "세션 키가 올바르지 않음"),
+        (status = 404, description = "대기열에 없음"),
+    )
+)]
+pub async fn get_matchmaking_status(
+    State((matchmaker, session_store)): State<(Matchmaker, SessionStore)>,
+    Path(session_key): Path<String>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    match matchmaker.lock().await.status(user_id) {
+        Some(status) => (StatusCode::OK, Json(status)).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/matchmaking/session/{session_key}",
+    tag = "matchmaking",
+    params(
+        ("session_key" = String, Path, description = "세션 키")
+    ),
+    responses(
+        (status = 200, description = "대기열 취소 성공"),
+        (status = 400, description = "세션 키가 올바르지 않음"),
+        (status = 404, description = "대기 중이 아님 (이미 매칭됐거나 참가하지 않음)"),
+    )
+)]
+pub async fn cancel_matchmaking(
+    State((matchmaker, session_store)): State<(Matchmaker, SessionStore)>,
+    Path(session_key): Path<String>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    if matchmaker.lock().await.cancel(user_id) {
+        #[cfg(debug_assertions)]
+        println!("매칭 대기열 취소: user_id={}", user_id);
+        StatusCode::OK.into_response()
+    } else {
+        StatusCode::NOT_FOUND.into_response()
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws/matchmaking/session/{session_key}",
+    tag = "matchmaking",
+    params(
+        ("session_key" = String, Path, description = "세션 키")
+    ),
+    responses(
+        (status = 101, description = "WebSocket 연결 성공: 첫 메시지로 CreateRoomRequestForm(JSON)을 보내면 MatchStatus(JSON)를 받는다"),
+        (status = 400, description = "유효하지 않은 세션 키"),
+    )
+)]
+pub async fn matchmaking_socket(
+    ws: WebSocketUpgrade,
+    Path(session_key): Path<String>,
+    State((matchmaker, session_store)): State<(Matchmaker, SessionStore)>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    ws.on_upgrade(move |socket| handle_matchmaking_socket(socket, matchmaker, user_id))
+}
+
+async fn handle_matchmaking_socket(socket: ws::WebSocket, matchmaker: Matchmaker, user_id: UserID) {
+    use ws::Message;
+
+    let (mut ws_tx, mut ws_rx) = socket.split();
+
+    // 첫 메시지: 매칭 설정
+    let form = match ws_rx.next().await {
+        Some(Ok(Message::Text(text))) => serde_json::from_str::<CreateRoomRequestForm>(&text),
+        _ => return,
+    };
+    let mut status = match form {
+        Ok(form) => match join_queue(&matchmaker, user_id, form).await {
+            Ok(status) => status,
+            Err((_, message)) => {
+                let _ = ws_tx.send(Message::Text(message.into())).await;
+                return;
+            }
+        },
+        Err(e) => {
+            let _ = ws_tx.send(Message::Text(e.to_string().into())).await;
+            return;
+        }
+    };
+
+    loop {
+        let current = status.borrow_and_update().clone();
+        let json = match serde_json::to_string(&current) {
+            Ok(json) => json,
+            Err(_) => break,
+        };
+        if ws_tx.send(Message::Text(json.into())).await.is_err() {
+            break;
+        }
+        if !matches!(current, MatchStatus::Waiting { .. }) {
+            // 매칭·취소가 끝나면 연결 종료
+            let _ = ws_tx.close().await;
+            return;
+        }
+
+        tokio::select! {
+            changed = status.changed() => if changed.is_err() { break },
+            message = ws_rx.next() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+
+    // 매칭 전에 연결이 끊기면 대기열에서 뺀다
+    matchmaker.lock().await.cancel(user_id);
+}
+
+pub fn matchmaking_router() -> OpenApiRouter<(Matchmaker, SessionStore)> {
+    OpenApiRouter::new()
+        .routes(routes!(
+            join_matchmaking,
+            get_matchmaking_status,
+            cancel_matchmaking
+        ))
+        .routes(routes!(matchmaking_socket))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::RoomGameForm;
+    use game_core::baduk_board::BadukBoardGameConfig;
+
+    fn ticket(user_id: u64, rating: i32, joined_at: Instant, main_time: u32) -> MatchTicket {
+        let config = BadukBoardGameConfig::new(main_time, 0, 3, 30_000);
+        MatchTicket {
+            user_id: UserID(user_id),
+            rating,
+            form: CreateRoomRequestForm::new(RoomGameForm::Baduk(config), true),
+            joined_at,
+            status: watch::channel(MatchStatus::Cancelled).0,
+        }
+    }
+
+    #[test]
+    fn find_pairs_test() {
+        let now = Instant::now();
+        let queue = vec![
+            ticket(1, 1500, now, 600_000),
+            ticket(2, 1900, now, 600_000),
+            ticket(3, 1550, now, 600_000),
+            ticket(4, 1500, now, 1_200_000),
+        ];
+        // 같은 시간 설정이고 허용 범위 안인 1·3만 짝
+        assert_eq!(find_pairs(&queue, now), vec![(0, 2)]);
+
+        // 오래 기다리면 범위가 넓어져 1900 과도 짝이 된다
+        let queue = vec![
+            ticket(1, 1500, now - Duration::from_secs(60), 600_000),
+            ticket(2, 1900, now - Duration::from_secs(60), 600_000),
+        ];
+        assert_eq!(find_pairs(&queue, now), vec![(0, 1)]);
+        assert_eq!(rating_window(Duration::from_secs(3600)), MAX_WINDOW);
+    }
+}
@@ -1,147 +1,429 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    sync::{Arc, atomic::AtomicU16},
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicU16, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{Engine as _, engine::general_purpose};
+
 use axum::{Json, extract::State, response::IntoResponse};
-use doljabiproto::common::{ClientToServer, ServerToClient};
+use doljabiproto::common::{
+    ClientToServer, Error, ErrorCode, ServerToClient, server_to_client::GameData,
+};
 use game_core::{UserID, baduk_board::BadukBoardGameConfig};
 use hyper::StatusCode;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{Mutex, broadcast, mpsc};
+use tokio::sync::{Mutex, broadcast, mpsc, watch};
 use utoipa::ToSchema;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::game_logic::{
-    baduk_board::{baduk_room::BadukRoom, omok_room::OmokRoom},
-    timer::{GameInterrupter, TimerManager},
+use crate::{
+    game_logic::{
+        baduk_board::{baduk_room::BadukRoom, check_rated_time_control, omok_room::OmokRoom},
+        feed::{FeedHistory, RoomFeed},
+        lobby::{Lobby, LobbyRoom, RoomStatus},
+        timer::{GameInterrupter, TimerManager},
+    },
+    soyul::kibo::GameKind,
 };
 
 pub mod baduk_board;
+pub mod chat;
+pub mod feed;
+pub mod lobby;
+pub mod matchmaking;
 pub mod timer;
 
-#[derive(Hash, PartialEq, Eq)]
+/// 관전 딜레이 최대치(수)
+const MAX_SPECTATOR_DELAY: u32 = 30;
+
+/// 대국 중 접속이 끊긴 플레이어를 기다리는 기본 시간(초)
+const DEFAULT_DISCONNECT_GRACE_SECS: u32 = 60;
+/// 접속 유예 시간 범위(초)
+const DISCONNECT_GRACE_RANGE: std::ops::RangeInclusive<u32> = 10..=600;
+
+/// 비공개 방에 비밀번호·초대 토큰을 이만큼 연달아 틀리면 입장 확인을 잠근다
+const MAX_FAILED_ENTRIES: u32 = 5;
+/// 잠긴 방은 이 시간 동안 맞는 자격도 받지 않는다
+const FAILED_ENTRY_LOCKOUT: Duration = Duration::from_secs(60);
+
+/// 입장 코드 길이
+const ENTER_CODE_LEN: usize = 6;
+/// 입장 코드 문자. 헷갈리기 쉬운 0/O, 1/I/L 은 뺐다
+const ENTER_CODE_CHARS: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+
+#[derive(Hash, PartialEq, Eq, Clone, Debug)]
 pub struct EnterCode {
-    code: u16,
+    code: String,
 }
 impl EnterCode {
-    fn new(code: u16) -> Self {
-        Self { code: code }
+    /// 무작위 입장 코드 (6자리 영문 대문자·숫자)
+    fn random() -> Self {
+        let code = (0..ENTER_CODE_LEN)
+            .map(|_| {
+                let index = OsRng.next_u32() as usize % ENTER_CODE_CHARS.len();
+                ENTER_CODE_CHARS[index] as char
+            })
+            .collect();
+        Self { code }
+    }
+    pub fn as_str(&self) -> &str {
+        &self.code
     }
-    pub fn as_u16(&self) -> u16 {
-        self.code
+}
+impl From<&str> for EnterCode {
+    fn from(code: &str) -> Self {
+        Self {
+            code: normalize_enter_code(code),
+        }
     }
 }
 
+/// 사용자가 입력한 입장 코드 정리 (앞뒤 공백 제거, 대문자로)
+pub fn normalize_enter_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// 비공개 방 초대 토큰 (128비트 난수, URL-safe Base64)
+fn generate_invite_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// 방에 들어온 WebSocket 연결 하나. 같은 유저의 여러 연결(탭)을 구분한다
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectionID(u64);
+
 #[derive(Clone, Debug)]
 pub enum SystemEvent {
     TimerInterrupt(Arc<AtomicU16>),
-    EnterUser(UserID),
+    EnterUser(UserID, ConnectionID),
     LeaveUser(UserID),
+    /// 소켓이 잰 유저의 왕복 지연(ms)
+    Latency(UserID, u32),
     Close,
 }
 
 #[derive(Clone, Debug)]
 pub enum InputMessage {
     System(SystemEvent),
-    Request((UserID, ClientToServer)),
+    Request((UserID, ConnectionID, ClientToServer)),
+}
+
+/// 요청을 보낸 연결에만 보내는 에러 메시지
+pub fn error_response(code: ErrorCode) -> ServerToClient {
+    let message = match code {
+        ErrorCode::Unspecified => "알 수 없는 에러",
+        ErrorCode::NotYourTurn => "차례가 아닙니다",
+        ErrorCode::Occupied => "이미 돌이 있는 자리입니다",
+        ErrorCode::Forbidden => "금수입니다",
+        ErrorCode::Ko => "패: 바로 되따낼 수 없습니다",
+        ErrorCode::GameNotStarted => "대국 중이 아닙니다",
+        ErrorCode::NotSeated => "관전자는 요청할 수 없습니다",
+        ErrorCode::RateLimited => "요청이 너무 잦습니다",
+        ErrorCode::InvalidRequest => "잘못된 요청입니다",
+        ErrorCode::UnsupportedVersion => "지원하지 않는 프로토콜 버전입니다. 새로고침해 주세요",
+        ErrorCode::AccessDenied => "비밀번호나 초대 토큰이 맞지 않습니다",
+    };
+    ServerToClient {
+        game_data: Some(GameData::Error(Error {
+            code: code as i32,
+            message: message.to_string(),
+        })),
+        ..Default::default()
+    }
 }
 
 pub trait GameLogic: Send + Sync {
-    fn send(&mut self, user_id: UserID, message: ClientToServer) -> ServerToClient;
-    fn enter_user(&mut self, user_id: UserID) -> ServerToClient;
+    /// 요청 처리. 거절하면 에러 코드를 돌려주고, 요청을 보낸 연결에만 알린다.
+    /// 방 전체에 보낼 메시지가 없으면(채팅 등 take_direct 로만 보내는 경우) Ok(None)
+    fn send(
+        &mut self,
+        user_id: UserID,
+        message: ClientToServer,
+    ) -> Result<Option<ServerToClient>, ErrorCode>;
+    /// 입장. connection 은 방금 들어온 연결 (그 연결에만 보낼 메시지는 take_direct 로)
+    fn enter_user(&mut self, user_id: UserID, connection: ConnectionID) -> ServerToClient;
     fn leave_user(&mut self, user_id: UserID) -> ServerToClient;
     fn timer_interrupt(&mut self, event: u16) -> ServerToClient;
+    /// 관전자에게 보낼 메시지 (관전 딜레이 등). None 이면 관전자도 같은 메시지를 받는다
+    fn spectator_view(&self, message: &ServerToClient) -> Option<ServerToClient>;
+    /// 로비에 공개할 현재 방 상태
+    fn room_status(&self) -> RoomStatus;
+    /// 소켓이 잰 왕복 지연 (랙 보정용). 방에 알리기만 하고 메시지는 보내지 않는다
+    fn update_latency(&mut self, user_id: UserID, rtt_ms: u32);
+    /// 처리 중에 쌓인, 순번 없이 보낼 메시지 (특정 유저·연결에만 보내는 것, 채팅).
+    /// 게임 노드가 처리마다 꺼내 가서 방 전체 메시지보다 먼저 보낸다
+    fn take_direct(&mut self) -> Vec<(Audience, ServerToClient)>;
+}
+
+/// 방 메시지를 받을 대상
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Audience {
+    All,
+    /// 자리에 앉은 플레이어만
+    Players,
+    /// 관전자만
+    Spectators,
+    /// 이 유저의 모든 연결
+    User(UserID),
+    /// 연결 하나
+    Connection(ConnectionID),
+}
+
+/// 방 메시지를 받는 연결 하나
+#[derive(Clone, Copy, Debug)]
+pub struct Recipient {
+    pub user_id: UserID,
+    pub connection: ConnectionID,
+    /// 자리에 앉았는지 (플레이어용·관전자용 메시지 구분)
+    pub seated: bool,
+}
+
+/// 게임 노드가 방의 모든 연결에 뿌리는 메시지. 각 연결이 audience 를 보고 걸러 낸다
+#[derive(Debug)]
+pub struct RoomMessage {
+    pub audience: Audience,
+    /// 전체 메시지 (seq 포함)
+    pub message: ServerToClient,
+    /// 바둑판을 뺀 델타. 델타로 보낼 수 없는 메시지면 None
+    pub delta: Option<ServerToClient>,
+}
+impl RoomMessage {
+    /// 델타를 받는 연결이면 델타를, 아니면 전체 메시지를
+    pub fn message_for(&self, delta: bool) -> &ServerToClient {
+        match (&self.delta, delta) {
+            (Some(message), true) => message,
+            _ => &self.message,
+        }
+    }
+
+    /// 이 연결이 이 메시지를 받아야 하는지
+    pub fn is_for(&self, recipient: &Recipient) -> bool {
+        match self.audience {
+            Audience::All => true,
+            Audience::Players => recipient.seated,
+            Audience::Spectators => !recipient.seated,
+            Audience::User(user_id) => user_id == recipient.user_id,
+            Audience::Connection(connection) => connection == recipient.connection,
+        }
+    }
+}
+
+/// 비공개 방 입장 조건. 둘 다 None 이면 공개 방
+#[derive(Clone, Default)]
+pub struct RoomAccess {
+    password: Option<String>,
+    invite_token: Option<String>,
+    /// 연달아 틀린 횟수
+    failures: u32,
+    /// 틀린 시도가 많아 입장 확인을 잠가 둔 시각
+    locked_until: Option<Instant>,
+}
+impl RoomAccess {
+    pub fn is_public(&self) -> bool {
+        self.password.is_none() && self.invite_token.is_none()
+    }
+
+    /// 비밀번호나 초대 토큰 중 하나가 맞으면 입장 가능.
+    /// MAX_FAILED_ENTRIES 번 연달아 틀리면 FAILED_ENTRY_LOCKOUT 동안 맞는 자격도 거절한다
+    pub fn check(&mut self, password: Option<&str>, invite_token: Option<&str>) -> bool {
+        if self.is_public() {
+            return true;
+        }
+        let now = Instant::now();
+        if self.locked_until.is_some_and(|until| now < until) {
+            return false;
+        }
+
+        let matches = |expected: &Option<String>, given: Option<&str>| match (expected, given) {
+            (Some(expected), Some(given)) => {
+                constant_time_eq(expected.as_bytes(), given.as_bytes())
+            }
+            _ => false,
+        };
+        // 어느 쪽이 맞았는지 시간으로 드러나지 않도록 둘 다 비교한다
+        let password_ok = matches(&self.password, password);
+        let invite_ok = matches(&self.invite_token, invite_token);
+        if password_ok | invite_ok {
+            self.failures = 0;
+            return true;
+        }
+
+        self.failures += 1;
+        if self.failures >= MAX_FAILED_ENTRIES {
+            self.failures = 0;
+            self.locked_until = Some(now + FAILED_ENTRY_LOCKOUT);
+        }
+        false
+    }
+}
+
+/// 길이가 같으면 어디서 달라지든 같은 시간이 걸리는 비교
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
 
 pub struct RoomChannels {
     input: mpsc::Sender<InputMessage>,
-    output: broadcast::Sender<Arc<ServerToClient>>,
+    output: broadcast::Sender<Arc<RoomMessage>>,
+    /// 게임 노드가 메시지를 처리할 때마다 갱신하는 좌석·관전자 현황
+    status: watch::Receiver<RoomStatus>,
+    /// 최근 방 메시지 (Resync 용)
+    history: FeedHistory,
+    access: RoomAccess,
+    /// 게임 종류 (봇 API 가 요청을 Baduk·Omok 중 어디에 실을지)
+    game: GameKind,
+    /// 최대 관전자 수. None 이면 제한 없음, 0 이면 관전 불가
+    max_spectators: Option<u32>,
+    /// 다음에 들어오는 연결의 번호
+    next_connection: AtomicU64,
+}
+impl RoomChannels {
+    /// 새로 들어오면 관전자가 되는데 관전석이 꽉 찼는지.
+    /// 이미 앉아 있는 유저나 빈 자리가 있을 때는 false
+    fn spectators_full(&self, user_id: UserID) -> bool {
+        let status = *self.status.borrow();
+        match (status.black, status.white) {
+            (Some(black), Some(white)) if black != user_id && white != user_id => self
+                .max_spectators
+                .is_some_and(|max| status.spectators >= max),
+            _ => false,
+        }
+    }
 }
 
 pub struct EnterCodeManagement {
-    counter: u16,
-    released_number: VecDeque<u16>,
+    in_use: HashSet<EnterCode>,
 }
 
 impl EnterCodeManagement {
+    /// 겹치지 않는 코드를 찾을 때까지 다시 뽑는 횟수
+    const MAX_ATTEMPTS: usize = 16;
+
     fn new() -> Self {
         Self {
-            counter: u16::MAX,
-            released_number: VecDeque::<u16>::from([u16::MAX]),
+            in_use: HashSet::new(),
         }
     }
 
     fn get(&mut self) -> Option<EnterCode> {
-        match self.released_number.pop_front() {
-            Some(num) => Some(EnterCode::new(num)),
-            None => match self.counter.checked_sub(1) {
-                Some(num) => {
-                    self.counter = num;
-                    Some(EnterCode::new(num))
-                }
-                None => None,
-            },
+        for _ in 0..Self::MAX_ATTEMPTS {
+            let enter_code = EnterCode::random();
+            if self.in_use.insert(enter_code.clone()) {
+                return Some(enter_code);
+            }
         }
+        None
     }
 
     fn release(&mut self, enter_code: EnterCode) {
-        self.released_number.push_back(enter_code.as_u16());
-    }
-
-    pub fn check_overlap_in_released_number(&self) {
-        let set: HashSet<u16> = self.released_number.iter().cloned().collect();
-        if set.len() != self.released_number.len() {
-            eprintln!("Error: EnterCodeManagement 중복 발생! => 시일 내 디버그 하기!");
-        }
-        println!("✅ EnterCodeManagement: 무결성 검사 완료");
+        self.in_use.remove(&enter_code);
     }
 }
 
 pub struct RoomManagement {
     enter_code_management: EnterCodeManagement,
-    room_channels_list: HashMap<u16, RoomChannels>,
+    room_channels_list: HashMap<EnterCode, RoomChannels>,
+    lobby: Lobby,
 }
 impl RoomManagement {
     pub fn new() -> Self {
         Self {
             enter_code_management: EnterCodeManagement::new(),
-            room_channels_list: HashMap::<u16, RoomChannels>::new(),
+            room_channels_list: HashMap::<EnterCode, RoomChannels>::new(),
+            lobby: Lobby::new(),
         }
     }
 
+    /// 열려 있는 방 요약을 모아 두는 공개 로비
+    pub fn lobby(&self) -> Lobby {
+        self.lobby.clone()
+    }
+
     pub fn get_enter_code(&mut self) -> Option<EnterCode> {
         self.enter_code_management.get()
     }
 
-    pub fn register_room(&mut self, enter_code: u16, room_channels: RoomChannels) {
+    pub fn register_room(&mut self, enter_code: EnterCode, room_channels: RoomChannels) {
         self.room_channels_list.insert(enter_code, room_channels);
     }
 
     pub fn release_enter_code(&mut self, enter_code: EnterCode) {
-        self.room_channels_list.remove(&enter_code.as_u16());
+        self.room_channels_list.remove(&enter_code);
         self.enter_code_management.release(enter_code);
     }
 
-    pub fn get_channels(
-        &self,
-        enter_code: u16,
-    ) -> Option<(
-        mpsc::Sender<InputMessage>,
-        broadcast::Receiver<Arc<ServerToClient>>,
-    )> {
-        match self.room_channels_list.get(&enter_code) {
-            Some(channel) => Some((channel.input.clone(), channel.output.subscribe())),
-            None => None,
-        }
+    /// 비밀번호·초대 토큰이 있어야 들어가는 방인지. 없는 방이면 None
+    pub fn is_private_room(&self, enter_code: &str) -> Option<bool> {
+        self.room_channels_list
+            .get(&EnterCode::from(enter_code))
+            .map(|channel| !channel.access.is_public())
+    }
+
+    /// 비공개 방 입장 자격 확인 (틀린 횟수도 센다). 없는 방이면 None
+    pub fn check_access(
+        &mut self,
+        enter_code: &str,
+        password: Option<&str>,
+        invite_token: Option<&str>,
+    ) -> Option<bool> {
+        self.room_channels_list
+            .get_mut(&EnterCode::from(enter_code))
+            .map(|channel| channel.access.check(password, invite_token))
+    }
+
+    /// 방의 게임 종류. 없는 방이면 None
+    pub fn game_kind(&self, enter_code: &str) -> Option<GameKind> {
+        self.room_channels_list
+            .get(&EnterCode::from(enter_code))
+            .map(|channel| channel.game)
+    }
+
+    /// 관전석이 꽉 차서 이 유저가 들어갈 수 없는 방인지. 없는 방이면 false
+    pub fn spectators_full(&self, enter_code: &str, user_id: UserID) -> bool {
+        self.room_channels_list
+            .get(&EnterCode::from(enter_code))
+            .is_some_and(|channel| channel.spectators_full(user_id))
+    }
+
+    pub fn get_channels(&self, enter_code: &str) -> Option<RoomConnection> {
+        self.room_channels_list
+            .get(&EnterCode::from(enter_code))
+            .map(|channel| RoomConnection {
+                input: channel.input.clone(),
+                output: channel.output.subscribe(),
+                status: channel.status.clone(),
+                history: channel.history.clone(),
+                connection: ConnectionID(channel.next_connection.fetch_add(1, Ordering::Relaxed)),
+            })
     }
 }
 
 pub type RoomManager = Arc<Mutex<RoomManagement>>;
 
+/// 방에 들어간 연결 하나가 쓰는 채널
+pub struct RoomConnection {
+    /// 요청 입력
+    pub input: mpsc::Sender<InputMessage>,
+    /// 방 메시지 구독
+    pub output: broadcast::Receiver<Arc<RoomMessage>>,
+    /// 좌석·관전자 현황
+    pub status: watch::Receiver<RoomStatus>,
+    /// 최근 방 메시지 (Resync 용)
+    pub history: FeedHistory,
+    /// 이 연결의 방 안 번호
+    pub connection: ConnectionID,
+}
+
 #[derive(Deserialize, Serialize, ToSchema, Clone, Copy)]
 #[serde(tag = "game_type", content = "game_config")]
-pub enum CreateRoomRequestForm {
+pub enum RoomGameForm {
     #[serde(rename = "baduk")]
     Baduk(BadukBoardGameConfig),
 
@@ -149,45 +431,114 @@ pub enum CreateRoomRequestForm {
     Omok(BadukBoardGameConfig),
 }
 
-#[derive(Deserialize, Serialize, ToSchema, Clone, Copy)]
-pub struct CreateRoomResponseForm {
-    enter_code: u16,
+#[derive(Deserialize, Serialize, ToSchema, Clone)]
+pub struct CreateRoomRequestForm {
+    #[serde(flatten)]
+    game: RoomGameForm,
+    /// 레이팅 대국 여부. 생략 시 시간 설정이 레이팅 기준에 맞으면 레이팅 대국,
+    /// 아니면 친선 대국(기보만 저장, 레이팅·전적 미반영). 랙 보정을 켰으면 친선 대국
+    #[serde(default)]
+    rated: Option<bool>,
+    /// 비공개 방 여부. 비공개 방은 로비에 보이지 않고, 초대 토큰이나 비밀번호가 있어야 입장할 수 있다
+    #[serde(default)]
+    private: bool,
+    /// 비공개 방 비밀번호. 지정하면 private 가 아니어도 비공개 방이 된다
+    #[serde(default)]
+    password: Option<String>,
+    /// 최대 관전자 수. 생략 시 제한 없음, 0 이면 관전 불가
+    #[serde(default)]
+    max_spectators: Option<u32>,
+    /// 관전 딜레이(수). 레이팅 대국에서만 쓸 수 있고, 관전자는 이만큼 전 국면을 본다
+    #[serde(default)]
+    spectator_delay: u32,
+    /// 대국 중 접속이 끊긴 플레이어의 자리를 유지하는 시간(초, 10~600). 생략 시 60초.
+    /// 지나면 상대가 승리를 요청할 수 있고, 양쪽 다 끊겨 있으면 대국을 중단한다
+    #[serde(default)]
+    disconnect_grace_secs: Option<u32>,
+    /// 랙 보정. 켜면 착수한 플레이어에게 측정한 네트워크 지연(편도, 최대 1초)만큼 메인 시간을 돌려준다.
+    /// 지연은 클라이언트가 늦게 답해 부풀릴 수 있으므로 친선 대국에서만 켤 수 있다
+    #[serde(default)]
+    lag_compensation: bool,
 }
-impl CreateRoomResponseForm {
-    pub fn new(enter_code: u16) -> Self {
-        Self {
-            enter_code: enter_code,
-        }
-    }
+
+/// 방(BadukRoom/OmokRoom)이 만들어질 때 받는 설정
+#[derive(Clone, Copy, Debug)]
+pub struct RoomSettings {
+    pub rated: bool,
+    pub spectator_delay: u32,
+    pub disconnect_grace: Duration,
+    pub lag_compensation: bool,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone)]
+pub struct CreateRoomResponseForm {
+    pub enter_code: String,
+    /// 비공개 방 초대 토큰. 공개 방이면 null
+    pub invite_token: Option<String>,
 }
 
 pub async fn run_game_node<G: GameLogic>(
     mut game: G,
     enter_code: EnterCode,
     room_manager: RoomManager,
+    mut lobby_room: LobbyRoom,
 
     mut mpsc_rx: mpsc::Receiver<InputMessage>,
-    broadcast_tx: broadcast::Sender<Arc<ServerToClient>>,
+    status_tx: watch::Sender<RoomStatus>,
+    mut feed: RoomFeed,
 ) {
+    lobby_room.update(game.room_status()).await;
+
     while let Some(input_message) = mpsc_rx.recv().await {
         #[cfg(debug_assertions)]
         println!("{:#?}", input_message);
-        if let Err(e) = broadcast_tx.send(Arc::new(match input_message {
-            InputMessage::Request((user_id, message)) => game.send(user_id, message),
-            InputMessage::System(SystemEvent::EnterUser(user_id)) => game.enter_user(user_id),
-            InputMessage::System(SystemEvent::LeaveUser(user_id)) => game.leave_user(user_id),
+        let message = match input_message {
+            InputMessage::Request((user_id, connection, message)) => {
+                match game.send(user_id, message) {
+                    Ok(message) => message,
+                    Err(code) => {
+                        // 거절은 요청을 보낸 연결에만 알린다
+                        feed.send_direct(Audience::Connection(connection), error_response(code));
+                        continue;
+                    }
+                }
+            }
+            InputMessage::System(SystemEvent::EnterUser(user_id, connection)) => {
+                Some(game.enter_user(user_id, connection))
+            }
+            InputMessage::System(SystemEvent::LeaveUser(user_id)) => Some(game.leave_user(user_id)),
+            InputMessage::System(SystemEvent::Latency(user_id, rtt_ms)) => {
+                game.update_latency(user_id, rtt_ms);
+                continue;
+            }
             InputMessage::System(SystemEvent::TimerInterrupt(event_id)) => {
                 match event_id.load(std::sync::atomic::Ordering::Relaxed) {
                     0 => continue,
-                    event => game.timer_interrupt(event),
+                    event => Some(game.timer_interrupt(event)),
                 }
             }
             InputMessage::System(SystemEvent::Close) => break,
-        })) {
-            eprintln!("게임 메시지 전송 에러!!! {}", e);
         };
+
+        // 각 연결이 자리 여부로 메시지를 거르므로, 좌석 현황을 먼저 갱신한다
+        let status = game.room_status();
+        status_tx.send_replace(status);
+
+        for (audience, direct) in game.take_direct() {
+            feed.send_direct(audience, direct);
+        }
+
+        if let Some(message) = message {
+            let outputs = match game.spectator_view(&message) {
+                Some(view) => vec![(Audience::Players, message), (Audience::Spectators, view)],
+                None => vec![(Audience::All, message)],
+            };
+            feed.publish(outputs);
+        }
+        lobby_room.update(status).await;
     }
 
+    lobby_room.close().await;
     let mut manager = room_manager.lock().await;
     manager.release_enter_code(enter_code);
 
@@ -195,48 +546,179 @@ pub async fn run_game_node<G: GameLogic>(
     println!("방 종료 성공");
 }
 
-#[utoipa::path(
-    post,
-    path = "/api/room/create",
-    request_body = CreateRoomRequestForm,
-    responses(
-        (status = 201, description = "방 생성 성공", body = CreateRoomResponseForm),
-        (status = 400, description = "잘못된 요청"),
-        (status = 500, description = "서버 오류"),
-    )
-)]
-pub async fn create_room_request(
-    State((room_manager, timer_manager)): State<(RoomManager, TimerManager)>,
-    Json(payload): Json<CreateRoomRequestForm>,
-) -> impl IntoResponse {
+impl RoomGameForm {
+    pub fn game_kind(&self) -> GameKind {
+        match self {
+            RoomGameForm::Baduk(_) => GameKind::Baduk,
+            RoomGameForm::Omok(_) => GameKind::Omok,
+        }
+    }
+
+    pub fn game_config(&self) -> BadukBoardGameConfig {
+        match self {
+            RoomGameForm::Baduk(config) | RoomGameForm::Omok(config) => *config,
+        }
+    }
+
+    /// 게임 종류와 시간 설정이 모두 같은지
+    pub fn same_settings(&self, other: &RoomGameForm) -> bool {
+        self.game_kind() == other.game_kind()
+            && self.game_config().output() == other.game_config().output()
+    }
+}
+
+impl CreateRoomRequestForm {
+    pub fn new(game: RoomGameForm, rated: bool) -> Self {
+        Self {
+            game,
+            rated: Some(rated),
+            private: false,
+            password: None,
+            max_spectators: None,
+            spectator_delay: 0,
+            disconnect_grace_secs: None,
+            lag_compensation: false,
+        }
+    }
+
+    /// 같은 설정의 비공개 방 (비밀번호 없이 초대 토큰으로만 입장)
+    pub fn to_private(&self) -> Self {
+        Self {
+            private: true,
+            password: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.private || self.password.is_some()
+    }
+
+    pub fn game(&self) -> RoomGameForm {
+        self.game
+    }
+
+    /// 레이팅 대국인지. 지정하지 않았으면 친선 대국 전용 설정(랙 보정)이 없고
+    /// 시간 설정이 레이팅 기준에 맞을 때 레이팅 대국
+    pub fn rated(&self) -> bool {
+        self.rated.unwrap_or_else(|| {
+            !self.lag_compensation
+                && check_rated_time_control(self.game.game_kind(), self.game.game_config()).is_ok()
+        })
+    }
+
+    pub fn max_spectators(&self) -> Option<u32> {
+        self.max_spectators
+    }
+
+    pub fn spectator_delay(&self) -> u32 {
+        self.spectator_delay
+    }
+
+    /// 방 설정
+    pub fn settings(&self) -> RoomSettings {
+        RoomSettings {
+            rated: self.rated(),
+            spectator_delay: self.spectator_delay,
+            disconnect_grace: Duration::from_secs(
+                self.disconnect_grace_secs
+                    .unwrap_or(DEFAULT_DISCONNECT_GRACE_SECS) as u64,
+            ),
+            lag_compensation: self.lag_compensation,
+        }
+    }
+
+    /// 방을 만들 수 있는 설정인지 검사. 레이팅 대국은 표준 시간 설정만 허용하고 랙 보정을 막는다
+    pub fn check(&self) -> Result<(), String> {
+        let rated = self.rated();
+        if rated {
+            check_rated_time_control(self.game.game_kind(), self.game.game_config())?;
+        }
+        if rated && self.lag_compensation {
+            return Err("레이팅 대국에서는 랙 보정을 켤 수 없습니다".to_string());
+        }
+        if self.password.as_ref().is_some_and(|p| p.is_empty()) {
+            return Err("비밀번호가 비어 있습니다".to_string());
+        }
+        if self.spectator_delay > 0 && !rated {
+            return Err("관전 딜레이는 레이팅 대국에서만 설정할 수 있습니다".to_string());
+        }
+        if self.spectator_delay > MAX_SPECTATOR_DELAY {
+            return Err(format!(
+                "관전 딜레이는 {}수 이하여야 합니다",
+                MAX_SPECTATOR_DELAY
+            ));
+        }
+        if let Some(secs) = self.disconnect_grace_secs
+            && !DISCONNECT_GRACE_RANGE.contains(&secs)
+        {
+            return Err(format!(
+                "접속 유예 시간은 {}~{}초여야 합니다",
+                DISCONNECT_GRACE_RANGE.start(),
+                DISCONNECT_GRACE_RANGE.end()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 방을 만들고 게임 노드를 띄운 뒤 입장 코드(비공개 방이면 초대 토큰도)를 돌려준다.
+/// 겹치지 않는 입장 코드를 못 뽑으면 None
+///
+/// 방 생성 API와 자동 매칭이 같은 경로로 방을 연다.
+pub async fn open_room(
+    room_manager: &RoomManager,
+    timer_manager: &TimerManager,
+    form: CreateRoomRequestForm,
+) -> Option<CreateRoomResponseForm> {
     let (mpsc_tx, mpsc_rx) = mpsc::channel::<InputMessage>(32);
-    let (broadcast_tx, _) = broadcast::channel::<Arc<ServerToClient>>(32);
+    let (broadcast_tx, _) = broadcast::channel::<Arc<RoomMessage>>(32);
+    let (status_tx, status_rx) = watch::channel(RoomStatus::default());
+    let history = FeedHistory::default();
 
     let game_timer = GameInterrupter {
         sender: timer_manager.clone(),
         receiver: mpsc_tx.clone(),
     };
 
-    let (enter_code_u16, enter_code) = {
+    let access = match form.is_private() {
+        true => RoomAccess {
+            password: form.password.clone(),
+            invite_token: Some(generate_invite_token()),
+            ..Default::default()
+        },
+        false => RoomAccess::default(),
+    };
+    let invite_token = access.invite_token.clone();
+
+    let (enter_code, lobby) = {
         let mut manager = room_manager.lock().await;
-        let enter_code = match manager.get_enter_code() {
-            Some(code) => code,
-            None => {
-                eprintln!("방 생성 실패: EnterCode 생성 실패");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
-        };
+        let enter_code = manager.get_enter_code()?;
         manager.register_room(
-            enter_code.as_u16(),
+            enter_code.clone(),
             RoomChannels {
                 input: mpsc_tx,
                 output: broadcast_tx.clone(),
+                status: status_rx,
+                history: history.clone(),
+                access,
+                game: form.game.game_kind(),
+                max_spectators: form.max_spectators,
+                next_connection: AtomicU64::new(1),
             },
         );
-        (enter_code.as_u16(), enter_code)
+        (enter_code, manager.lobby())
+    };
+    let response = CreateRoomResponseForm {
+        enter_code: enter_code.as_str().to_string(),
+        invite_token,
     };
 
     let manager = room_manager.clone();
+    let (game, settings) = (form.game, form.settings());
+    #[cfg(debug_assertions)]
+    let private = form.is_private();
+    let lobby_room = LobbyRoom::new(lobby, response.enter_code.clone(), form);
 
     macro_rules! spawn_node {
         ($game: expr) => {
@@ -244,31 +726,324 @@ pub async fn create_room_request(
                 $game,
                 enter_code,
                 manager,
+                lobby_room,
                 mpsc_rx,
-                broadcast_tx,
+                status_tx,
+                RoomFeed::new(broadcast_tx, history),
             ));
         };
     }
 
-    match payload {
-        CreateRoomRequestForm::Baduk(config) => {
-            spawn_node!(BadukRoom::new(config, game_timer));
+    match game {
+        RoomGameForm::Baduk(config) => {
+            spawn_node!(BadukRoom::new(config, settings, game_timer));
         }
-        CreateRoomRequestForm::Omok(config) => {
-            spawn_node!(OmokRoom::new(config, game_timer));
+        RoomGameForm::Omok(config) => {
+            spawn_node!(OmokRoom::new(config, settings, game_timer));
         }
     };
 
     #[cfg(debug_assertions)]
-    println!("{}: 방 생성 성공", enter_code_u16);
+    println!(
+        "{}: 방 생성 성공 ({}{})",
+        response.enter_code,
+        if settings.rated {
+            "레이팅"
+        } else {
+            "친선"
+        },
+        if private { ", 비공개" } else { "" }
+    );
 
-    (
-        StatusCode::CREATED,
-        Json(CreateRoomResponseForm::new(enter_code_u16)),
+    Some(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/room/create",
+    request_body = CreateRoomRequestForm,
+    responses(
+        (status = 201, description = "방 생성 성공", body = CreateRoomResponseForm),
+        (status = 400, description = "잘못된 요청 (레이팅 대국에 허용되지 않는 시간 설정 등)"),
+        (status = 500, description = "서버 오류"),
     )
-        .into_response()
+)]
+pub async fn create_room_request(
+    State((room_manager, timer_manager)): State<(RoomManager, TimerManager)>,
+    Json(payload): Json<CreateRoomRequestForm>,
+) -> impl IntoResponse {
+    if let Err(message) = payload.check() {
+        eprintln!("방 생성 실패: {}", message);
+        return (StatusCode::BAD_REQUEST, message).into_response();
+    }
+
+    match open_room(&room_manager, &timer_manager, payload).await {
+        Some(response) => (StatusCode::CREATED, Json(response)).into_response(),
+        None => {
+            eprintln!("방 생성 실패: EnterCode 생성 실패");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
 }
 
 pub fn create_room_router() -> OpenApiRouter<(RoomManager, TimerManager)> {
     OpenApiRouter::new().routes(routes!(create_room_request))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enter_code_test() {
+        let code = EnterCode::random();
+        assert_eq!(code.as_str().len(), ENTER_CODE_LEN);
+        assert!(code.as_str().bytes().all(|c| ENTER_CODE_CHARS.contains(&c)));
+        assert_eq!(EnterCode::from(" ab3cd9 ").as_str(), "AB3CD9");
+
+        let mut public = RoomAccess::default();
+        assert!(public.check(None, None));
+
+        let mut private = RoomAccess {
+            password: Some("1234".into()),
+            invite_token: Some("token".into()),
+            ..Default::default()
+        };
+        assert!(!private.check(None, None));
+        assert!(!private.check(Some("0000"), Some("wrong")));
+        assert!(private.check(Some("1234"), None));
+        assert!(private.check(None, Some("token")));
+        assert!(!private.check(Some("12345"), None));
+
+        assert!(constant_time_eq(b"token", b"token"));
+        assert!(!constant_time_eq(b"token", b"tokem"));
+        assert!(!constant_time_eq(b"token", b"toke"));
+    }
+
+    #[test]
+    fn room_access_lockout_test() {
+        let mut private = RoomAccess {
+            password: Some("1234".into()),
+            ..Default::default()
+        };
+        for _ in 0..MAX_FAILED_ENTRIES {
+            assert!(!private.check(Some("0000"), None));
+        }
+        // 잠긴 동안은 맞는 비밀번호도 거절
+        assert!(!private.check(Some("1234"), None));
+
+        private.locked_until = Some(Instant::now());
+        assert!(private.check(Some("1234"), None));
+        assert_eq!(private.failures, 0);
+    }
+
+    #[test]
+    fn create_room_rated_test() {
+        let form = |json: &str| serde_json::from_str::<CreateRoomRequestForm>(json).unwrap();
+        let standard = r#""game_type":"baduk","game_config":{"main_time":1800000,"fischer_time":0,"remaining_overtime":3,"overtime":30000}"#;
+        let blitz = r#""game_type":"baduk","game_config":{"main_time":60000,"fischer_time":0,"remaining_overtime":0,"overtime":0}"#;
+
+        // rated 를 생략하면 시간 설정으로 정한다
+        assert!(form(&format!("{{{standard}}}")).rated());
+        assert!(!form(&format!("{{{blitz}}}")).rated());
+        assert!(!form(&format!("{{{standard},\"rated\":false}}")).rated());
+
+        assert!(form(&format!("{{{blitz}}}")).check().is_ok());
+        assert!(
+            form(&format!("{{{blitz},\"rated\":true}}"))
+                .check()
+                .is_err()
+        );
+        // 레이팅 대국에서는 랙 보정을 켤 수 없다
+        assert!(
+            form(&format!(
+                "{{{standard},\"rated\":true,\"lag_compensation\":true}}"
+            ))
+            .check()
+            .is_err()
+        );
+        // rated 를 생략하고 랙 보정을 켜면 친선 대국
+        let casual = form(&format!("{{{standard},\"lag_compensation\":true}}"));
+        assert!(!casual.rated());
+        assert!(casual.check().is_ok());
+    }
+
+    #[test]
+    fn disconnect_grace_range_test() {
+        let standard = r#""game_type":"baduk","game_config":{"main_time":1800000,"fischer_time":0,"remaining_overtime":3,"overtime":30000}"#;
+        let form = |grace: &str| {
+            serde_json::from_str::<CreateRoomRequestForm>(&format!("{{{standard}{grace}}}"))
+                .unwrap()
+        };
+
+        let default = form("");
+        assert!(default.check().is_ok());
+        assert_eq!(
+            default.settings().disconnect_grace,
+            Duration::from_secs(DEFAULT_DISCONNECT_GRACE_SECS as u64)
+        );
+        for secs in [10, 600] {
+            let form = form(&format!(",\"disconnect_grace_secs\":{secs}"));
+            assert!(form.check().is_ok());
+            assert_eq!(
+                form.settings().disconnect_grace,
+                Duration::from_secs(secs as u64)
+            );
+        }
+        for secs in [0, 9, 601] {
+            assert!(
+                form(&format!(",\"disconnect_grace_secs\":{secs}"))
+                    .check()
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn spectators_full_test() {
+        let (status_tx, status_rx) = watch::channel(RoomStatus {
+            black: Some(UserID(1)),
+            white: None,
+            started: false,
+            spectators: 1,
+        });
+        let channels = RoomChannels {
+            input: mpsc::channel(1).0,
+            output: broadcast::channel(1).0,
+            status: status_rx,
+            history: FeedHistory::default(),
+            access: RoomAccess::default(),
+            game: GameKind::Baduk,
+            max_spectators: Some(1),
+            next_connection: AtomicU64::new(1),
+        };
+        // 빈 자리가 있으면 앉을 수 있다
+        assert!(!channels.spectators_full(UserID(3)));
+
+        status_tx.send_modify(|status| status.white = Some(UserID(2)));
+        assert!(channels.spectators_full(UserID(3)));
+        // 앉아 있던 유저의 재접속은 막지 않는다
+        assert!(!channels.spectators_full(UserID(2)));
+
+        status_tx.send_modify(|status| status.spectators = 0);
+        assert!(!channels.spectators_full(UserID(3)));
+
+        let unlimited = RoomChannels {
+            max_spectators: None,
+            ..channels
+        };
+        status_tx.send_modify(|status| status.spectators = 100);
+        assert!(!unlimited.spectators_full(UserID(3)));
+    }
+
+    #[tokio::test]
+    async fn rejected_request_goes_to_sender_test() {
+        use doljabiproto::{
+            badukboard::{BadukBoardClient, ChaksuRequest, baduk_board_client::Payload},
+            common::client_to_server::GameData as ClientData,
+        };
+
+        let room_manager = Arc::new(Mutex::new(RoomManagement::new()));
+        let (timer_manager, _) = mpsc::unbounded_channel();
+        let form: CreateRoomRequestForm = serde_json::from_str(
+            r#"{"game_type":"baduk","game_config":{"main_time":1800000,"fischer_time":0,"remaining_overtime":3,"overtime":30000},"private":true}"#,
+        )
+        .unwrap();
+        let room = open_room(&room_manager, &timer_manager, form)
+            .await
+            .unwrap();
+        let connect = || async {
+            room_manager
+                .lock()
+                .await
+                .get_channels(&room.enter_code)
+                .unwrap()
+        };
+        let mut player = connect().await;
+        let spectator = connect().await;
+
+        // 자리가 빈 방이라 흑·백에 앉힌 뒤 들어온 유저 3 은 관전자
+        for (user_id, connection) in [
+            (1, player.connection),
+            (2, player.connection),
+            (3, spectator.connection),
+        ] {
+            player
+                .input
+                .send(InputMessage::System(SystemEvent::EnterUser(
+                    UserID(user_id),
+                    connection,
+                )))
+                .await
+                .unwrap();
+        }
+        let chaksu = ClientToServer {
+            game_data: Some(ClientData::Baduk(BadukBoardClient {
+                payload: Some(Payload::Coordinate(ChaksuRequest { coordinate: 60 })),
+            })),
+        };
+        player
+            .input
+            .send(InputMessage::Request((
+                UserID(3),
+                spectator.connection,
+                chaksu,
+            )))
+            .await
+            .unwrap();
+
+        let error = loop {
+            let message = tokio::time::timeout(Duration::from_secs(1), player.output.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            if let Some(GameData::Error(error)) = &message.message.game_data {
+                break (message.audience, error.code);
+            }
+        };
+        assert_eq!(
+            error,
+            (
+                Audience::Connection(spectator.connection),
+                ErrorCode::NotSeated as i32
+            )
+        );
+        player
+            .input
+            .send(InputMessage::System(SystemEvent::Close))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn room_message_audience_test() {
+        let message = |audience| RoomMessage {
+            audience,
+            message: ServerToClient::default(),
+            delta: None,
+        };
+        let player = Recipient {
+            user_id: UserID(1),
+            connection: ConnectionID(1),
+            seated: true,
+        };
+        // 같은 유저의 다른 탭
+        let other_tab = Recipient {
+            connection: ConnectionID(2),
+            ..player
+        };
+        let spectator = Recipient {
+            user_id: UserID(3),
+            connection: ConnectionID(3),
+            seated: false,
+        };
+
+        assert!(message(Audience::Players).is_for(&player));
+        assert!(!message(Audience::Players).is_for(&spectator));
+        assert!(message(Audience::Spectators).is_for(&spectator));
+        assert!(message(Audience::User(UserID(1))).is_for(&other_tab));
+        assert!(!message(Audience::User(UserID(1))).is_for(&spectator));
+        assert!(message(Audience::Connection(ConnectionID(1))).is_for(&player));
+        assert!(!message(Audience::Connection(ConnectionID(1))).is_for(&other_tab));
+    }
+}
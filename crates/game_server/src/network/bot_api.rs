@@ -0,0 +1,1194 @@
+// bot_api.rs
+//
+// 봇 HTTP API (lichess bot API 와 같은 모양). WebSocket·Protobuf 클라이언트 없이 HTTP 와 JSON 만으로 봇을 만들 수 있다.
+//  - 봇 계정: 대국 기록이 없는 계정을 봇으로 바꾸고(users.is_bot) API 토큰을 받는다. 봇 요청은 Authorization: Bearer <토큰>
+//  - 도전: 유저가 봇에게 도전하면 비공개 방이 열린다. 봇은 이벤트 스트림(NDJSON)으로 도전을 받아 수락·거절한다
+//  - 대국: 봇은 게임 스트림(NDJSON)을 여는 동안 방에 들어가 있다. 착수·패스·기권·대국 시작은 WebSocket 과 같은
+//    InputMessage::Request 로 방에 들어가고, 거절되면 Error 가 게임 스트림으로 온다.
+//    스트림 한 줄은 JSON WebSocket 과 같은 ServerToClient 하나 (빈 줄은 연결 유지용)
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering::Relaxed},
+    },
+    time::Duration,
+};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::{
+    Json,
+    body::Body,
+    extract::{FromRequestParts, Path, State},
+    http::{HeaderMap, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use base64::{Engine as _, engine::general_purpose};
+use blake2::{Blake2s256, Digest};
+use doljabiproto::{
+    badukboard::{
+        BadukBoardClient, ChaksuRequest, GameStartRequest, PassTurnRequest, ResignRequest,
+        baduk_board_client::Payload,
+    },
+    common::{ClientToServer, client_to_server::GameData as ClientData},
+};
+use game_core::UserID;
+use rusqlite::Connection;
+use serde::Serialize;
+use tokio::sync::{Mutex, broadcast, broadcast::error::RecvError, mpsc};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{
+    game_logic::{
+        ConnectionID, CreateRoomRequestForm, InputMessage, Recipient, RoomConnection, RoomGameForm,
+        RoomManager, SystemEvent, feed::replay, open_room, timer::TimerManager,
+    },
+    network::{
+        check_session_key::SessionKey,
+        socket::{Negotiated, RateLimit, room_credentials},
+    },
+    soyul::{
+        kibo::GameKind,
+        session::SessionStore,
+        soyul_db::{
+            bot_by_api_token, delete_api_token, insert_api_token, is_bot_account, upgrade_to_bot,
+        },
+        soyul_login::{ApiResponse, get_user_profile_by_id},
+    },
+};
+
+/// 스트림에 연결 유지용 빈 줄을 보내는 간격
+const STREAM_KEEPALIVE: Duration = Duration::from_secs(5);
+/// 스트림 한 개가 쌓아 둘 수 있는 줄 수
+const STREAM_BUFFER: usize = 32;
+
+/// 도전 상태
+#[derive(Serialize, ToSchema, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengeStatus {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+/// 유저가 봇에게 보낸 도전. 도전할 때 비공개 방이 열리고, 봇은 수락하면 초대 토큰을 받는다
+#[derive(Serialize, ToSchema, Clone)]
+pub struct Challenge {
+    pub id: u64,
+    pub challenger_id: i64,
+    pub challenger_name: Option<String>,
+    pub bot_id: i64,
+    #[serde(flatten)]
+    pub game: RoomGameForm,
+    pub rated: bool,
+    pub status: ChallengeStatus,
+    pub enter_code: String,
+    #[serde(skip)]
+    invite_token: Option<String>,
+}
+
+/// 봇이 들어갈 대국 (게임 스트림 주소에 쓰는 값)
+#[derive(Serialize, ToSchema, Clone)]
+pub struct BotGameStart {
+    pub enter_code: String,
+    /// 게임 스트림의 X-Room-Invite 헤더
+    pub invite_token: Option<String>,
+}
+
+/// 이벤트 스트림 한 줄
+#[derive(Serialize, ToSchema, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotEvent {
+    /// 새 도전 (스트림을 열 때는 아직 답하지 않은 도전도 보낸다)
+    Challenge { challenge: Challenge },
+    /// 도전을 수락해 대국이 열림
+    GameStart { game: BotGameStart },
+}
+
+/// 게임 스트림을 열어 둔 봇의 방 입력
+struct BotGame {
+    input: mpsc::Sender<InputMessage>,
+    connection: ConnectionID,
+    rate_limit: RateLimit,
+}
+
+/// 봇 API 상태
+#[derive(Clone)]
+pub struct BotApi {
+    room_manager: RoomManager,
+    timer_manager: TimerManager,
+    session_store: SessionStore,
+    challenges: Arc<Mutex<HashMap<u64, Challenge>>>,
+    next_challenge: Arc<AtomicU64>,
+    /// (받을 봇, 이벤트)
+    events: broadcast::Sender<(UserID, BotEvent)>,
+    /// (입장 코드, 봇) -> 게임 스트림. 같은 방에 스트림을 다시 열면 새 스트림으로 바뀐다
+    games: Arc<Mutex<HashMap<(String, UserID), BotGame>>>,
+}
+
+impl BotApi {
+    pub fn new(
+        room_manager: RoomManager,
+        timer_manager: TimerManager,
+        session_store: SessionStore,
+    ) -> Self {
+        let (events, _) = broadcast::channel(64);
+        Self {
+            room_manager,
+            timer_manager,
+            session_store,
+            challenges: Arc::default(),
+            next_challenge: Arc::new(AtomicU64::new(1)),
+            events,
+            games: Arc::default(),
+        }
+    }
+
+    /// 방이 닫힌 도전은 지운다
+    async fn prune_challenges(&self) {
+        let manager = self.room_manager.lock().await;
+        self.challenges
+            .lock()
+            .await
+            .retain(|_, challenge| manager.is_private_room(&challenge.enter_code).is_some());
+    }
+
+    async fn session_user(&self, session_key: &SessionKey) -> Option<UserID> {
+        self.session_store.read().await.get(&session_key.0).copied()
+    }
+}
+
+/// 새 API 토큰 (URL-safe Base64, 256비트)
+fn generate_api_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// DB 에 저장하는 토큰 해시
+fn hash_api_token(token: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(Blake2s256::digest(token.as_bytes()))
+}
+
+/// Authorization: Bearer <토큰> 의 토큰
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+/// 토큰 해시로 봇 계정 찾기. 없는 토큰이거나 봇이 아닌 계정이면 None
+fn bot_by_token_hash(conn: &Connection, token_hash: &str) -> rusqlite::Result<Option<UserID>> {
+    Ok(bot_by_api_token(conn, token_hash)?.map(|user_id| UserID(user_id as u64)))
+}
+
+/// Authorization: Bearer <토큰> 으로 인증한 봇 계정
+pub struct BotAuth {
+    pub user_id: UserID,
+    token_hash: String,
+}
+
+impl BotAuth {
+    fn from_headers(headers: &HeaderMap) -> Result<Self, StatusCode> {
+        let token = bearer_token(headers).ok_or(StatusCode::UNAUTHORIZED)?;
+        let token_hash = hash_api_token(token);
+
+        let user_id = Connection::open("mydb.db")
+            .and_then(|conn| bot_by_token_hash(&conn, &token_hash))
+            .map_err(|e| {
+                eprintln!("⚠️ 봇 토큰 조회 실패: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        Ok(BotAuth {
+            user_id,
+            token_hash,
+        })
+    }
+}
+
+impl<S> FromRequestParts<S> for BotAuth
+where
+    S: Send + Sync,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        BotAuth::from_headers(&parts.headers)
+    }
+}
+
+/// 줄 채널을 NDJSON 응답 본문으로. 클라이언트가 끊으면 받는 쪽이 닫혀 보내는 작업이 끝난다
+fn ndjson_response(lines: mpsc::Receiver<String>) -> Response {
+    let stream = futures_util::stream::unfold(lines, |mut lines| async move {
+        let line = lines.recv().await?;
+        Some((Ok::<_, Infallible>(format!("{}\n", line)), lines))
+    });
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(stream),
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/account/upgrade",
+    tag = "bot",
+    params(("x-session-key" = String, Header, description = "세션 키")),
+    responses(
+        (status = 200, description = "봇 계정으로 전환 성공", body = ApiResponse),
+        (status = 400, description = "유효하지 않은 세션 키 또는 대국 기록이 있는 계정", body = ApiResponse),
+        (status = 500, description = "서버 내부 오류", body = ApiResponse),
+    )
+)]
+pub async fn upgrade_bot_account(
+    State(api): State<BotApi>,
+    session_key: SessionKey,
+) -> (StatusCode, Json<ApiResponse>) {
+    let reply = |status, message: &str| {
+        (
+            status,
+            Json(ApiResponse {
+                success: status == StatusCode::OK,
+                message: message.into(),
+            }),
+        )
+    };
+
+    let Some(user_id) = api.session_user(&session_key).await else {
+        return reply(StatusCode::BAD_REQUEST, "세션 키가 올바르지 않습니다.");
+    };
+
+    match Connection::open("mydb.db").and_then(|conn| upgrade_to_bot(&conn, i64::from(user_id))) {
+        Ok(true) => {
+            #[cfg(debug_assertions)]
+            println!("✅ 봇 계정 전환: user_id = {}", user_id);
+            reply(StatusCode::OK, "봇 계정으로 전환했습니다.")
+        }
+        Ok(false) => reply(
+            StatusCode::BAD_REQUEST,
+            "대국 기록이 있는 계정은 봇으로 바꿀 수 없습니다.",
+        ),
+        Err(e) => {
+            eprintln!("⚠️ 봇 계정 전환 실패: {}", e);
+            reply(StatusCode::INTERNAL_SERVER_ERROR, "데이터베이스 오류")
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BotTokenResponse {
+    /// API 토큰. 지금 한 번만 보여 주므로 잘 보관해야 한다
+    pub token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/token",
+    tag = "bot",
+    params(("x-session-key" = String, Header, description = "봇 계정의 세션 키")),
+    responses(
+        (status = 201, description = "토큰 발급", body = BotTokenResponse),
+        (status = 400, description = "유효하지 않은 세션 키"),
+        (status = 403, description = "봇 계정이 아님"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn issue_bot_token(State(api): State<BotApi>, session_key: SessionKey) -> Response {
+    let Some(user_id) = api.session_user(&session_key).await else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let token = generate_api_token();
+    let result = Connection::open("mydb.db").and_then(|conn| {
+        if !is_bot_account(&conn, i64::from(user_id))? {
+            return Ok(false);
+        }
+        insert_api_token(&conn, i64::from(user_id), &hash_api_token(&token))?;
+        Ok(true)
+    });
+
+    match result {
+        Ok(true) => (StatusCode::CREATED, Json(BotTokenResponse { token })).into_response(),
+        Ok(false) => StatusCode::FORBIDDEN.into_response(),
+        Err(e) => {
+            eprintln!("⚠️ 봇 토큰 발급 실패: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bot/token",
+    tag = "bot",
+    params(("Authorization" = String, Header, description = "Bearer <API 토큰>")),
+    responses(
+        (status = 204, description = "요청에 쓴 토큰 폐기"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn revoke_bot_token(bot: BotAuth) -> StatusCode {
+    match Connection::open("mydb.db").and_then(|conn| delete_api_token(&conn, &bot.token_hash)) {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(e) => {
+            eprintln!("⚠️ 봇 토큰 폐기 실패: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ChallengeResponse {
+    pub challenge_id: u64,
+    /// 도전한 유저가 들어갈 방
+    pub enter_code: String,
+    pub invite_token: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/{bot_id}/challenge",
+    tag = "bot",
+    params(
+        ("bot_id" = i64, Path, description = "도전할 봇 계정의 user_id"),
+        ("x-session-key" = String, Header, description = "도전하는 유저의 세션 키"),
+    ),
+    request_body = CreateRoomRequestForm,
+    responses(
+        (status = 201, description = "도전 생성, 비공개 방 열림", body = ChallengeResponse),
+        (status = 400, description = "유효하지 않은 세션 키, 자기 자신에게 도전, 잘못된 방 설정"),
+        (status = 404, description = "봇 계정이 아님"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn create_challenge(
+    State(api): State<BotApi>,
+    Path(bot_id): Path<i64>,
+    session_key: SessionKey,
+    Json(form): Json<CreateRoomRequestForm>,
+) -> Response {
+    let Some(challenger) = api.session_user(&session_key).await else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    if i64::from(challenger) == bot_id {
+        return (StatusCode::BAD_REQUEST, "자기 자신에게 도전할 수 없습니다").into_response();
+    }
+    if let Err(message) = form.check() {
+        return (StatusCode::BAD_REQUEST, message).into_response();
+    }
+
+    let challenger_name = match Connection::open("mydb.db").and_then(|conn| {
+        if !is_bot_account(&conn, bot_id)? {
+            return Ok(None);
+        }
+        Ok(Some(
+            get_user_profile_by_id(&conn, challenger)?.and_then(|profile| profile.username),
+        ))
+    }) {
+        Ok(Some(name)) => name,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            eprintln!("⚠️ 봇 도전 실패: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let form = form.to_private();
+    let Some(room) = open_room(&api.room_manager, &api.timer_manager, form.clone()).await else {
+        eprintln!("봇 도전 실패: EnterCode 생성 실패");
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let challenge = Challenge {
+        id: api.next_challenge.fetch_add(1, Relaxed),
+        challenger_id: i64::from(challenger),
+        challenger_name,
+        bot_id,
+        game: form.game(),
+        rated: form.rated(),
+        status: ChallengeStatus::Pending,
+        enter_code: room.enter_code.clone(),
+        invite_token: room.invite_token.clone(),
+    };
+    #[cfg(debug_assertions)]
+    println!(
+        "봇 도전 {}: {} -> {} ({})",
+        challenge.id, challenger, bot_id, room.enter_code
+    );
+
+    api.prune_challenges().await;
+    api.challenges
+        .lock()
+        .await
+        .insert(challenge.id, challenge.clone());
+    let response = ChallengeResponse {
+        challenge_id: challenge.id,
+        enter_code: room.enter_code,
+        invite_token: room.invite_token,
+    };
+    // 이벤트 스트림을 연 봇이 없어도 도전은 남아 있다가 스트림을 열 때 간다
+    let _ = api
+        .events
+        .send((UserID(bot_id as u64), BotEvent::Challenge { challenge }));
+
+    (StatusCode::CREATED, Json(response)).into_response()
+}
+
+/// 도전한 유저나 도전받은 봇만 볼 수 있는 도전
+async fn viewable_challenge(
+    api: &BotApi,
+    viewer: UserID,
+    challenge_id: u64,
+) -> Result<Challenge, StatusCode> {
+    api.prune_challenges().await;
+    api.challenges
+        .lock()
+        .await
+        .get(&challenge_id)
+        .filter(|challenge| {
+            challenge.challenger_id == i64::from(viewer) || challenge.bot_id == i64::from(viewer)
+        })
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bot/challenge/{challenge_id}",
+    tag = "bot",
+    params(
+        ("challenge_id" = u64, Path, description = "도전 번호"),
+        ("x-session-key" = Option<String>, Header, description = "도전한 유저의 세션 키"),
+        ("Authorization" = Option<String>, Header, description = "도전받은 봇의 Bearer <API 토큰>"),
+    ),
+    responses(
+        (status = 200, description = "도전 상태", body = Challenge),
+        (status = 401, description = "세션 키·토큰이 없거나 유효하지 않음"),
+        (status = 404, description = "없는 도전이거나 내 도전이 아님 (방이 닫히면 지워진다)"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_challenge(
+    State(api): State<BotApi>,
+    Path(challenge_id): Path<u64>,
+    headers: HeaderMap,
+) -> Result<Json<Challenge>, StatusCode> {
+    let viewer = match bearer_token(&headers) {
+        Some(_) => BotAuth::from_headers(&headers)?.user_id,
+        None => {
+            let session_key = headers
+                .get("x-session-key")
+                .and_then(|value| value.to_str().ok())
+                .ok_or(StatusCode::UNAUTHORIZED)?;
+            api.session_user(&SessionKey(session_key.to_string()))
+                .await
+                .ok_or(StatusCode::UNAUTHORIZED)?
+        }
+    };
+    viewable_challenge(&api, viewer, challenge_id)
+        .await
+        .map(Json)
+}
+
+/// 봇에게 온 답하지 않은 도전의 상태를 바꾼다
+async fn answer_challenge(
+    api: &BotApi,
+    bot_id: UserID,
+    challenge_id: u64,
+    status: ChallengeStatus,
+) -> Result<Challenge, StatusCode> {
+    api.prune_challenges().await;
+    let mut challenges = api.challenges.lock().await;
+    let challenge = challenges
+        .get_mut(&challenge_id)
+        .filter(|challenge| challenge.bot_id == i64::from(bot_id))
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if challenge.status != ChallengeStatus::Pending {
+        return Err(StatusCode::CONFLICT);
+    }
+    challenge.status = status;
+    Ok(challenge.clone())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/challenge/{challenge_id}/accept",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("challenge_id" = u64, Path, description = "도전 번호")),
+    responses(
+        (status = 200, description = "수락. 게임 스트림으로 방에 들어간다", body = BotGameStart),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "이 봇에게 온 도전이 아님"),
+        (status = 409, description = "이미 답한 도전"),
+    )
+)]
+pub async fn accept_challenge(
+    State(api): State<BotApi>,
+    Path(challenge_id): Path<u64>,
+    bot: BotAuth,
+) -> Result<Json<BotGameStart>, StatusCode> {
+    let challenge =
+        answer_challenge(&api, bot.user_id, challenge_id, ChallengeStatus::Accepted).await?;
+    let game = BotGameStart {
+        enter_code: challenge.enter_code,
+        invite_token: challenge.invite_token,
+    };
+    let _ = api
+        .events
+        .send((bot.user_id, BotEvent::GameStart { game: game.clone() }));
+    Ok(Json(game))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/challenge/{challenge_id}/decline",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("challenge_id" = u64, Path, description = "도전 번호")),
+    responses(
+        (status = 200, description = "거절"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "이 봇에게 온 도전이 아님"),
+        (status = 409, description = "이미 답한 도전"),
+    )
+)]
+pub async fn decline_challenge(
+    State(api): State<BotApi>,
+    Path(challenge_id): Path<u64>,
+    bot: BotAuth,
+) -> StatusCode {
+    match answer_challenge(&api, bot.user_id, challenge_id, ChallengeStatus::Declined).await {
+        Ok(_) => StatusCode::OK,
+        Err(status) => status,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bot/stream/event",
+    tag = "bot",
+    params(("Authorization" = String, Header, description = "Bearer <API 토큰>")),
+    responses(
+        (status = 200, description = "봇 이벤트 스트림 (NDJSON, 한 줄에 BotEvent 하나)", body = BotEvent, content_type = "application/x-ndjson"),
+        (status = 401, description = "유효하지 않은 토큰"),
+    )
+)]
+pub async fn stream_events(State(api): State<BotApi>, bot: BotAuth) -> Response {
+    // 답하지 않은 도전을 보내는 사이에 온 이벤트도 놓치지 않도록 먼저 구독한다
+    let mut events = api.events.subscribe();
+    api.prune_challenges().await;
+    let pending: Vec<BotEvent> = api
+        .challenges
+        .lock()
+        .await
+        .values()
+        .filter(|c| c.bot_id == i64::from(bot.user_id) && c.status == ChallengeStatus::Pending)
+        .map(|challenge| BotEvent::Challenge {
+            challenge: challenge.clone(),
+        })
+        .collect();
+
+    let (lines_tx, lines_rx) = mpsc::channel::<String>(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let to_line = |event: &BotEvent| serde_json::to_string(event).unwrap_or_default();
+        for event in &pending {
+            if lines_tx.send(to_line(event)).await.is_err() {
+                return;
+            }
+        }
+
+        let start = tokio::time::Instant::now() + STREAM_KEEPALIVE;
+        let mut keepalive = tokio::time::interval_at(start, STREAM_KEEPALIVE);
+        loop {
+            let line = tokio::select! {
+                event = events.recv() => match event {
+                    Ok((user_id, event)) if user_id == bot.user_id => to_line(&event),
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+                _ = keepalive.tick() => String::new(),
+            };
+            if lines_tx.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    ndjson_response(lines_rx)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bot/game/stream/{enter_code}",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("enter_code" = String, Path, description = "방 입장 코드"),
+        ("X-Room-Password" = Option<String>, Header, description = "비공개 방 비밀번호"),
+        ("X-Room-Invite" = Option<String>, Header, description = "도전 수락·방 생성 응답의 초대 토큰"),
+    ),
+    responses(
+        (status = 200, description = "게임 스트림 (NDJSON, 한 줄에 JSON WebSocket 과 같은 ServerToClient 하나). 여는 동안 방에 들어가 있다", content_type = "application/x-ndjson"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 403, description = "비공개 방: 비밀번호·초대 토큰이 없거나 틀림(연달아 틀리면 잠시 잠김), 또는 관전석이 꽉 참"),
+        (status = 404, description = "존재하지 않는 방"),
+    )
+)]
+pub async fn stream_game(
+    State(api): State<BotApi>,
+    Path(enter_code): Path<String>,
+    headers: HeaderMap,
+    bot: BotAuth,
+) -> Response {
+    let user_id = bot.user_id;
+    let (password, invite) = room_credentials(&headers);
+    let connection = {
+        let mut manager = api.room_manager.lock().await;
+        match manager.check_access(&enter_code, password.as_deref(), invite.as_deref()) {
+            None => return StatusCode::NOT_FOUND.into_response(),
+            Some(false) => return StatusCode::FORBIDDEN.into_response(),
+            Some(true) if manager.spectators_full(&enter_code, user_id) => {
+                return StatusCode::FORBIDDEN.into_response();
+            }
+            Some(true) => manager.get_channels(&enter_code),
+        }
+    };
+    let Some(RoomConnection {
+        input,
+        output: mut broadcast_rx,
+        status: status_rx,
+        history,
+        connection,
+    }) = connection
+    else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    if input
+        .send(InputMessage::System(SystemEvent::EnterUser(
+            user_id, connection,
+        )))
+        .await
+        .is_err()
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    let key = (enter_code, user_id);
+    api.games.lock().await.insert(
+        key.clone(),
+        BotGame {
+            input: input.clone(),
+            connection,
+            rate_limit: RateLimit::new(),
+        },
+    );
+    #[cfg(debug_assertions)]
+    println!("{}: 봇 게임 스트림 시작 ({})", key.0, user_id);
+
+    let recipient = move || {
+        let status = status_rx.borrow();
+        Recipient {
+            user_id,
+            connection,
+            seated: status.black == Some(user_id) || status.white == Some(user_id),
+        }
+    };
+
+    // Hello 를 주고받지 않으므로 채팅은 맞추지 않은 연결로 본다
+    let negotiated = Negotiated::current();
+
+    let (lines_tx, lines_rx) = mpsc::channel::<String>(STREAM_BUFFER);
+    tokio::spawn(async move {
+        let start = tokio::time::Instant::now() + STREAM_KEEPALIVE;
+        let mut keepalive = tokio::time::interval_at(start, STREAM_KEEPALIVE);
+        // 소켓과 같이, 다시 보낸 것과 겹치는 순번 있는 메시지는 건너뛴다
+        let mut last_seq = 0;
+        'stream: loop {
+            let messages = tokio::select! {
+                response = broadcast_rx.recv() => match response {
+                    Ok(response) => {
+                        let seq = response.message.seq;
+                        if !response.is_for(&recipient()) || (seq != 0 && seq <= last_seq) {
+                            continue;
+                        }
+                        vec![response.message.clone()]
+                    }
+                    Err(RecvError::Lagged(_)) => replay(&history, last_seq, &recipient(), false),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = keepalive.tick() => {
+                    if lines_tx.send(String::new()).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            };
+
+            for message in messages {
+                last_seq = last_seq.max(message.seq);
+                if !negotiated.accepts(&message) {
+                    continue;
+                }
+                match serde_json::to_string(&message) {
+                    Ok(line) => {
+                        if lines_tx.send(line).await.is_err() {
+                            break 'stream;
+                        }
+                    }
+                    Err(e) => eprintln!("JSON 인코딩 에러!!! {}", e),
+                }
+            }
+        }
+
+        // 같은 방에 새 스트림이 열렸으면 그쪽 등록은 남겨 둔다
+        let mut games = api.games.lock().await;
+        if games
+            .get(&key)
+            .is_some_and(|game| game.connection == connection)
+        {
+            games.remove(&key);
+        }
+        drop(games);
+        #[cfg(debug_assertions)]
+        println!("{}: 봇 게임 스트림 종료 ({})", key.0, user_id);
+        // 스트림마다 EnterUser 를 보냈으므로 LeaveUser 도 스트림마다 보낸다.
+        // 새 스트림이 열려 있으면 방은 아직 연결이 남은 것으로 보고 봇을 자리에 둔다
+        if let Err(e) = input
+            .send(InputMessage::System(SystemEvent::LeaveUser(user_id)))
+            .await
+        {
+            eprintln!("연결 해제 신호 전송 오류!!! {}", e);
+        };
+    });
+
+    ndjson_response(lines_rx)
+}
+
+/// 게임 스트림을 연 방에 봇의 요청을 넣는다. 결과(거절 포함)는 게임 스트림으로 온다
+async fn submit(api: &BotApi, bot: &BotAuth, enter_code: &str, payload: Payload) -> StatusCode {
+    let Some(kind) = api.room_manager.lock().await.game_kind(enter_code) else {
+        return StatusCode::NOT_FOUND;
+    };
+    let request = BadukBoardClient {
+        payload: Some(payload),
+    };
+    let request = ClientToServer {
+        game_data: Some(match kind {
+            GameKind::Baduk => ClientData::Baduk(request),
+            GameKind::Omok => ClientData::Omok(request),
+        }),
+    };
+
+    let (input, connection) = {
+        let mut games = api.games.lock().await;
+        let key = (enter_code.to_string(), bot.user_id);
+        let Some(game) = games.get_mut(&key) else {
+            return StatusCode::NOT_FOUND;
+        };
+        if !game.rate_limit.allow() {
+            return StatusCode::TOO_MANY_REQUESTS;
+        }
+        (game.input.clone(), game.connection)
+    };
+
+    #[cfg(debug_assertions)]
+    println!("{:#?}", request);
+    match input
+        .send(InputMessage::Request((bot.user_id, connection, request)))
+        .await
+    {
+        Ok(()) => StatusCode::OK,
+        Err(e) => {
+            eprintln!("봇 요청 전달 오류!!! {}", e);
+            StatusCode::NOT_FOUND
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/game/{enter_code}/move/{coordinate}",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("enter_code" = String, Path, description = "방 입장 코드"),
+        ("coordinate" = u32, Path, description = "착수할 칸 번호 (ChaksuRequest.coordinate 와 같음)"),
+    ),
+    responses(
+        (status = 200, description = "방에 전달함. 거절되면 게임 스트림에 Error 가 온다"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "없는 방이거나 이 방의 게임 스트림을 열지 않음"),
+        (status = 429, description = "요청이 너무 잦음"),
+    )
+)]
+pub async fn bot_move(
+    State(api): State<BotApi>,
+    Path((enter_code, coordinate)): Path<(String, u32)>,
+    bot: BotAuth,
+) -> StatusCode {
+    let payload = Payload::Coordinate(ChaksuRequest { coordinate });
+    submit(&api, &bot, &enter_code, payload).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/game/{enter_code}/pass",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("enter_code" = String, Path, description = "방 입장 코드")),
+    responses(
+        (status = 200, description = "방에 전달함. 거절되면 게임 스트림에 Error 가 온다"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "없는 방이거나 이 방의 게임 스트림을 열지 않음"),
+        (status = 429, description = "요청이 너무 잦음"),
+    )
+)]
+pub async fn bot_pass(
+    State(api): State<BotApi>,
+    Path(enter_code): Path<String>,
+    bot: BotAuth,
+) -> StatusCode {
+    let payload = Payload::PassTurn(PassTurnRequest {});
+    submit(&api, &bot, &enter_code, payload).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/game/{enter_code}/resign",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("enter_code" = String, Path, description = "방 입장 코드")),
+    responses(
+        (status = 200, description = "방에 전달함. 거절되면 게임 스트림에 Error 가 온다"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "없는 방이거나 이 방의 게임 스트림을 열지 않음"),
+        (status = 429, description = "요청이 너무 잦음"),
+    )
+)]
+pub async fn bot_resign(
+    State(api): State<BotApi>,
+    Path(enter_code): Path<String>,
+    bot: BotAuth,
+) -> StatusCode {
+    let payload = Payload::Resign(ResignRequest {});
+    submit(&api, &bot, &enter_code, payload).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bot/game/{enter_code}/start",
+    tag = "bot",
+    params(
+        ("Authorization" = String, Header, description = "Bearer <API 토큰>"),
+        ("enter_code" = String, Path, description = "방 입장 코드")),
+    responses(
+        (status = 200, description = "방에 전달함. 두 자리가 다 차지 않았으면 게임 스트림에 Error 가 온다"),
+        (status = 401, description = "유효하지 않은 토큰"),
+        (status = 404, description = "없는 방이거나 이 방의 게임 스트림을 열지 않음"),
+        (status = 429, description = "요청이 너무 잦음"),
+    )
+)]
+pub async fn bot_start(
+    State(api): State<BotApi>,
+    Path(enter_code): Path<String>,
+    bot: BotAuth,
+) -> StatusCode {
+    let payload = Payload::Gamestart(GameStartRequest {});
+    submit(&api, &bot, &enter_code, payload).await
+}
+
+pub fn bot_router() -> OpenApiRouter<BotApi> {
+    OpenApiRouter::new()
+        .routes(routes!(upgrade_bot_account))
+        .routes(routes!(issue_bot_token, revoke_bot_token))
+        .routes(routes!(create_challenge))
+        .routes(routes!(get_challenge))
+        .routes(routes!(accept_challenge))
+        .routes(routes!(decline_challenge))
+        .routes(routes!(stream_events))
+        .routes(routes!(stream_game))
+        .routes(routes!(bot_move))
+        .routes(routes!(bot_pass))
+        .routes(routes!(bot_resign))
+        .routes(routes!(bot_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::RoomManagement;
+
+    #[test]
+    fn api_token_hash() {
+        let token = generate_api_token();
+        assert_ne!(token, generate_api_token());
+        assert_eq!(hash_api_token(&token), hash_api_token(&token));
+        assert_ne!(hash_api_token(&token), token);
+    }
+
+    #[test]
+    fn bot_event_lines() {
+        let line = serde_json::to_value(BotEvent::GameStart {
+            game: BotGameStart {
+                enter_code: "ABC234".to_string(),
+                invite_token: None,
+            },
+        })
+        .unwrap();
+        assert_eq!(line["type"], "game_start");
+        assert_eq!(line["game"]["enter_code"], "ABC234");
+    }
+
+    fn test_api() -> BotApi {
+        let (timer_manager, _) = mpsc::unbounded_channel();
+        BotApi::new(
+            Arc::new(Mutex::new(RoomManagement::new())),
+            timer_manager,
+            SessionStore::default(),
+        )
+    }
+
+    fn baduk_form() -> CreateRoomRequestForm {
+        serde_json::from_str(r#"{"game_type":"baduk","game_config":{"main_time":1800000,"fischer_time":0,"remaining_overtime":3,"overtime":30000}}"#).unwrap()
+    }
+
+    /// 봇(7)에게 온 답하지 않은 도전을 방과 함께 만든다
+    async fn pending_challenge(api: &BotApi, challenger_id: i64) -> u64 {
+        let form = baduk_form().to_private();
+        let room = open_room(&api.room_manager, &api.timer_manager, form.clone())
+            .await
+            .unwrap();
+        let challenge = Challenge {
+            id: api.next_challenge.fetch_add(1, Relaxed),
+            challenger_id,
+            challenger_name: None,
+            bot_id: 7,
+            game: form.game(),
+            rated: form.rated(),
+            status: ChallengeStatus::Pending,
+            enter_code: room.enter_code,
+            invite_token: room.invite_token,
+        };
+        let id = challenge.id;
+        api.challenges.lock().await.insert(id, challenge);
+        id
+    }
+
+    #[tokio::test]
+    async fn challenge_answer_test() {
+        let api = test_api();
+        let accepted = pending_challenge(&api, 1).await;
+        let declined = pending_challenge(&api, 1).await;
+
+        // 다른 봇은 답할 수 없다
+        assert_eq!(
+            answer_challenge(&api, UserID(8), accepted, ChallengeStatus::Accepted)
+                .await
+                .err(),
+            Some(StatusCode::NOT_FOUND)
+        );
+
+        let challenge = answer_challenge(&api, UserID(7), accepted, ChallengeStatus::Accepted)
+            .await
+            .ok()
+            .unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Accepted);
+        assert!(challenge.invite_token.is_some());
+        // 이미 답한 도전
+        assert_eq!(
+            answer_challenge(&api, UserID(7), accepted, ChallengeStatus::Declined)
+                .await
+                .err(),
+            Some(StatusCode::CONFLICT)
+        );
+
+        let challenge = answer_challenge(&api, UserID(7), declined, ChallengeStatus::Declined)
+            .await
+            .ok()
+            .unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Declined);
+
+        // 도전한 유저와 도전받은 봇만 볼 수 있다
+        for viewer in [UserID(1), UserID(7)] {
+            let challenge = viewable_challenge(&api, viewer, accepted)
+                .await
+                .ok()
+                .unwrap();
+            assert_eq!(challenge.status, ChallengeStatus::Accepted);
+        }
+        assert_eq!(
+            viewable_challenge(&api, UserID(2), accepted).await.err(),
+            Some(StatusCode::NOT_FOUND)
+        );
+    }
+
+    #[test]
+    fn bot_token_auth_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::soyul::soyul_db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash) VALUES (1, 'user', ''), (2, 'bot', '');",
+        )
+        .unwrap();
+        assert!(upgrade_to_bot(&conn, 2).unwrap());
+
+        let user_token = generate_api_token();
+        let bot_token = generate_api_token();
+        insert_api_token(&conn, 1, &hash_api_token(&user_token)).unwrap();
+        insert_api_token(&conn, 2, &hash_api_token(&bot_token)).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", bot_token).parse().unwrap(),
+        );
+        let token = bearer_token(&headers).unwrap();
+        assert_eq!(
+            bot_by_token_hash(&conn, &hash_api_token(token)).unwrap(),
+            Some(UserID(2))
+        );
+        // 봇이 아닌 계정의 토큰, 없는 토큰
+        assert_eq!(
+            bot_by_token_hash(&conn, &hash_api_token(&user_token)).unwrap(),
+            None
+        );
+        assert_eq!(
+            bot_by_token_hash(&conn, &hash_api_token("없는 토큰")).unwrap(),
+            None
+        );
+
+        delete_api_token(&conn, &hash_api_token(&bot_token)).unwrap();
+        assert_eq!(
+            bot_by_token_hash(&conn, &hash_api_token(&bot_token)).unwrap(),
+            None
+        );
+        assert!(bearer_token(&HeaderMap::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn reopened_stream_leaves_room_test() {
+        let api = test_api();
+        let room = open_room(&api.room_manager, &api.timer_manager, baduk_form())
+            .await
+            .unwrap();
+        let RoomConnection {
+            input,
+            mut status,
+            connection,
+            ..
+        } = api
+            .room_manager
+            .lock()
+            .await
+            .get_channels(&room.enter_code)
+            .unwrap();
+        let stream = || {
+            stream_game(
+                State(api.clone()),
+                Path(room.enter_code.clone()),
+                HeaderMap::new(),
+                BotAuth {
+                    user_id: UserID(7),
+                    token_hash: String::new(),
+                },
+            )
+        };
+
+        // 같은 방에 스트림을 다시 열어 앞 스트림을 대신한 뒤 둘 다 닫는다
+        let first = stream().await;
+        let second = stream().await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+        status
+            .wait_for(|s| s.black == Some(UserID(7)))
+            .await
+            .unwrap();
+        drop((first, second));
+        // 스트림은 다음 방 메시지를 보내려다 닫힌 것을 알아챈다
+        input
+            .send(InputMessage::System(SystemEvent::EnterUser(
+                UserID(8),
+                connection,
+            )))
+            .await
+            .unwrap();
+
+        // 두 스트림이 모두 닫히면 봇은 방을 나간다
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            status.wait_for(|s| s.black.is_none()),
+        )
+        .await
+        .expect("봇이 방에 남아 있다")
+        .unwrap();
+        assert!(api.games.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn submit_request_test() {
+        let api = test_api();
+        let room = open_room(&api.room_manager, &api.timer_manager, baduk_form())
+            .await
+            .unwrap();
+        let bot = BotAuth {
+            user_id: UserID(7),
+            token_hash: String::new(),
+        };
+        let payload = || Payload::Coordinate(ChaksuRequest { coordinate: 60 });
+
+        // 게임 스트림을 열지 않았으면 넣을 곳이 없다
+        assert_eq!(
+            submit(&api, &bot, &room.enter_code, payload()).await,
+            StatusCode::NOT_FOUND
+        );
+
+        let (input, mut input_rx) = mpsc::channel(4);
+        let connection = api
+            .room_manager
+            .lock()
+            .await
+            .get_channels(&room.enter_code)
+            .unwrap()
+            .connection;
+        api.games.lock().await.insert(
+            (room.enter_code.clone(), bot.user_id),
+            BotGame {
+                input,
+                connection,
+                rate_limit: RateLimit::new(),
+            },
+        );
+
+        assert_eq!(
+            submit(&api, &bot, &room.enter_code, payload()).await,
+            StatusCode::OK
+        );
+        match input_rx.recv().await {
+            Some(InputMessage::Request((user_id, request_connection, request))) => {
+                assert_eq!(user_id, bot.user_id);
+                assert_eq!(request_connection, connection);
+                assert_eq!(
+                    request.game_data,
+                    Some(ClientData::Baduk(BadukBoardClient {
+                        payload: Some(payload()),
+                    }))
+                );
+            }
+            _ => panic!("착수 요청이 방에 들어가야 한다"),
+        }
+    }
+}
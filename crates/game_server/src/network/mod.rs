@@ -1,2 +1,3 @@
+pub mod bot_api;
 pub mod check_session_key;
 pub mod socket;
@@ -1,47 +1,303 @@
 use axum::{
     extract::{
-        Path, State,
+        Path, Query, State,
         ws::{self, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
 };
-use doljabiproto::common::{ClientToServer, ServerToClient};
-use futures_util::{SinkExt, StreamExt};
+use doljabiproto::{
+    PROTOCOL_VERSION,
+    common::{
+        ClientToServer, ErrorCode, Hello, HelloAck, Ping, Pong, ServerToClient,
+        client_to_server::GameData as ClientData, server_to_client::GameData as ServerData,
+    },
+};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
 use game_core::UserID;
 use serde::{Deserialize, Serialize};
-use std::{sync::Arc, time::Duration};
-use tokio::sync::{broadcast, mpsc};
-use utoipa::ToSchema;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, AtomicU64, Ordering::Relaxed},
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use utoipa::{IntoParams, ToSchema};
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 use crate::{
-    game_logic::{InputMessage, RoomManager, SystemEvent},
+    game_logic::{
+        InputMessage, Recipient, RoomConnection, RoomManager, SystemEvent,
+        baduk_board::unix_millis, error_response, feed::replay,
+    },
     soyul::session::SessionStore,
 };
 
+/// 서버가 Ping(앱 Ping + WebSocket Ping)을 보내는 간격
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// 클라이언트에게서 아무 메시지(Pong 포함)도 오지 않으면 연결을 끊는 시간
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+/// 연결 하나가 REQUEST_WINDOW 동안 보낼 수 있는 요청 수 (게임 요청·Resync)
+const REQUEST_LIMIT: u32 = 10;
+const REQUEST_WINDOW: Duration = Duration::from_secs(1);
+/// 헤더로 자격을 보내지 않은 비공개 방에서 자격을 실은 Hello 를 기다리는 시간
+const CREDENTIALS_TIMEOUT: Duration = Duration::from_secs(10);
+/// 비공개 방 자격을 싣는 헤더. URL 에 남지 않도록 쿼리 대신 쓴다.
+/// 헤더를 붙일 수 없는 브라우저는 첫 메시지 Hello 의 password·invite_token 으로 보낸다
+pub(crate) const PASSWORD_HEADER: &str = "x-room-password";
+pub(crate) const INVITE_HEADER: &str = "x-room-invite";
+/// Hello 를 보내지 않는 예전 클라이언트의 프로토콜 버전.
+/// 이 버전은 바둑·오목 메시지만 알고, Ping·Pong·에러·채팅·HelloAck 는 그다음 버전부터 있다
+const LEGACY_PROTOCOL_VERSION: u32 = 1;
+/// 서버가 받는 가장 낮은 프로토콜 버전. 더 낮으면 에러를 보내고 끊는다
+const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// 바둑판 없는 델타 메시지 (EnterRoomQuery.delta 와 같음)
+const FEATURE_DELTA: &str = "delta";
+/// Resync 로 놓친 메시지 다시 받기
+const FEATURE_RESYNC: &str = "resync";
+/// 채팅 메시지 받기. 없으면 채팅은 보내지 않는다
+const FEATURE_CHAT: &str = "chat";
+const SERVER_FEATURES: [&str; 3] = [FEATURE_DELTA, FEATURE_RESYNC, FEATURE_CHAT];
+
+/// Hello 에 대한 답. 클라이언트가 보낸 기능 중 서버가 아는 것만 쓴다.
+/// 서버가 모르는 메시지를 쓰는 더 높은 버전은 받지 않는다
+fn negotiate(hello: &Hello) -> HelloAck {
+    HelloAck {
+        protocol_version: PROTOCOL_VERSION,
+        min_protocol_version: MIN_PROTOCOL_VERSION,
+        features: SERVER_FEATURES
+            .iter()
+            .filter(|feature| hello.features.iter().any(|f| f == *feature))
+            .map(|feature| feature.to_string())
+            .collect(),
+        accepted: (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&hello.protocol_version),
+    }
+}
+
+/// 이 연결에서 맞춘 버전·기능. Hello 를 받기 전에는 예전 클라이언트(버전 1, 기능 없음)로 본다
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Negotiated {
+    protocol_version: u32,
+    delta: bool,
+    chat: bool,
+}
+impl Negotiated {
+    /// delta 는 EnterRoomQuery.delta
+    fn legacy(delta: bool) -> Self {
+        Self {
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            delta,
+            chat: false,
+        }
+    }
+
+    /// Hello 없이 현재 버전 메시지를 받는 연결 (봇 게임 스트림). 채팅은 맞추지 않았으니 받지 않는다
+    pub(crate) fn current() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            delta: false,
+            chat: false,
+        }
+    }
+
+    fn from_ack(hello: &Hello, ack: &HelloAck, delta: bool) -> Self {
+        Self {
+            protocol_version: hello.protocol_version,
+            delta: delta || ack.features.iter().any(|f| f == FEATURE_DELTA),
+            chat: ack.features.iter().any(|f| f == FEATURE_CHAT),
+        }
+    }
+
+    /// 이 연결에 보내도 되는 메시지인지. 예전 클라이언트에는 바둑·오목 메시지만,
+    /// 채팅은 chat 기능을 맞춘 연결에만 보낸다
+    pub(crate) fn accepts(&self, message: &ServerToClient) -> bool {
+        match &message.game_data {
+            None | Some(ServerData::Baduk(_)) | Some(ServerData::Omok(_)) => true,
+            Some(ServerData::Chat(_)) => self.chat,
+            Some(_) => self.protocol_version > LEGACY_PROTOCOL_VERSION,
+        }
+    }
+}
+
+/// Hello 에 HelloAck 로 답한다. 받지 않는 버전이면 에러를 보내고 연결을 닫은 뒤 None
+async fn answer_hello(
+    ws_tx: &mut SplitSink<ws::WebSocket, ws::Message>,
+    encoding: Encoding,
+    user_id: UserID,
+    hello: &Hello,
+    delta: bool,
+) -> Option<Negotiated> {
+    let ack = negotiate(hello);
+    #[cfg(debug_assertions)]
+    println!(
+        "{}: 프로토콜 버전 {} (서버 {}), 기능 {:?}",
+        user_id, hello.protocol_version, PROTOCOL_VERSION, ack.features
+    );
+    let negotiated = Negotiated::from_ack(hello, &ack, delta);
+    let accepted = ack.accepted;
+    let ack = ServerToClient {
+        game_data: Some(ServerData::Hello(ack)),
+        ..Default::default()
+    };
+    ws_tx.send(encoding.encode(&ack)?).await.ok()?;
+    if !accepted {
+        eprintln!(
+            "{}: 지원하지 않는 프로토콜 버전 {} (서버 {}~{})",
+            user_id, hello.protocol_version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION
+        );
+        close_with_error(
+            ws_tx,
+            encoding,
+            ErrorCode::UnsupportedVersion,
+            ws::close_code::PROTOCOL,
+            "unsupported protocol version",
+        )
+        .await;
+        return None;
+    }
+    Some(negotiated)
+}
+
+/// 연결별 요청 횟수 제한. REQUEST_WINDOW 마다 다시 센다 (봇 API 도 게임 스트림마다 같이 센다)
+pub(crate) struct RateLimit {
+    window_start: Instant,
+    count: u32,
+}
+impl RateLimit {
+    pub(crate) fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// 이번 요청을 받아도 되는지
+    pub(crate) fn allow(&mut self) -> bool {
+        if self.window_start.elapsed() >= REQUEST_WINDOW {
+            self.window_start = Instant::now();
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= REQUEST_LIMIT
+    }
+}
+
+/// WebSocket 서브프로토콜: 바이너리 Protobuf (기본)
+const PROTOBUF_SUBPROTOCOL: &str = "doljabi.proto";
+/// WebSocket 서브프로토콜: 같은 ClientToServer·ServerToClient 의 JSON (텍스트 프레임)
+const JSON_SUBPROTOCOL: &str = "doljabi.json";
+
+/// 연결이 메시지를 주고받는 인코딩. 어느 쪽이든 메시지와 처리는 같다
+#[derive(Clone, Copy, Default, Debug, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Protobuf,
+    Json,
+}
+impl Encoding {
+    /// 보낼 메시지를 이 인코딩의 프레임으로
+    fn encode(self, message: &ServerToClient) -> Option<ws::Message> {
+        use prost::Message as _;
+        match self {
+            Encoding::Protobuf => Some(ws::Message::Binary(message.encode_to_vec().into())),
+            Encoding::Json => match serde_json::to_string(message) {
+                Ok(text) => Some(ws::Message::Text(text.into())),
+                Err(e) => {
+                    eprintln!("JSON 인코딩 에러!!! {}", e);
+                    None
+                }
+            },
+        }
+    }
+
+    /// 받은 프레임을 요청으로. 인코딩과 다른 종류의 프레임이거나 읽을 수 없으면 None
+    fn decode(self, frame: &ws::Message) -> Option<ClientToServer> {
+        use prost::Message as _;
+        match (self, frame) {
+            (Encoding::Protobuf, ws::Message::Binary(data)) => {
+                ClientToServer::decode(&data[..]).ok()
+            }
+            (Encoding::Json, ws::Message::Text(text)) => serde_json::from_str(text.as_str()).ok(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, ToSchema)]
 pub enum EnterRoomErrorCode {
     EnterCodeError,
     IncorrectSessionKey,
 }
 
+/// 헤더의 비공개 방 자격 (비밀번호, 초대 토큰)
+pub(crate) fn room_credentials(headers: &HeaderMap) -> (Option<String>, Option<String>) {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    (header(PASSWORD_HEADER), header(INVITE_HEADER))
+}
+
+/// 이 연결에만 에러를 보내고 연결을 닫는다
+async fn close_with_error(
+    ws_tx: &mut SplitSink<ws::WebSocket, ws::Message>,
+    encoding: Encoding,
+    code: ErrorCode,
+    close_code: u16,
+    reason: &'static str,
+) {
+    if let Some(error) = encoding.encode(&error_response(code)) {
+        let _ = ws_tx.send(error).await;
+    }
+    let _ = ws_tx
+        .send(ws::Message::Close(Some(ws::CloseFrame {
+            code: close_code,
+            reason: reason.into(),
+        })))
+        .await;
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EnterRoomQuery {
+    /// 메시지 인코딩 (protobuf: 바이너리 프레임, json: 같은 메시지의 JSON 텍스트 프레임).
+    /// Sec-WebSocket-Protocol 로 doljabi.json·doljabi.proto 를 고르면 그쪽이 우선
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// true 면 판이 그대로이거나 한 수만 늘어난 메시지를 바둑판 없이(델타) 받는다.
+    /// 판은 game_state.last_move 로 고치고, 주기적으로 전체 판이 온다
+    #[serde(default)]
+    pub delta: bool,
+}
+
 #[utoipa::path(
     get,
     path = "/ws/room/{enter_code}/session/{session_key}",
     params(
-        ("enter_code" = u16, Path, description = "방 입장 코드"),
-        ("session_key" = String, Path, description = "세션 키")
+        ("enter_code" = String, Path, description = "방 입장 코드 (6자리 영문·숫자, 대소문자 무시)"),
+        ("session_key" = String, Path, description = "세션 키"),
+        ("X-Room-Password" = Option<String>, Header, description = "비공개 방 비밀번호. 헤더를 못 붙이면 첫 메시지 Hello 의 password 로"),
+        ("X-Room-Invite" = Option<String>, Header, description = "비공개 방 초대 토큰. 헤더를 못 붙이면 첫 메시지 Hello 의 invite_token 으로"),
+        EnterRoomQuery,
     ),
     responses(
         (status = 101, description = "WebSocket 연결 성공: 방 입장 성공"),
         (status = 400, description = "유효하지 않은 세션 키"),
+        (status = 403, description = "비공개 방: 헤더의 비밀번호·초대 토큰이 틀림(연달아 틀리면 잠시 잠김), 또는 관전석이 꽉 참(관전 불가 방 포함)"),
         (status = 404, description = "존재하지 않는 방"),
     )
 )]
 pub async fn enter_room(
     ws: WebSocketUpgrade,
-    Path((enter_code, session_key)): Path<(u16, String)>,
+    Path((enter_code, session_key)): Path<(String, String)>,
+    Query(q): Query<EnterRoomQuery>,
+    headers: HeaderMap,
     State((room_manager, session_store)): State<(RoomManager, SessionStore)>,
 ) -> impl IntoResponse {
     let is_user_id = {
@@ -65,32 +321,131 @@ pub async fn enter_room(
         }
     };
 
-    let communication_channel = { room_manager.lock().await.get_channels(enter_code) };
+    let (password, invite) = room_credentials(&headers);
+    let (communication_channel, needs_credentials) = {
+        let mut manager = room_manager.lock().await;
+        // 비공개 방: 헤더로 자격을 보냈으면 지금 확인하고, 아니면 첫 메시지(Hello)에서 확인한다
+        let needs_credentials = match manager.is_private_room(&enter_code) {
+            Some(true) if password.is_some() || invite.is_some() => {
+                if manager.check_access(&enter_code, password.as_deref(), invite.as_deref())
+                    != Some(true)
+                {
+                    #[cfg(debug_assertions)]
+                    println!("{}: 비공개 방 입장 거부 ({})", enter_code, user_id);
+                    return StatusCode::FORBIDDEN.into_response();
+                }
+                false
+            }
+            Some(private) => private,
+            None => return StatusCode::NOT_FOUND.into_response(),
+        };
+        if manager.spectators_full(&enter_code, user_id) {
+            #[cfg(debug_assertions)]
+            println!("{}: 관전석이 꽉 참 ({})", enter_code, user_id);
+            return StatusCode::FORBIDDEN.into_response();
+        }
+        (manager.get_channels(&enter_code), needs_credentials)
+    };
+    let access = needs_credentials.then(|| (room_manager.clone(), enter_code.clone()));
 
-    let (mpsc_tx, broadcast_rx) = match communication_channel {
+    let connection = match communication_channel {
         Some(channel) => channel,
         None => {
             return StatusCode::NOT_FOUND.into_response();
         }
     };
 
-    ws.on_upgrade(move |socket| handle_websocket(socket, mpsc_tx, broadcast_rx, user_id))
+    // 서브프로토콜을 고른 클라이언트는 그것을, 아니면 쿼리의 encoding 을 따른다
+    let ws = ws.protocols([JSON_SUBPROTOCOL, PROTOBUF_SUBPROTOCOL]);
+    let encoding = match ws.selected_protocol() {
+        Some(protocol) if protocol.as_bytes() == JSON_SUBPROTOCOL.as_bytes() => Encoding::Json,
+        Some(_) => Encoding::Protobuf,
+        None => q.encoding,
+    };
+
+    ws.on_upgrade(move |socket| {
+        handle_websocket(socket, connection, user_id, q.delta, encoding, access)
+    })
 }
 
+/// access 가 Some 이면 아직 자격을 확인하지 않은 비공개 방: 첫 메시지가 자격을 실은 Hello 여야 한다.
+/// 아니면 Hello 를 기다리지 않고 바로 입장하고, 첫 메시지로 Hello 가 오면 그때 버전·기능을 올린다
 async fn handle_websocket(
     socket: ws::WebSocket,
-    mpsc_tx: mpsc::Sender<InputMessage>,
-    mut broadcast_rx: broadcast::Receiver<Arc<ServerToClient>>,
+    connection: RoomConnection,
     user_id: UserID,
+    delta: bool,
+    encoding: Encoding,
+    access: Option<(RoomManager, String)>,
 ) {
-    use prost::Message as ProstMessage;
     use ws::Message;
 
     let (mut ws_tx, mut ws_rx) = socket.split();
+    let mut negotiated = Negotiated::legacy(delta);
+
+    // 헤더로 자격을 보내지 않은 비공개 방: 입장 전에 Hello 에 실린 자격을 확인한다
+    let private_hello = access.is_some();
+    if let Some((room_manager, enter_code)) = &access {
+        let hello = match tokio::time::timeout(CREDENTIALS_TIMEOUT, ws_rx.next()).await {
+            Ok(Some(Ok(frame))) => match encoding.decode(&frame) {
+                Some(ClientToServer {
+                    game_data: Some(ClientData::Hello(hello)),
+                }) => Some(hello),
+                _ => None,
+            },
+            Ok(None) => return,
+            _ => None,
+        };
+        let allowed = match &hello {
+            Some(hello) => {
+                room_manager.lock().await.check_access(
+                    enter_code,
+                    hello.password.as_deref(),
+                    hello.invite_token.as_deref(),
+                ) == Some(true)
+            }
+            None => false,
+        };
+        let Some(hello) = hello.filter(|_| allowed) else {
+            #[cfg(debug_assertions)]
+            println!("{}: 비공개 방 입장 거부 ({})", enter_code, user_id);
+            close_with_error(
+                &mut ws_tx,
+                encoding,
+                ErrorCode::AccessDenied,
+                ws::close_code::POLICY,
+                "access denied",
+            )
+            .await;
+            return;
+        };
+        negotiated = match answer_hello(&mut ws_tx, encoding, user_id, &hello, delta).await {
+            Some(negotiated) => negotiated,
+            None => return,
+        };
+    }
+    let RoomConnection {
+        input: mpsc_tx,
+        output: mut broadcast_rx,
+        status: status_rx,
+        history,
+        connection,
+    } = connection;
+    // 방 메시지 중 이 연결이 받을 것만 고르는 기준 (자리에 앉았는지는 그때그때 본다)
+    let recipient = move || {
+        let status = status_rx.borrow();
+        Recipient {
+            user_id,
+            connection,
+            seated: status.black == Some(user_id) || status.white == Some(user_id),
+        }
+    };
 
     // 방 접속 시도 -> 실패 시 종료
     if let Err(_) = mpsc_tx
-        .send(InputMessage::System(SystemEvent::EnterUser(user_id)))
+        .send(InputMessage::System(SystemEvent::EnterUser(
+            user_id, connection,
+        )))
         .await
     {
         return;
@@ -99,21 +454,93 @@ async fn handle_websocket(
     // 종료 후 종료 신호 보내기 위한 채널 확보
     let send_disconnect = mpsc_tx.clone();
 
+    // 이 연결에만 보내는 메시지 (클라이언트 Ping 에 대한 Pong)
+    let (direct_tx, mut direct_rx) = mpsc::channel::<ServerToClient>(8);
+    // 입장한 뒤 첫 메시지로 온 Hello (답과 기능 변경은 보내는 쪽에서 메시지 순서에 맞춰 한다)
+    let (hello_tx, mut hello_rx) = mpsc::channel::<Hello>(1);
+    // 놓친 방 메시지를 다시 보내 달라는 요청 (from_seq)
+    let (resync_tx, mut resync_rx) = mpsc::channel::<u64>(8);
+    // 마지막으로 보낸 서버 Ping 시각, 마지막으로 잰 왕복 지연
+    let last_ping_ms = Arc::new(AtomicU64::new(0));
+    let last_rtt_ms = Arc::new(AtomicU32::new(0));
+    let (ping_sent, rtt) = (last_ping_ms.clone(), last_rtt_ms.clone());
+
     let send_task = tokio::spawn(async move {
-        while let Ok(Some(message)) =
-            tokio::time::timeout(Duration::from_secs(10), ws_rx.next()).await
-        {
+        let mut rate_limit = RateLimit::new();
+        // Hello 는 연결의 첫 메시지로만 받는다 (비공개 방은 입장 전에 이미 받았다)
+        let mut awaiting_hello = !private_hello;
+        // 하트비트 응답을 포함해 아무것도 오지 않은 채 HEARTBEAT_TIMEOUT 이 지나면 끊긴 것으로 본다
+        while let Ok(Some(message)) = tokio::time::timeout(HEARTBEAT_TIMEOUT, ws_rx.next()).await {
             match message {
-                Ok(Message::Binary(data)) => {
-                    if let Ok(request) = ClientToServer::decode(&data[..]) {
-                        #[cfg(debug_assertions)]
-                        println!("{:#?}", request);
-                        if let Err(e) = mpsc_tx
-                            .send(InputMessage::Request((user_id, request)))
-                            .await
-                        {
-                            eprintln!("클라이언트 데이터 송신 오류!!! {}", e);
-                        };
+                Ok(frame @ (Message::Binary(_) | Message::Text(_))) => {
+                    let Some(request) = encoding.decode(&frame) else {
+                        let _ = direct_tx
+                            .send(error_response(ErrorCode::InvalidRequest))
+                            .await;
+                        continue;
+                    };
+                    let first_message = std::mem::replace(&mut awaiting_hello, false);
+                    // Ping·Pong 을 뺀 요청은 너무 잦으면 이 연결에만 에러로 답하고 버린다
+                    if !matches!(
+                        request.game_data,
+                        Some(ClientData::Ping(_)) | Some(ClientData::Pong(_))
+                    ) && !rate_limit.allow()
+                    {
+                        let _ = direct_tx.send(error_response(ErrorCode::RateLimited)).await;
+                        continue;
+                    }
+                    match request.game_data {
+                        // 클라이언트 시계 맞춤: 서버 시각과 지금까지 잰 왕복 지연으로 답한다
+                        Some(ClientData::Ping(ping)) => {
+                            let pong = ServerToClient {
+                                game_data: Some(ServerData::Pong(Pong {
+                                    ping_sent_at_ms: ping.sent_at_ms,
+                                    sent_at_ms: unix_millis(),
+                                    rtt_ms: rtt.load(Relaxed),
+                                })),
+                                ..Default::default()
+                            };
+                            let _ = direct_tx.send(pong).await;
+                        }
+                        // 다시 보내는 건 방 메시지와 순서를 맞추도록 보내는 쪽에서 한다
+                        Some(ClientData::Resync(resync)) => {
+                            let _ = resync_tx.send(resync.from_seq).await;
+                        }
+                        Some(ClientData::Hello(hello)) if first_message => {
+                            let _ = hello_tx.send(hello).await;
+                        }
+                        Some(ClientData::Hello(_)) => {
+                            let _ = direct_tx
+                                .send(error_response(ErrorCode::InvalidRequest))
+                                .await;
+                        }
+                        // 서버 Ping 에 대한 답: 마지막으로 보낸 Ping 에 대한 것만 잰다
+                        Some(ClientData::Pong(pong)) => {
+                            if pong.ping_sent_at_ms == 0
+                                || pong.ping_sent_at_ms != ping_sent.load(Relaxed)
+                            {
+                                continue;
+                            }
+                            let rtt_ms = unix_millis().saturating_sub(pong.ping_sent_at_ms);
+                            let rtt_ms = u32::try_from(rtt_ms).unwrap_or(u32::MAX);
+                            rtt.store(rtt_ms, Relaxed);
+                            if let Err(e) = mpsc_tx
+                                .send(InputMessage::System(SystemEvent::Latency(user_id, rtt_ms)))
+                                .await
+                            {
+                                eprintln!("클라이언트 데이터 송신 오류!!! {}", e);
+                            };
+                        }
+                        _ => {
+                            #[cfg(debug_assertions)]
+                            println!("{:#?}", request);
+                            if let Err(e) = mpsc_tx
+                                .send(InputMessage::Request((user_id, connection, request)))
+                                .await
+                            {
+                                eprintln!("클라이언트 데이터 송신 오류!!! {}", e);
+                            };
+                        }
                     }
                 }
 
@@ -135,14 +562,65 @@ async fn handle_websocket(
     });
 
     let recv_task = tokio::spawn(async move {
-        while let Ok(response) = broadcast_rx.recv().await {
-            #[cfg(debug_assertions)]
-            println!("{:#?}", response);
-            let mut buf = Vec::new();
-            if response.encode(&mut buf).is_ok() {
-                if let Err(e) = ws_tx.send(Message::Binary(buf.into())).await {
-                    eprintln!("WebSocket 전송 에러!!! {}", e);
-                };
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        // 이 연결에 마지막으로 보낸 방 메시지 seq. 다시 보낸 것과 겹치는 메시지는 건너뛴다
+        let mut last_seq = 0;
+        loop {
+            let messages = tokio::select! {
+                response = broadcast_rx.recv() => match response {
+                    Ok(response) => {
+                        // 순번 있는 메시지 중 다시 보낸 것과 겹치는 것은 건너뛴다
+                        let seq = response.message.seq;
+                        if !response.is_for(&recipient()) || (seq != 0 && seq <= last_seq) {
+                            continue;
+                        }
+                        vec![response.message_for(negotiated.delta).clone()]
+                    }
+                    // 밀려서 놓친 메시지는 기록에서 다시 보낸다
+                    Err(RecvError::Lagged(_)) => {
+                        replay(&history, last_seq, &recipient(), negotiated.delta)
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                Some(from_seq) = resync_rx.recv() => {
+                    replay(&history, from_seq, &recipient(), negotiated.delta)
+                }
+                Some(direct) = direct_rx.recv() => vec![direct],
+                // 입장한 뒤 온 Hello: 답을 보내고 이후 메시지부터 맞춘 버전·기능으로 보낸다
+                Some(hello) = hello_rx.recv() => {
+                    match answer_hello(&mut ws_tx, encoding, user_id, &hello, delta).await {
+                        Some(upgraded) => negotiated = upgraded,
+                        None => break,
+                    }
+                    continue;
+                }
+                _ = heartbeat.tick() => {
+                    // 앱 Pong 을 보내지 않는 클라이언트도 브라우저가 자동으로 답하도록 WebSocket Ping 도 보낸다
+                    if ws_tx.send(Message::Ping(Default::default())).await.is_err() {
+                        break;
+                    }
+                    let now = unix_millis();
+                    last_ping_ms.store(now, Relaxed);
+                    vec![ServerToClient {
+                        game_data: Some(ServerData::Ping(Ping { sent_at_ms: now })),
+                        ..Default::default()
+                    }]
+                }
+            };
+
+            for message in messages {
+                // 예전 클라이언트가 모르는 메시지, 채팅을 맞추지 않은 연결의 채팅은 보내지 않는다
+                if !negotiated.accepts(&message) {
+                    continue;
+                }
+                #[cfg(debug_assertions)]
+                println!("{:#?}", message);
+                last_seq = last_seq.max(message.seq);
+                if let Some(frame) = encoding.encode(&message) {
+                    if let Err(e) = ws_tx.send(frame).await {
+                        eprintln!("WebSocket 전송 에러!!! {}", e);
+                    };
+                }
             }
         }
     });
@@ -163,3 +641,148 @@ async fn handle_websocket(
 pub fn web_socket_upgrade_router() -> OpenApiRouter<(RoomManager, SessionStore)> {
     OpenApiRouter::new().routes(routes!(enter_room))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hello_negotiation() {
+        let ack = negotiate(&Hello {
+            protocol_version: PROTOCOL_VERSION,
+            features: vec!["chat".to_string(), "unknown".to_string()],
+            ..Default::default()
+        });
+        assert!(ack.accepted);
+        assert_eq!(ack.features, vec![FEATURE_CHAT.to_string()]);
+
+        // 서버가 모르는 메시지를 쓰는 더 높은 버전은 거절
+        assert!(
+            !negotiate(&Hello {
+                protocol_version: PROTOCOL_VERSION + 1,
+                ..Default::default()
+            })
+            .accepted
+        );
+        // 버전을 비운 Hello 는 거절
+        assert!(!negotiate(&Hello::default()).accepted);
+    }
+
+    #[test]
+    fn legacy_client_gets_v1_messages() {
+        let board = ServerToClient {
+            game_data: Some(ServerData::Baduk(Default::default())),
+            ..Default::default()
+        };
+        let ping = ServerToClient {
+            game_data: Some(ServerData::Ping(Ping::default())),
+            ..Default::default()
+        };
+        let chat = ServerToClient {
+            game_data: Some(ServerData::Chat(Default::default())),
+            ..Default::default()
+        };
+        let error = error_response(ErrorCode::Ko);
+
+        // Hello 를 보내지 않은 연결은 바둑·오목 메시지만 받는다
+        let legacy = Negotiated::legacy(true);
+        assert!(legacy.delta);
+        assert!(legacy.accepts(&board));
+        assert!(!legacy.accepts(&ping) && !legacy.accepts(&chat) && !legacy.accepts(&error));
+
+        // 입장한 뒤 Hello 로 올리면 Ping·에러를 받고, 채팅은 맞춘 기능일 때만
+        let hello = Hello {
+            protocol_version: PROTOCOL_VERSION,
+            features: vec![FEATURE_DELTA.to_string()],
+            ..Default::default()
+        };
+        let upgraded = Negotiated::from_ack(&hello, &negotiate(&hello), false);
+        assert!(upgraded.delta && !upgraded.chat);
+        assert!(upgraded.accepts(&ping) && upgraded.accepts(&error));
+        assert!(!upgraded.accepts(&chat));
+    }
+
+    #[test]
+    fn json_and_protobuf_carry_same_message() {
+        let hello = ClientToServer {
+            game_data: Some(ClientData::Hello(Hello {
+                protocol_version: PROTOCOL_VERSION,
+                features: vec![FEATURE_DELTA.to_string()],
+                // 비공개 방 자격도 첫 메시지에 싣는다
+                invite_token: Some("token".to_string()),
+                ..Default::default()
+            })),
+        };
+        let json = serde_json::to_string(&hello).unwrap();
+        let frame = ws::Message::Text(json.into());
+        assert_eq!(Encoding::Json.decode(&frame), Some(hello.clone()));
+        // 인코딩과 다른 종류의 프레임은 받지 않는다
+        assert_eq!(Encoding::Protobuf.decode(&frame), None);
+
+        // 빠진 필드는 기본값
+        let ping = ws::Message::Text(r#"{"game_data":{"Ping":{}}}"#.into());
+        assert_eq!(
+            Encoding::Json.decode(&ping),
+            Some(ClientToServer {
+                game_data: Some(ClientData::Ping(Ping::default())),
+            })
+        );
+
+        let error = error_response(ErrorCode::Ko);
+        let Some(ws::Message::Text(text)) = Encoding::Json.encode(&error) else {
+            panic!("JSON 은 텍스트 프레임");
+        };
+        assert_eq!(
+            serde_json::from_str::<ServerToClient>(text.as_str()).unwrap(),
+            error
+        );
+        assert!(matches!(
+            Encoding::Protobuf.encode(&error),
+            Some(ws::Message::Binary(_))
+        ));
+    }
+
+    #[test]
+    fn json_writes_64bit_fields_as_strings() {
+        use doljabiproto::badukboard::{BadukBoardData, BadukBoardServer, BadukBoardState};
+
+        // 높은 비트가 켜진 비트보드는 JavaScript 숫자(2^53)로 담을 수 없다
+        let board = ServerToClient {
+            seq: 7,
+            game_data: Some(ServerData::Baduk(BadukBoardServer {
+                game_state: Some(BadukBoardData {
+                    board: Some(BadukBoardState {
+                        black: vec![u64::MAX, 1 << 63],
+                        white: vec![0],
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let Some(ws::Message::Text(text)) = Encoding::Json.encode(&board) else {
+            panic!("JSON 은 텍스트 프레임");
+        };
+        let json: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+        assert_eq!(json["seq"], "7");
+        assert_eq!(
+            json["game_data"]["Baduk"]["game_state"]["board"]["black"],
+            serde_json::json!(["18446744073709551615", "9223372036854775808"])
+        );
+        assert_eq!(
+            serde_json::from_str::<ServerToClient>(text.as_str()).unwrap(),
+            board
+        );
+
+        // 클라이언트가 숫자로 보내도 받는다
+        let resync = ws::Message::Text(r#"{"game_data":{"Resync":{"from_seq":12}}}"#.into());
+        let Some(ClientToServer {
+            game_data: Some(ClientData::Resync(resync)),
+        }) = Encoding::Json.decode(&resync)
+        else {
+            panic!("Resync 를 읽지 못함");
+        };
+        assert_eq!(resync.from_seq, 12);
+    }
+}
@@ -2,12 +2,16 @@
 //
 // 종료되어 games 테이블에 저장된 게임을 클라이언트로 내려주는 REST API.
 //  - GET /api/games/{game_id}/sgf            : 단일 게임 기보(SGF) 다운로드
+//  - GET /api/games/{game_id}/chat/session/{session_key}: 대국 중 채팅 (다시보기용, 두 플레이어만)
+//  - GET /api/games/{game_id}/export?format=  : 다른 기보 형식(GIB/NGF/RIF/PSQ)으로 파일 다운로드 (SGF 는 플레이어가 채팅을 C[] 로 넣을 수 있음)
+//  - GET /api/games/{game_id}/diagram?move=   : N수째 국면 그림(SVG/PNG)
 //  - GET /api/user/games/session/{session_key}: 로그인 유저의 게임 리스트(메타)
+//  - POST /api/games/import/session/{session_key}: 오프라인 대국 기보 업로드(검증 후 저장)
 
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::IntoResponse,
 };
 use rusqlite::{Connection, params};
@@ -15,7 +19,15 @@ use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::soyul::session::{SessionStore, get_user_id_by_session};
+use crate::game_logic::baduk_board::{EndReason, sgf_color_char, sgf_result};
+use crate::soyul::{
+    kibo::SgfGame,
+    kibo_diagram::{DiagramOptions, render_png, render_svg},
+    kibo_formats::{KiboFormat, export_kibo, import_kibo},
+    session::{SessionStore, get_user_id_by_session},
+    soyul_db::{load_game_chat, save_imported_game},
+    soyul_login::get_user_profile_by_id,
+};
 
 //
 // ── 단일 게임 기보(SGF) 다운로드 ──
@@ -74,6 +86,374 @@ pub async fn get_game_sgf(Path(game_id): Path<i64>) -> impl IntoResponse {
     }
 }
 
+//
+// ── 대국 중 채팅 ──
+//
+// 방이 기보를 저장할 때 game_chat 에 같이 저장한 채팅. 보낸 시점의 수(move_number)로
+// 다시보기 화면에서 그 수에 맞춰 보여 줄 수 있다.
+// 비공개 방에서 오간 채팅일 수 있으므로 그 게임의 두 플레이어에게만 내려준다.
+//
+
+/// 이 게임의 흑·백 중 한 명인지. 게임이 없으면 None
+fn is_game_player(conn: &Connection, game_id: i64, user_id: i64) -> rusqlite::Result<Option<bool>> {
+    match conn.query_row(
+        "SELECT black_id = ?2 OR white_id = ?2 FROM games WHERE id = ?1",
+        params![game_id, user_id],
+        |row| row.get(0),
+    ) {
+        Ok(player) => Ok(Some(player)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GameChatLine {
+    /// 보낸 시점까지 둔 수 (0 이면 첫 수 전)
+    pub move_number: usize,
+    /// 관전자 채팅이면 true
+    pub spectator: bool,
+    pub user_id: i64,
+    pub username: String,
+    pub text: String,
+    /// 보낸 시각 (Unix ms)
+    pub sent_at_ms: u64,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{game_id}/chat/session/{session_key}",
+    tag = "game",
+    params(
+        ("game_id" = i64, Path, description = "게임 고유 번호 (games.id)"),
+        ("session_key" = String, Path, description = "세션 키"),
+    ),
+    responses(
+        (status = 200, description = "채팅 조회 성공 (보낸 순서대로, 없으면 빈 배열)", body = Vec<GameChatLine>),
+        (status = 400, description = "세션 키가 올바르지 않음"),
+        (status = 403, description = "이 게임의 플레이어가 아님"),
+        (status = 404, description = "해당 게임 없음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_game_chat(
+    State(session_store): State<SessionStore>,
+    Path((game_id, session_key)): Path<(i64, String)>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => i64::from(id),
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⚠️ 채팅 조회(DB 오픈 실패): {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let chat = match is_game_player(&conn, game_id, user_id) {
+        Ok(Some(true)) => load_game_chat(&conn, game_id),
+        Ok(Some(false)) => {
+            eprintln!(
+                "❌ 채팅 조회 거절: user_id={}는 game_id={}의 플레이어가 아님",
+                user_id, game_id
+            );
+            return StatusCode::FORBIDDEN.into_response();
+        }
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => Err(e),
+    };
+
+    match chat {
+        Ok(chat) => {
+            let lines: Vec<GameChatLine> = chat
+                .into_iter()
+                .map(|line| GameChatLine {
+                    move_number: line.move_number,
+                    spectator: line.spectator,
+                    user_id: line.user_id,
+                    username: line.user_name,
+                    text: line.text,
+                    sent_at_ms: line.sent_at_ms,
+                })
+                .collect();
+            (StatusCode::OK, Json(lines)).into_response()
+        }
+        Err(e) => {
+            eprintln!("⚠️ 채팅 조회 에러: game_id={}, {}", game_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+//
+// ── 다른 기보 형식으로 내보내기 ──
+//
+// 저장된 SGF를 SgfGame으로 다시 읽어 요청한 형식으로 바꾼 뒤 파일 그대로 내려준다.
+// 바둑은 gib / ngf, 오목은 rif / psq, 둘 다 sgf 를 쓸 수 있다.
+//
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// 기보 형식: "sgf" | "gib" | "ngf" | "rif" | "psq"
+    pub format: String,
+    /// true 면 대국 중 채팅을 보낸 시점의 수에 C[] 로 넣는다 (sgf 형식만, 두 플레이어만)
+    #[serde(default)]
+    pub chat: bool,
+    /// chat=true 일 때 플레이어인지 확인할 세션 키
+    pub session_key: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{game_id}/export",
+    tag = "game",
+    params(
+        ("game_id" = i64, Path, description = "게임 고유 번호 (games.id)"),
+        ExportQuery,
+    ),
+    responses(
+        (status = 200, description = "기보 파일", body = String),
+        (status = 400, description = "알 수 없는 기보 형식"),
+        (status = 403, description = "채팅을 요청했지만 이 게임의 플레이어가 아님"),
+        (status = 404, description = "해당 게임 없음"),
+        (status = 422, description = "게임 종류에 맞지 않는 형식이거나 저장된 기보를 읽을 수 없음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn export_game(
+    State(session_store): State<SessionStore>,
+    Path(game_id): Path<i64>,
+    Query(q): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let format = match KiboFormat::from_name(&q.format) {
+        Some(format) => format,
+        None => {
+            eprintln!("❌ 기보 내보내기 실패: 알 수 없는 형식 {}", q.format);
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    // 채팅을 요청했으면 세션 키 → user_id
+    let chat_user = match (q.chat, format, &q.session_key) {
+        (true, KiboFormat::Sgf, Some(session_key)) => {
+            get_user_id_by_session(&session_store, session_key)
+                .await
+                .map(i64::from)
+        }
+        _ => None,
+    };
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⚠️ 기보 내보내기(DB 오픈 실패): {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let sgf: String =
+        match conn.query_row("SELECT sgf FROM games WHERE id = ?1", [game_id], |row| {
+            row.get(0)
+        }) {
+            Ok(sgf) => sgf,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                eprintln!("❌ 기보 내보내기 실패: game_id={} 없음", game_id);
+                return StatusCode::NOT_FOUND.into_response();
+            }
+            Err(e) => {
+                eprintln!("⚠️ 기보 내보내기 에러: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+    let chat = match (q.chat, format) {
+        (true, KiboFormat::Sgf) => {
+            // 채팅은 두 플레이어만 받을 수 있다 (없는 게임은 위에서 이미 404)
+            let player = match chat_user {
+                Some(user_id) => is_game_player(&conn, game_id, user_id),
+                None => Ok(None),
+            };
+            match player.and_then(|player| match player {
+                Some(true) => load_game_chat(&conn, game_id).map(Some),
+                _ => Ok(None),
+            }) {
+                Ok(Some(chat)) => chat,
+                Ok(None) => {
+                    eprintln!(
+                        "❌ 기보 내보내기 거절: game_id={}의 채팅은 플레이어만 받을 수 있음",
+                        game_id
+                    );
+                    return StatusCode::FORBIDDEN.into_response();
+                }
+                Err(e) => {
+                    eprintln!("⚠️ 기보 내보내기(채팅 조회) 에러: {}", e);
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                }
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    let body = match SgfGame::from_sgf_str(&sgf).and_then(|mut game| {
+        game.chat = chat;
+        export_kibo(&game, format)
+    }) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("❌ 기보 내보내기 실패: game_id={}, {}", game_id, e);
+            return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response();
+        }
+    };
+
+    #[cfg(debug_assertions)]
+    println!(
+        "✅ 기보 내보내기 성공: game_id={}, format={}",
+        game_id,
+        format.extension()
+    );
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"game_{}.{}\"",
+                    game_id,
+                    format.extension()
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+//
+// ── 국면 그림(SVG/PNG) ──
+//
+// 저장된 기보를 game_core로 move수까지 다시 두고 좌표·마지막 수·수 번호·따낸 돌 수를
+// 그린다. 공유 미리보기, 인쇄용 기보, game_core 국면 디버깅에 쓴다.
+//
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiagramQuery {
+    /// 몇 수째 국면인지 (0 이면 빈 판). 생략 시 마지막 수
+    pub r#move: Option<usize>,
+    /// "svg" | "png". 생략 시 "svg"
+    pub format: Option<String>,
+    /// 돌 위에 수 번호 표시 여부. 생략 시 true
+    pub numbers: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/games/{game_id}/diagram",
+    tag = "game",
+    params(
+        ("game_id" = i64, Path, description = "게임 고유 번호 (games.id)"),
+        DiagramQuery,
+    ),
+    responses(
+        (status = 200, description = "국면 그림 (image/svg+xml 또는 image/png)"),
+        (status = 400, description = "알 수 없는 그림 형식이거나 수 번호가 수순보다 큼"),
+        (status = 404, description = "해당 게임 없음"),
+        (status = 422, description = "저장된 기보를 다시 둘 수 없음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_game_diagram(
+    Path(game_id): Path<i64>,
+    Query(q): Query<DiagramQuery>,
+) -> impl IntoResponse {
+    let is_png = match q.format.as_deref().map(str::to_ascii_lowercase).as_deref() {
+        None | Some("svg") => false,
+        Some("png") => true,
+        Some(other) => {
+            eprintln!("❌ 국면 그림 실패: 알 수 없는 형식 {}", other);
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⚠️ 국면 그림(DB 오픈 실패): {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let sgf: String =
+        match conn.query_row("SELECT sgf FROM games WHERE id = ?1", [game_id], |row| {
+            row.get(0)
+        }) {
+            Ok(sgf) => sgf,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                eprintln!("❌ 국면 그림 실패: game_id={} 없음", game_id);
+                return StatusCode::NOT_FOUND.into_response();
+            }
+            Err(e) => {
+                eprintln!("⚠️ 국면 그림 에러: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+    let game = match SgfGame::from_sgf_str(&sgf) {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("❌ 국면 그림 실패: game_id={}, {}", game_id, e);
+            return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response();
+        }
+    };
+    let move_number = q.r#move.unwrap_or(game.moves.len());
+    if move_number > game.moves.len() {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("수순은 {}수까지 있습니다", game.moves.len()),
+        )
+            .into_response();
+    }
+    let position = match game.position_at(move_number) {
+        Ok(position) => position,
+        Err(e) => {
+            eprintln!("❌ 국면 그림 실패: game_id={}, {}", game_id, e);
+            return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response();
+        }
+    };
+
+    let options = DiagramOptions {
+        move_numbers: q.numbers.unwrap_or(true),
+    };
+
+    #[cfg(debug_assertions)]
+    println!(
+        "✅ 국면 그림: game_id={}, move={}, png={}",
+        game_id, move_number, is_png
+    );
+
+    if !is_png {
+        let svg = render_svg(game.game_kind, &position, options);
+        return (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "image/svg+xml")],
+            svg,
+        )
+            .into_response();
+    }
+    match render_png(game.game_kind, &position, options) {
+        Ok(png) => (StatusCode::OK, [(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(e) => {
+            eprintln!("⚠️ 국면 그림 PNG 인코딩 실패: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 //
 // ── 로그인 유저의 게임 리스트 ──
 //
@@ -100,6 +480,8 @@ pub struct GameSummary {
     pub opponent_id: i64,
     /// 요청자가 둔 색 ("black" | "white")
     pub my_color: String,
+    /// 업로드로 가져온 기보이면 true
+    pub imported: bool,
     pub created_at: String,
 }
 
@@ -144,7 +526,7 @@ pub async fn get_my_games(
     };
 
     let mut stmt = match conn.prepare(
-        "SELECT id, black_id, white_id, game_type, board_size, result, created_at
+        "SELECT id, black_id, white_id, game_type, board_size, result, created_at, imported
          FROM games
          WHERE (black_id = ?1 OR white_id = ?1)
            AND (?2 IS NULL OR game_type = ?2)
@@ -172,6 +554,7 @@ pub async fn get_my_games(
             result: row.get(5)?,
             opponent_id,
             my_color,
+            imported: row.get::<_, i64>(7)? != 0,
             created_at: row.get(6)?,
         })
     });
@@ -202,8 +585,218 @@ pub async fn get_my_games(
         .into_response()
 }
 
+//
+// ── 기보 업로드(가져오기) ──
+//
+// 업로드된 기보(SGF, GIB, NGF, PSQ)는 game_core(Baduk/Omok)로 한 수씩 다시 두어
+// 규칙 위반을 검사하고, 서버 SgfGame 형식으로 다시 써서(헤더 정리) imported = 1 로 저장한다.
+//
+
+#[derive(Deserialize, ToSchema)]
+pub struct ImportSgfForm {
+    /// 업로드할 기보 본문 (format 으로 지정한 형식 그대로: SGF, GIB, NGF, PSQ 텍스트)
+    pub record: String,
+    /// 업로더가 둔 색 ("black" | "white"). 상대 쪽 id 는 0 으로 저장된다
+    pub my_color: String,
+    /// 기보 형식: "sgf" | "gib" | "ngf" | "psq". 생략 시 "sgf"
+    pub format: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportSgfResponse {
+    pub success: bool,
+    /// 실패 시 사유 (규칙 위반이면 첫 번째 잘못된 수를 포함)
+    pub message: String,
+    /// 저장된 게임 고유 번호 (games.id)
+    pub game_id: Option<i64>,
+}
+impl ImportSgfResponse {
+    fn fail(status: StatusCode, message: String) -> axum::response::Response {
+        (
+            status,
+            Json(ImportSgfResponse {
+                success: false,
+                message,
+                game_id: None,
+            }),
+        )
+            .into_response()
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/games/import/session/{session_key}",
+    tag = "game",
+    params(
+        ("session_key" = String, Path, description = "세션 키")
+    ),
+    request_body = ImportSgfForm,
+    responses(
+        (status = 201, description = "기보 가져오기 성공", body = ImportSgfResponse),
+        (status = 400, description = "세션 키 또는 요청 형식이 올바르지 않음", body = ImportSgfResponse),
+        (status = 422, description = "기보 파싱 실패 또는 규칙 위반 수 포함", body = ImportSgfResponse),
+        (status = 500, description = "서버 내부 오류", body = ImportSgfResponse),
+    )
+)]
+pub async fn import_game_sgf(
+    State(session_store): State<SessionStore>,
+    Path(session_key): Path<String>,
+    Json(form): Json<ImportSgfForm>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => {
+            return ImportSgfResponse::fail(
+                StatusCode::BAD_REQUEST,
+                "세션 키가 올바르지 않습니다.".into(),
+            );
+        }
+    };
+
+    let is_black = match form.my_color.as_str() {
+        "black" => true,
+        "white" => false,
+        _ => {
+            return ImportSgfResponse::fail(
+                StatusCode::BAD_REQUEST,
+                "my_color 는 \"black\" 또는 \"white\" 여야 합니다.".into(),
+            );
+        }
+    };
+
+    let format = match form.format.as_deref().map(KiboFormat::from_name) {
+        None => KiboFormat::Sgf,
+        Some(Some(format)) => format,
+        Some(None) => {
+            return ImportSgfResponse::fail(
+                StatusCode::BAD_REQUEST,
+                "format 은 \"sgf\", \"gib\", \"ngf\", \"psq\" 중 하나여야 합니다.".into(),
+            );
+        }
+    };
+
+    // 파싱 + game_core 재생 검증
+    let mut game = match import_kibo(&form.record, format) {
+        Ok(game) => game,
+        Err(e) => return ImportSgfResponse::fail(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+    };
+    let winner = match game.replay() {
+        Ok(winner) => winner,
+        Err(e) => {
+            #[cfg(debug_assertions)]
+            println!("❌ 기보 가져오기 거부: {}", e);
+            return ImportSgfResponse::fail(StatusCode::UNPROCESSABLE_ENTITY, e.to_string());
+        }
+    };
+
+    // 오목 5목으로 승부가 났으면 RE[] 를 실제 승자 기준으로 맞춘다
+    if let Some(winner) = winner
+        && !game.result.starts_with(sgf_color_char(winner))
+    {
+        game.set_result(&sgf_result(winner, EndReason::Immediate));
+    }
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⚠️ 기보 가져오기(DB 오픈 실패): {}", e);
+            return ImportSgfResponse::fail(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "데이터베이스 연결 오류".into(),
+            );
+        }
+    };
+
+    // 업로더 쪽 이름이 비어 있으면 닉네임으로 채운다
+    let username = get_user_profile_by_id(&conn, user_id)
+        .ok()
+        .flatten()
+        .and_then(|p| p.username)
+        .unwrap_or_default();
+    let (black_name, white_name) = (game.black_player.clone(), game.white_player.clone());
+    match is_black {
+        true if black_name.is_empty() => game.set_players(&username, &white_name),
+        false if white_name.is_empty() => game.set_players(&black_name, &username),
+        _ => {}
+    }
+
+    let (black_id, white_id) = match is_black {
+        true => (i64::from(user_id), 0),
+        false => (0, i64::from(user_id)),
+    };
+    let result = (!game.result.is_empty()).then(|| game.result.clone());
+
+    match save_imported_game(
+        &conn,
+        black_id,
+        white_id,
+        game.game_kind.game_type(),
+        game.board_size,
+        result.as_deref(),
+        &game.to_sgf_string(),
+    ) {
+        Ok(game_id) => {
+            #[cfg(debug_assertions)]
+            println!("✅ 기보 가져오기 성공: game_id={}", game_id);
+            (
+                StatusCode::CREATED,
+                Json(ImportSgfResponse {
+                    success: true,
+                    message: format!("기보 가져오기 성공 ({}수)", game.moves.len()),
+                    game_id: Some(game_id),
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            eprintln!("⚠️ 기보 가져오기(INSERT) 실패: {}", e);
+            ImportSgfResponse::fail(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "기보 저장 중 오류가 발생했습니다.".into(),
+            )
+        }
+    }
+}
+
 pub fn sgf_router() -> OpenApiRouter<SessionStore> {
     OpenApiRouter::new()
         .routes(routes!(get_game_sgf))
+        .routes(routes!(get_game_chat))
+        .routes(routes!(export_game))
+        .routes(routes!(get_game_diagram))
         .routes(routes!(get_my_games))
+        .routes(routes!(import_game_sgf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::{
+        kibo::ChatLine,
+        soyul_db::{init_schema, save_finished_game, save_game_chat},
+    };
+
+    #[test]
+    fn game_chat_player_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let game_id = save_finished_game(&conn, 1, 2, "baduk", 19, "B+R", "(;GM[1])").unwrap();
+        let line = ChatLine {
+            move_number: 3,
+            spectator: true,
+            user_id: 3,
+            user_name: "관전자".into(),
+            text: "안녕".into(),
+            sent_at_ms: 1000,
+        };
+        save_game_chat(&conn, game_id, std::slice::from_ref(&line)).unwrap();
+
+        assert_eq!(is_game_player(&conn, game_id, 1).unwrap(), Some(true));
+        assert_eq!(is_game_player(&conn, game_id, 2).unwrap(), Some(true));
+        // 관전자로 채팅을 보냈어도 플레이어가 아니다
+        assert_eq!(is_game_player(&conn, game_id, 3).unwrap(), Some(false));
+        assert_eq!(is_game_player(&conn, game_id + 1, 1).unwrap(), None);
+        assert_eq!(load_game_chat(&conn, game_id).unwrap(), vec![line]);
+    }
 }
@@ -1,8 +1,17 @@
 // kibo.rs
 
-use game_core::baduk_board::Color;
+use std::fmt::Display;
 
-#[derive(Debug, Clone, Copy)]
+use game_core::baduk_board::{
+    BadukBoardError, BadukBoardGameConfig, Color, baduk::Baduk, omok::Omok,
+};
+
+use crate::game_logic::baduk_board::sgf_color_char;
+
+/// SGF PC[] 에 기록하는 서버 이름
+pub const SERVER_NAME: &str = "doljabi";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GameKind {
     Baduk,
     Omok,
@@ -14,29 +23,143 @@ impl GameKind {
             GameKind::Omok => 4,
         }
     }
-    fn board_size(self) -> u8 {
+    pub fn board_size(self) -> u8 {
         match self {
             GameKind::Baduk => 19,
             GameKind::Omok => 15,
         }
     }
+    /// SGF RU[] 규칙 이름 (바둑: 중국 규칙, 오목: 흑 33·44·장목 금수)
+    fn rules(self) -> &'static str {
+        match self {
+            GameKind::Baduk => "Chinese",
+            GameKind::Omok => "Renju",
+        }
+    }
+    /// games.game_type 컬럼 값 ("baduk" | "omok")
+    pub fn game_type(self) -> &'static str {
+        match self {
+            GameKind::Baduk => "baduk",
+            GameKind::Omok => "omok",
+        }
+    }
+}
+
+/// SGF 기보 파싱·검증 실패 사유
+#[derive(Debug, Clone, PartialEq)]
+pub enum KiboError {
+    /// SGF 문법 오류
+    Syntax(String),
+    /// 서버가 다루지 않는 기보 (게임 종류, 판 크기, 배치돌 등)
+    Unsupported(String),
+    /// number번째 수가 규칙상 둘 수 없는 수
+    IllegalMove {
+        number: usize,
+        color: Color,
+        coordinate: String,
+        reason: String,
+    },
+}
+impl Display for KiboError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KiboError::Syntax(message) => write!(f, "SGF 문법 오류: {}", message),
+            KiboError::Unsupported(message) => write!(f, "지원하지 않는 기보: {}", message),
+            KiboError::IllegalMove {
+                number,
+                color,
+                coordinate,
+                reason,
+            } => write!(
+                f,
+                "{}번째 수 {}[{}]: {}",
+                number,
+                sgf_color_char(*color),
+                coordinate,
+                reason
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct Move {
     pub color: Color,
-    pub x: u8, // 0부터 시작 (0~14 → 15x15)
-    pub y: u8,
+    pub point: Option<(u8, u8)>, // 0부터 시작하는 (x, y) (0~14 → 15x15). None 이면 패스
+    pub clock: Option<MoveClock>, // 이 수를 둔 직후 남은 시간 (BL/WL, OB/OW)
+}
+
+/// 착수 직후 둔 사람의 남은 시간
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveClock {
+    /// 남은 시간(ms). 초읽기 중이면 한 번의 초읽기 시간
+    pub time_left_ms: u32,
+    /// 초읽기 중일 때 남은 초읽기 횟수 (기본 시간이 남아 있으면 None)
+    pub periods_left: Option<u8>,
+}
+
+/// 대국 중 채팅 한 줄. SGF 로 내보낼 때 보낸 시점의 수에 C[] 로 붙인다
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatLine {
+    /// 보낸 시점까지 둔 수 (0 이면 첫 수 전, 루트 노드)
+    pub move_number: usize,
+    /// 관전자 채팅이면 true
+    pub spectator: bool,
+    pub user_id: i64,
+    pub user_name: String,
+    pub text: String,
+    /// 보낸 시각 (Unix ms)
+    pub sent_at_ms: u64,
+}
+impl ChatLine {
+    /// C[] 에 넣는 한 줄. 관전자 채팅은 앞에 [관전] 을 붙인다
+    fn to_comment(&self) -> String {
+        match self.spectator {
+            true => format!("[관전] {}: {}", self.user_name, self.text),
+            false => format!("{}: {}", self.user_name, self.text),
+        }
+    }
+}
+
+/// 기보를 특정 수까지 다시 둔 국면 (기보 그림·디버깅용)
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub board_size: u8,
+    /// y * board_size + x 위치의 돌 색 (빈 자리는 Color::Free)
+    pub stones: Vec<Color>,
+    /// 각 자리에 놓여 있는 돌의 수 번호 (1부터)
+    pub move_numbers: Vec<Option<usize>>,
+    /// 지금까지 둔 수 (패스 포함)
+    pub move_number: usize,
+    pub last_move: Option<Move>,
+    /// 흑이 따낸 백돌 수
+    pub black_captures: u16,
+    /// 백이 따낸 흑돌 수
+    pub white_captures: u16,
+    /// 마지막 수로 따낸 돌의 좌표 (바둑)
+    pub captured: Vec<u16>,
+    /// 마지막 수로 생긴 패 자리 (바둑)
+    pub ko_point: Option<u16>,
+    /// 오목 5목 승자
+    pub winner: Option<Color>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SgfGame {
-    pub game_kind: GameKind,  // GM[] 태그 결정 (바둑/오목)
-    pub board_size: u8,       // SZ[15] 같은 거
-    pub black_player: String, // PB[]
-    pub white_player: String, // PW[]
-    pub result: String,       // RE[B+R] 등
-    pub moves: Vec<Move>,     // 수순 리스트
+    pub game_kind: GameKind,                      // GM[] 태그 결정 (바둑/오목)
+    pub board_size: u8,                           // SZ[15] 같은 거
+    pub black_player: String,                     // PB[]
+    pub white_player: String,                     // PW[]
+    pub black_rank: String,                       // BR[] (서버 대국은 레이팅 숫자)
+    pub white_rank: String,                       // WR[]
+    pub result: String,                           // RE[B+R] 등
+    pub date: String,                             // DT[YYYY-MM-DD]
+    pub place: String,                            // PC[] (서버 이름)
+    pub game_id: Option<i64>,                     // GN[] (games.id)
+    pub time_limit: Option<BadukBoardGameConfig>, // TM[] / OT[]
+    pub moves: Vec<Move>,                         // 수순 리스트
+    pub end_comment: String,                      // 마지막 노드의 C[] (기권·시간패 표기)
+    pub chat: Vec<ChatLine>,                      // 대국 중 채팅 (보낸 시점의 수에 C[] 로)
 }
 
 impl SgfGame {
@@ -47,8 +170,16 @@ impl SgfGame {
             board_size: game_kind.board_size(),
             black_player: "".to_string(),
             white_player: "".to_string(),
+            black_rank: String::new(),
+            white_rank: String::new(),
             result: String::new(), // 처음에는 결과 없음
+            date: String::new(),
+            place: SERVER_NAME.to_string(),
+            game_id: None,
+            time_limit: None,
             moves: Vec::new(),
+            end_comment: String::new(),
+            chat: Vec::new(),
         }
     }
 
@@ -71,9 +202,59 @@ impl SgfGame {
         self.white_player = white_player.to_string();
     }
 
-    /// 한 수 추가 (color, x, y)
+    /// 플레이어 레이팅(BR/WR) 기록
+    pub fn set_ratings(&mut self, black_rating: i32, white_rating: i32) {
+        self.black_rank = black_rating.to_string();
+        self.white_rank = white_rating.to_string();
+    }
+
+    /// 대국 날짜(DT)를 오늘(UTC)로 기록
+    pub fn set_date_today(&mut self) {
+        self.date = sgf_date_today();
+    }
+
+    /// 시간 설정(TM/OT) 기록
+    pub fn set_time_limit(&mut self, config: BadukBoardGameConfig) {
+        self.time_limit = Some(config);
+    }
+
+    /// games 테이블에 저장된 뒤 받은 게임 번호(GN) 기록
+    pub fn set_game_id(&mut self, game_id: i64) {
+        self.game_id = Some(game_id);
+    }
+
+    /// 한 수 추가 (color, x, y). SGF로 표현할 수 없는 좌표(판 밖, 52 이상)는 기록하지 않는다
     pub fn add_move(&mut self, color: Color, x: u8, y: u8) {
-        self.moves.push(Move { color, x, y });
+        if x >= self.board_size || y >= self.board_size || coord_to_sgf(x, y).is_none() {
+            eprintln!("기보 기록 에러: 잘못된 좌표 ({}, {})", x, y);
+            return;
+        }
+        self.moves.push(Move {
+            color,
+            point: Some((x, y)),
+            clock: None,
+        });
+    }
+
+    /// 패스 추가 (SGF B[] / W[])
+    pub fn add_pass(&mut self, color: Color) {
+        self.moves.push(Move {
+            color,
+            point: None,
+            clock: None,
+        });
+    }
+
+    /// 마지막 노드에 붙일 종료 사유 코멘트 (예: "흑 기권")
+    pub fn set_end_comment(&mut self, comment: &str) {
+        self.end_comment = comment.to_string();
+    }
+
+    /// 마지막 수에 착수 직후 남은 시간 기록
+    pub fn set_last_clock(&mut self, clock: MoveClock) {
+        if let Some(m) = self.moves.last_mut() {
+            m.clock = Some(clock);
+        }
     }
 
     /// SGF 문자열로 변환해서 돌려주는 메서드
@@ -84,8 +265,35 @@ impl SgfGame {
         s.push_str("(;FF[4]\n"); // SGF 포맷 버전
         s.push_str(&format!("GM[{}]\n", self.game_kind.gm_number())); // 게임 종류 (1 = 바둑, 오목도 그냥 1로 많이 씀)
         s.push_str(&format!("SZ[{}]\n", self.board_size));
-        s.push_str(&format!("PB[{}]\n", self.black_player));
-        s.push_str(&format!("PW[{}]\n", self.white_player));
+        s.push_str(&format!("RU[{}]\n", self.game_kind.rules()));
+        if self.game_kind == GameKind::Baduk {
+            // 현재 계가에는 덤·치석이 없다
+            s.push_str("KM[0]\nHA[0]\n");
+        }
+        if let Some(game_id) = self.game_id {
+            s.push_str(&format!("GN[{}]\n", game_id));
+        }
+        if !self.date.is_empty() {
+            s.push_str(&format!("DT[{}]\n", escape_sgf_text(&self.date)));
+        }
+        if !self.place.is_empty() {
+            s.push_str(&format!("PC[{}]\n", escape_sgf_text(&self.place)));
+        }
+        if let Some(time_limit) = &self.time_limit {
+            let (main_time, fischer_time, remaining_overtime, overtime) = time_limit.output();
+            s.push_str(&format!("TM[{}]\n", main_time / 1000));
+            if let Some(ot) = overtime_description(fischer_time, remaining_overtime, overtime) {
+                s.push_str(&format!("OT[{}]\n", ot));
+            }
+        }
+        s.push_str(&format!("PB[{}]\n", escape_sgf_text(&self.black_player)));
+        s.push_str(&format!("PW[{}]\n", escape_sgf_text(&self.white_player)));
+        if !self.black_rank.is_empty() {
+            s.push_str(&format!("BR[{}]\n", escape_sgf_text(&self.black_rank)));
+        }
+        if !self.white_rank.is_empty() {
+            s.push_str(&format!("WR[{}]\n", escape_sgf_text(&self.white_rank)));
+        }
 
         if self.result.is_empty() {
             s.push_str("RE[?]\n"); // 결과 모름
@@ -93,29 +301,540 @@ impl SgfGame {
             s.push_str(&format!("RE[{}]\n", self.result));
         }
 
+        // 첫 수 전 채팅, 수가 하나도 없으면 종료 코멘트도 루트 노드에 붙인다
+        let root_comment = self.node_comment(0);
+        if !root_comment.is_empty() {
+            s.push_str(&format!("C[{}]\n", escape_sgf_text(&root_comment)));
+        }
+
         s.push('\n');
 
         // --- 수순 부분 ---
-        for m in &self.moves {
-            let coord = coord_to_sgf(m.x, m.y); // ex) (0,0) -> "aa"
+        for (index, m) in self.moves.iter().enumerate() {
+            // ex) (0,0) -> "aa", 패스 -> ""
+            let coord = match m.point {
+                Some((x, y)) => match coord_to_sgf(x, y) {
+                    Some(coord) => coord,
+                    None => continue,
+                },
+                None => String::new(),
+            };
+
+            let color = match m.color {
+                Color::Black => "B",
+                Color::White => "W",
+                _ => continue, // Free, ColorError 는 기보에는 안 찍음
+            };
+            s.push_str(&format!(";{}[{}]", color, coord));
 
-            match m.color {
-                Color::Black => s.push_str(&format!(";B[{}]\n", coord)),
-                Color::White => s.push_str(&format!(";W[{}]\n", coord)),
-                _ => {} // Free, ColorError 는 기보에는 안 찍음
+            // 착수 직후 남은 시간: BL/WL(초), 초읽기 중이면 OB/OW(남은 횟수)
+            if let Some(clock) = &m.clock {
+                s.push_str(&format!(
+                    "{}L[{:.1}]",
+                    color,
+                    clock.time_left_ms as f64 / 1000.0
+                ));
+                if let Some(periods) = clock.periods_left {
+                    s.push_str(&format!("O{}[{}]", color, periods));
+                }
             }
+            let comment = self.node_comment(index + 1);
+            if !comment.is_empty() {
+                s.push_str(&format!("C[{}]", escape_sgf_text(&comment)));
+            }
+            s.push('\n');
         }
 
         s.push_str(")\n");
         s
     }
+
+    /// n수째 노드(0 이면 루트)의 C[]: 그 수 뒤에 오간 채팅, 마지막 노드면 종료 코멘트까지
+    fn node_comment(&self, n: usize) -> String {
+        let mut lines: Vec<String> = self
+            .chat
+            .iter()
+            .filter(|line| line.move_number == n)
+            .map(ChatLine::to_comment)
+            .collect();
+        if n == self.moves.len() && !self.end_comment.is_empty() {
+            lines.push(self.end_comment.clone());
+        }
+        lines.join("\n")
+    }
+
+    /// SGF 문자열을 읽어 SgfGame으로 만든다 (헤더 정리 + 주 수순 추출, 규칙 검사는 replay)
+    pub fn from_sgf_str(sgf: &str) -> Result<Self, KiboError> {
+        let nodes = parse_main_line(sgf)?;
+        let root = &nodes[0];
+
+        let size = match node_property(root, "SZ") {
+            Some(sz) => sz.trim().parse::<u8>().map_err(|_| {
+                KiboError::Unsupported(format!("판 크기 SZ[{}]는 지원하지 않습니다", sz))
+            })?,
+            None => GameKind::Baduk.board_size(),
+        };
+
+        // GM[]이 없으면 판 크기로 게임 종류를 추정한다 (migration_sgf_rusqlite 와 같은 기준)
+        let game_kind = match node_property(root, "GM").map(str::trim) {
+            Some("1") => GameKind::Baduk,
+            Some("4") => GameKind::Omok,
+            None if size == GameKind::Omok.board_size() => GameKind::Omok,
+            None => GameKind::Baduk,
+            Some(gm) => {
+                return Err(KiboError::Unsupported(format!(
+                    "게임 종류 GM[{}]는 지원하지 않습니다",
+                    gm
+                )));
+            }
+        };
+
+        if size != game_kind.board_size() {
+            return Err(KiboError::Unsupported(format!(
+                "{} 기보는 {}줄 판만 지원합니다 (SZ[{}])",
+                game_kind.game_type(),
+                game_kind.board_size(),
+                size
+            )));
+        }
+
+        let mut game = SgfGame::new(game_kind);
+        game.set_players(
+            node_property(root, "PB").unwrap_or_default().trim(),
+            node_property(root, "PW").unwrap_or_default().trim(),
+        );
+        if let Some(result) = node_property(root, "RE").and_then(normalize_result) {
+            game.set_result(&result);
+        }
+        game.black_rank = node_property(root, "BR")
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        game.white_rank = node_property(root, "WR")
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        game.date = node_property(root, "DT")
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        if let Some(place) = node_property(root, "PC") {
+            game.place = place.trim().to_string();
+        }
+        if let Some(comment) = nodes.last().and_then(|node| node_property(node, "C")) {
+            game.end_comment = comment.trim().to_string();
+        }
+
+        for node in &nodes {
+            for (ident, values) in node {
+                let color = match ident.as_str() {
+                    "B" => Color::Black,
+                    "W" => Color::White,
+                    "AB" | "AW" | "AE" => {
+                        return Err(KiboError::Unsupported(
+                            "배치돌(AB/AW/AE)이 있는 기보는 지원하지 않습니다".to_string(),
+                        ));
+                    }
+                    _ => continue,
+                };
+
+                let number = game.moves.len() + 1;
+                let value = values[0].trim();
+                // FF[3] 까지는 19줄 이하에서 "tt" 도 패스
+                let is_pass = value.is_empty() || (value == "tt" && size <= 19);
+                let point = match sgf_to_coord(value) {
+                    _ if is_pass => None,
+                    Some(coord) => Some(coord),
+                    None => {
+                        return Err(KiboError::IllegalMove {
+                            number,
+                            color,
+                            coordinate: value.to_string(),
+                            reason: "좌표 형식 오류".to_string(),
+                        });
+                    }
+                };
+                match point {
+                    Some((x, y)) if x >= size || y >= size => {
+                        return Err(KiboError::IllegalMove {
+                            number,
+                            color,
+                            coordinate: value.to_string(),
+                            reason: illegal_reason(&BadukBoardError::OutOfBoard).to_string(),
+                        });
+                    }
+                    Some((x, y)) => game.add_move(color, x, y),
+                    None => game.add_pass(color),
+                }
+
+                // 같은 노드의 BL/WL, OB/OW 를 그 수의 남은 시간으로 읽는다
+                let clock_ident = format!("{}L", ident);
+                if let Some(seconds) =
+                    node_property(node, &clock_ident).and_then(|v| v.trim().parse::<f64>().ok())
+                {
+                    let periods_ident = format!("O{}", ident);
+                    game.set_last_clock(MoveClock {
+                        time_left_ms: (seconds.max(0.0) * 1000.0) as u32,
+                        periods_left: node_property(node, &periods_ident)
+                            .and_then(|v| v.trim().parse::<u8>().ok()),
+                    });
+                }
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// 수순 전체를 game_core(Baduk/Omok)로 다시 두어 규칙 위반을 검사한다.
+    /// 오목에서 5목으로 승부가 나면 그 승자를 돌려준다.
+    pub fn replay(&self) -> Result<Option<Color>, KiboError> {
+        Ok(self.position_at(self.moves.len())?.winner)
+    }
+
+    /// 첫 수부터 count수까지 game_core(Baduk/Omok)로 다시 둔 국면.
+    /// count 가 수순 길이보다 크면 마지막 수까지 둔다.
+    pub fn position_at(&self, count: usize) -> Result<Position, KiboError> {
+        let mut baduk = Baduk::new();
+        let mut omok = Omok::new();
+        // (정수 좌표, 수 번호) — 나중 수가 같은 자리를 덮어쓴다
+        let mut placed: Vec<(u16, usize)> = Vec::new();
+
+        for (index, m) in self.moves.iter().take(count).enumerate() {
+            let illegal = |reason: &str| KiboError::IllegalMove {
+                number: index + 1,
+                color: m.color,
+                coordinate: m
+                    .point
+                    .and_then(|(x, y)| coord_to_sgf(x, y))
+                    .unwrap_or_default(),
+                reason: reason.to_string(),
+            };
+
+            if omok.winner().is_some() {
+                return Err(illegal("승부가 결정된 뒤의 수"));
+            }
+            let board = match self.game_kind {
+                GameKind::Baduk => baduk.is_board(),
+                GameKind::Omok => omok.is_board(),
+            };
+            if m.color != board.is_turn() {
+                return Err(illegal("차례가 아닌 색의 수"));
+            }
+
+            // 패스: 대국 방(BadukRoom/OmokRoom)과 똑같이 처리한다 (바둑은 패 자리도 풀린다)
+            let Some((x, y)) = m.point else {
+                match self.game_kind {
+                    GameKind::Baduk => baduk.pass(),
+                    GameKind::Omok => omok.board.switch_turn(),
+                }
+                continue;
+            };
+
+            let coordinate = board.xy_expression_to_integer_expression(x as u16, y as u16);
+            board
+                .check_outboard_coordinate(coordinate)
+                .map_err(|e| illegal(illegal_reason(&e)))?;
+
+            let result = match self.game_kind {
+                GameKind::Baduk => baduk.chaksu(coordinate),
+                GameKind::Omok => omok.chaksu(coordinate, true),
+            };
+            result.map_err(|e| illegal(illegal_reason(&e)))?;
+            placed.push((coordinate, index + 1));
+        }
+
+        let board = match self.game_kind {
+            GameKind::Baduk => baduk.is_board(),
+            GameKind::Omok => omok.is_board(),
+        };
+        let points = self.board_size as usize * self.board_size as usize;
+        let stones: Vec<Color> = (0..points as u16).map(|c| board.is_color(c)).collect();
+
+        // 따낸 돌 자리에는 번호를 남기지 않는다
+        let mut move_numbers = vec![None; points];
+        for (coordinate, number) in placed {
+            if stones[coordinate as usize] != Color::Free {
+                move_numbers[coordinate as usize] = Some(number);
+            }
+        }
+
+        let move_number = count.min(self.moves.len());
+        Ok(Position {
+            board_size: self.board_size,
+            stones,
+            move_numbers,
+            move_number,
+            last_move: move_number.checked_sub(1).map(|i| self.moves[i]),
+            black_captures: baduk.black_caught_stone,
+            white_captures: baduk.white_caught_stone,
+            captured: baduk.last_captured().to_vec(),
+            ko_point: baduk.get_ko_position(),
+            winner: omok.winner(),
+        })
+    }
+}
+
+/// 0-based 한 축 값 → SGF 좌표 문자 (0~25 → 'a'~'z', 26~51 → 'A'~'Z')
+fn axis_to_sgf(value: u8) -> Option<char> {
+    match value {
+        0..=25 => Some((b'a' + value) as char),
+        26..=51 => Some((b'A' + value - 26) as char),
+        _ => None,
+    }
 }
 
-/// 0-based (x,y) → SGF 좌표 문자열 ("aa", "ab", "cc"...)
-fn coord_to_sgf(x: u8, y: u8) -> String {
-    let cx = (b'a' + x) as char; // 0 -> 'a', 1 -> 'b'
-    let cy = (b'a' + y) as char;
-    format!("{cx}{cy}")
+/// 0-based (x,y) → SGF 좌표 문자열 ("aa", "ab", "cc"...). 52줄 이상은 None
+fn coord_to_sgf(x: u8, y: u8) -> Option<String> {
+    Some(format!("{}{}", axis_to_sgf(x)?, axis_to_sgf(y)?))
+}
+
+/// SGF Text/SimpleText 값 escape (']' 와 '\\' 앞에 '\\')
+fn escape_sgf_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ']' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 초읽기·피셔 설정 → SGF OT[] 문자열 (예: "3x30 byo-yomi", "3x30 byo-yomi + 10 fischer")
+fn overtime_description(
+    fischer_time: u32,
+    remaining_overtime: u8,
+    overtime: u32,
+) -> Option<String> {
+    let mut parts = Vec::new();
+    if remaining_overtime > 0 {
+        parts.push(format!(
+            "{}x{} byo-yomi",
+            remaining_overtime,
+            overtime / 1000
+        ));
+    }
+    if fischer_time > 0 {
+        parts.push(format!("{} fischer", fischer_time / 1000));
+    }
+    (!parts.is_empty()).then(|| parts.join(" + "))
+}
+
+/// 오늘 날짜(UTC)를 SGF DT[] 형식(YYYY-MM-DD)으로
+fn sgf_date_today() -> String {
+    let days = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86400)
+        .unwrap_or(0) as i64;
+
+    // 1970-01-01 기준 일수 → 그레고리력 날짜 (H. Hinnant, civil_from_days)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// SGF 좌표 문자열 ("aa", "pd" ...) → 0-based (x,y). 형식이 틀리면 None
+fn sgf_to_coord(value: &str) -> Option<(u8, u8)> {
+    let axis = |c: u8| match c {
+        b'a'..=b'z' => Some(c - b'a'),
+        b'A'..=b'Z' => Some(c - b'A' + 26),
+        _ => None,
+    };
+    match value.as_bytes() {
+        [cx, cy] => Some((axis(*cx)?, axis(*cy)?)),
+        _ => None,
+    }
+}
+
+/// game_core 착수 에러 → 사람이 읽을 수 있는 사유
+fn illegal_reason(error: &BadukBoardError) -> &'static str {
+    match error {
+        BadukBoardError::OutOfBoard => "판 밖의 좌표",
+        BadukBoardError::OverLap => "이미 돌이 있는 자리",
+        BadukBoardError::BannedChaksu => "금수(착수 금지)",
+        BadukBoardError::Ko => "패(바로 되따낼 수 없음)",
+        BadukBoardError::InvalidArgument => "잘못된 착수",
+    }
+}
+
+/// RE[] 값을 서버 표기(sgf_result 형식)로 정리한다. 알 수 없는 값이면 None
+pub fn normalize_result(result: &str) -> Option<String> {
+    let result = result.trim();
+    match result.to_ascii_lowercase().as_str() {
+        "0" | "draw" | "jigo" => return Some("Draw".to_string()),
+        "" | "?" | "void" => return None,
+        _ => {}
+    }
+
+    let (winner, detail) = result.split_once('+')?;
+    let winner = match winner.trim() {
+        "B" | "b" => "B",
+        "W" | "w" => "W",
+        _ => return None,
+    };
+    let detail = detail.trim();
+    let detail = match detail.to_ascii_lowercase().as_str() {
+        "" => String::new(),
+        "r" | "resign" => "R".to_string(),
+        "t" | "time" => "T".to_string(),
+        "f" | "forfeit" => "F".to_string(),
+        _ => match detail.parse::<f32>() {
+            Ok(score) if score >= 0.0 => detail.to_string(),
+            _ => return None,
+        },
+    };
+    Some(format!("{}+{}", winner, detail))
+}
+
+/// SGF 노드 하나: (속성 이름, 값 목록) 리스트
+type SgfNode = Vec<(String, Vec<String>)>;
+
+/// 최소한의 SGF 파서. 변화도가 있으면 각 분기점에서 첫 번째 변화도(주 수순)만 따라간다.
+struct SgfParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+impl SgfParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, message: &str) -> KiboError {
+        KiboError::Syntax(format!("{} (위치 {})", message, self.pos))
+    }
+
+    fn parse_game_tree(&mut self, nodes: &mut Vec<SgfNode>) -> Result<(), KiboError> {
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return Err(self.error("'('가 필요합니다"));
+        }
+        self.pos += 1;
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(';') => {
+                    self.pos += 1;
+                    nodes.push(self.parse_node()?);
+                }
+                Some('(') => {
+                    // 첫 번째 변화도만 주 수순으로 읽고 나머지 형제 변화도는 버린다
+                    self.parse_game_tree(nodes)?;
+                    loop {
+                        self.skip_whitespace();
+                        if self.peek() != Some('(') {
+                            break;
+                        }
+                        self.parse_game_tree(&mut Vec::new())?;
+                    }
+                }
+                Some(')') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(c) => return Err(self.error(&format!("예상하지 못한 문자 '{}'", c))),
+                None => return Err(self.error("')'가 닫히지 않았습니다")),
+            }
+        }
+    }
+
+    fn parse_node(&mut self) -> Result<SgfNode, KiboError> {
+        let mut node = SgfNode::new();
+
+        loop {
+            self.skip_whitespace();
+            if !self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                return Ok(node);
+            }
+
+            // FF[3] 이전의 소문자 섞인 속성 이름(CoPyright 등)은 대문자만 남긴다
+            let mut ident = String::new();
+            while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+                if c.is_ascii_uppercase() {
+                    ident.push(c);
+                }
+                self.pos += 1;
+            }
+
+            let mut values = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some('[') {
+                    break;
+                }
+                self.pos += 1;
+                values.push(self.parse_value()?);
+            }
+            if values.is_empty() {
+                return Err(self.error(&format!("{} 속성에 값이 없습니다", ident)));
+            }
+            node.push((ident, values));
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<String, KiboError> {
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        // 줄바꿈 escape 는 soft line break 이므로 버린다
+                        Some('\n') | Some('\r') => {}
+                        Some(c) => value.push(c),
+                        None => break,
+                    }
+                    self.pos += 1;
+                }
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+                None => break,
+            }
+        }
+        Err(self.error("']'가 닫히지 않았습니다"))
+    }
+}
+
+/// SGF 본문에서 주 수순의 노드 목록을 뽑아낸다. (여러 게임이 있으면 첫 게임만)
+fn parse_main_line(sgf: &str) -> Result<Vec<SgfNode>, KiboError> {
+    let mut parser = SgfParser {
+        chars: sgf.chars().collect(),
+        pos: 0,
+    };
+    let mut nodes = Vec::new();
+    parser.parse_game_tree(&mut nodes)?;
+    if nodes.is_empty() {
+        return Err(KiboError::Syntax("노드가 없습니다".to_string()));
+    }
+    Ok(nodes)
+}
+
+/// 노드에서 속성의 첫 번째 값을 찾는다
+fn node_property<'a>(node: &'a SgfNode, ident: &str) -> Option<&'a str> {
+    node.iter()
+        .find(|(key, _)| key == ident)
+        .and_then(|(_, values)| values.first())
+        .map(|value| value.as_str())
 }
 
 use std::collections::HashMap;
@@ -140,4 +859,166 @@ mod tests {
         println!("{}", game.to_sgf_string());
         println!("===========================");
     }
+
+    #[test]
+    fn sgf_parse_roundtrip_test() {
+        let mut game = SgfGame::baduk();
+        game.add_move(Color::Black, 3, 3);
+        game.add_move(Color::White, 15, 15);
+        game.set_players("흑돌", "백[돌]");
+        game.set_result("W+R");
+        game.set_date_today();
+        game.set_time_limit(BadukBoardGameConfig::new(600000, 0, 3, 30000));
+        game.set_last_clock(MoveClock {
+            time_left_ms: 30000,
+            periods_left: Some(2),
+        });
+
+        let sgf = game.to_sgf_string();
+        assert!(sgf.contains("TM[600]\nOT[3x30 byo-yomi]\n"));
+        assert!(sgf.contains(";W[pp]WL[30.0]OW[2]\n"));
+
+        let parsed = SgfGame::from_sgf_str(&sgf).unwrap();
+        assert_eq!(parsed.game_kind, GameKind::Baduk);
+        assert_eq!(parsed.white_player, "백[돌]");
+        assert_eq!(parsed.date, game.date);
+        assert_eq!(parsed.moves[1].clock, game.moves[1].clock);
+        assert_eq!(parsed.result, "W+R");
+        assert_eq!(parsed.moves.len(), 2);
+        assert_eq!(parsed.replay(), Ok(None));
+    }
+
+    #[test]
+    fn sgf_parse_variation_test() {
+        // 변화도는 첫 번째 분기만 따라간다
+        let sgf = "(;GM[1]SZ[19]RE[b+resign];B[pd](;W[dp];B[pp])(;W[dd]))";
+        let parsed = SgfGame::from_sgf_str(sgf).unwrap();
+        assert_eq!(parsed.result, "B+R");
+        assert_eq!(parsed.moves.len(), 3);
+        assert_eq!(parsed.moves[1].point, Some((3, 15)));
+    }
+
+    #[test]
+    fn sgf_pass_test() {
+        let mut game = SgfGame::baduk();
+        game.add_move(Color::Black, 3, 3);
+        game.add_pass(Color::White);
+        game.add_move(Color::Black, 15, 3);
+        game.add_pass(Color::White);
+        game.add_pass(Color::Black);
+        game.set_end_comment("계가");
+
+        let sgf = game.to_sgf_string();
+        assert!(sgf.contains(";W[]\n;B[pd]\n;W[]\n;B[]C[계가]\n"));
+
+        let parsed = SgfGame::from_sgf_str(&sgf).unwrap();
+        assert_eq!(parsed.moves[1].point, None);
+        assert_eq!(parsed.end_comment, "계가");
+        assert_eq!(parsed.replay(), Ok(None));
+
+        // 19줄 이하의 "tt" 도 패스로 읽는다
+        let parsed = SgfGame::from_sgf_str("(;GM[1]SZ[19];B[tt];W[dd])").unwrap();
+        assert_eq!(parsed.moves[0].point, None);
+        assert_eq!(parsed.replay(), Ok(None));
+    }
+
+    #[test]
+    fn sgf_chat_comment_test() {
+        let mut game = SgfGame::baduk();
+        let chat = |move_number, spectator, text: &str| ChatLine {
+            move_number,
+            spectator,
+            user_id: 1,
+            user_name: "흑돌".to_string(),
+            text: text.to_string(),
+            sent_at_ms: 0,
+        };
+        game.chat.push(chat(0, false, "잘 부탁드립니다"));
+        game.add_move(Color::Black, 3, 3);
+        game.chat.push(chat(1, true, "좋은 수"));
+        game.add_pass(Color::White);
+        game.set_end_comment("백 기권");
+        game.chat.push(chat(2, false, "수고하셨습니다"));
+
+        let sgf = game.to_sgf_string();
+        assert!(sgf.contains("C[흑돌: 잘 부탁드립니다]\n"));
+        assert!(sgf.contains(";B[dd]C[[관전\\] 흑돌: 좋은 수]\n"));
+        assert!(sgf.contains(";W[]C[흑돌: 수고하셨습니다\n백 기권]\n"));
+    }
+
+    #[test]
+    fn sgf_header_test() {
+        let mut game = SgfGame::baduk();
+        game.add_move(Color::Black, 3, 3);
+        game.set_players("흑돌", "백돌");
+        game.set_ratings(1500, 1420);
+        game.set_result("B+R");
+        game.date = "2024-05-01".to_string();
+        game.set_game_id(42);
+        game.set_time_limit(BadukBoardGameConfig::new(600000, 0, 3, 30000));
+        game.set_last_clock(MoveClock {
+            time_left_ms: 28500,
+            periods_left: Some(3),
+        });
+
+        let sgf = game.to_sgf_string();
+        for tag in [
+            "DT[2024-05-01]",
+            "RU[Chinese]",
+            "KM[0]",
+            "TM[600]",
+            "OT[3x30 byo-yomi]",
+            "GN[42]",
+            "BR[1500]",
+            "WR[1420]",
+            ";B[dd]BL[28.5]OB[3]",
+        ] {
+            assert!(sgf.contains(tag), "{} 없음:\n{}", tag, sgf);
+        }
+
+        // 오목은 덤이 없고 규칙은 렌주
+        let sgf = SgfGame::omok().to_sgf_string();
+        assert!(sgf.contains("RU[Renju]"));
+        assert!(!sgf.contains("KM["));
+    }
+
+    #[test]
+    fn sgf_coordinate_test() {
+        assert_eq!(coord_to_sgf(0, 25).as_deref(), Some("az"));
+        assert_eq!(coord_to_sgf(26, 51).as_deref(), Some("AZ"));
+        assert_eq!(coord_to_sgf(52, 0), None);
+        assert_eq!(sgf_to_coord("AZ"), Some((26, 51)));
+        assert_eq!(sgf_to_coord("a1"), None);
+    }
+
+    #[test]
+    fn sgf_replay_ko_pass_test() {
+        // 흑이 패를 따낸 뒤(db) 백이 패스하면, 흑은 따낸 자리(cb)를 바로 이을 수 있다
+        let sgf = "(;GM[1]SZ[19];B[bb];W[da];B[ca];W[dc];B[cc];W[eb];B[kk];W[cb];B[db];W[];B[cb])";
+        let game = SgfGame::from_sgf_str(sgf).unwrap();
+        assert_eq!(game.replay(), Ok(None));
+
+        let position = game.position_at(game.moves.len()).unwrap();
+        assert_eq!(position.stones[19 + 2], Color::Black);
+        assert_eq!(position.black_captures, 1);
+    }
+
+    #[test]
+    fn sgf_replay_illegal_move_test() {
+        let sgf = "(;GM[1]SZ[19];B[aa];W[bb];B[aa])";
+        let error = SgfGame::from_sgf_str(sgf).unwrap().replay().unwrap_err();
+        assert_eq!(
+            error,
+            KiboError::IllegalMove {
+                number: 3,
+                color: Color::Black,
+                coordinate: "aa".to_string(),
+                reason: "이미 돌이 있는 자리".to_string(),
+            }
+        );
+
+        let sgf = "(;GM[4]SZ[15];B[aa];W[ba];B[ab];W[bb];B[ac];W[bc];B[ad];W[bd];B[ae];W[be])";
+        let error = SgfGame::from_sgf_str(sgf).unwrap().replay().unwrap_err();
+        assert!(matches!(error, KiboError::IllegalMove { number: 10, .. }));
+    }
 }
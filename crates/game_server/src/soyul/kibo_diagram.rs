@@ -0,0 +1,632 @@
+// kibo_diagram.rs
+//
+// 기보 국면(Position)을 바둑판 그림으로 그린다.
+// 먼저 선·원·글자로 이루어진 도형 목록(Shape)을 만들고,
+//  - SVG: 도형을 그대로 SVG 요소로 출력
+//  - PNG: 도형을 직접 래스터화(안티에일리어싱 + 5x7 비트맵 글꼴)해서 png 로 인코딩
+// 하므로 브라우저나 시스템 글꼴 없이 서버 안에서 그림이 나온다.
+
+use game_core::baduk_board::Color;
+
+use crate::soyul::kibo::{GameKind, Position};
+
+/// 한 칸 크기(px)
+const CELL: f32 = 32.0;
+/// 가장자리 선에서 그림 끝까지 거리(px). 가장자리 돌 바깥에 좌표 글자가 들어간다
+const MARGIN: f32 = CELL * 1.5;
+/// 그림 아래 수 번호·따낸 돌 표시 줄 높이(px)
+const CAPTION: f32 = CELL;
+
+type Rgb = [u8; 3];
+const BOARD_COLOR: Rgb = [0xDC, 0xB3, 0x5C];
+const LINE_COLOR: Rgb = [0x20, 0x20, 0x20];
+const BLACK_STONE: Rgb = [0x10, 0x10, 0x10];
+const WHITE_STONE: Rgb = [0xFA, 0xFA, 0xFA];
+const LAST_MOVE_COLOR: Rgb = [0xE0, 0x20, 0x20];
+
+/// 그림 옵션
+#[derive(Debug, Clone, Copy)]
+pub struct DiagramOptions {
+    /// 돌 위에 수 번호를 쓸지
+    pub move_numbers: bool,
+}
+
+/// 그림을 이루는 도형 하나 (좌표는 px)
+enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        fill: Rgb,
+    },
+    Line {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        width: f32,
+        stroke: Rgb,
+    },
+    Circle {
+        cx: f32,
+        cy: f32,
+        r: f32,
+        fill: Option<Rgb>,
+        stroke: Option<(Rgb, f32)>,
+    },
+    /// (x, y) 를 가운데로 하는 한 줄 글자
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        text: String,
+        fill: Rgb,
+    },
+}
+
+/// 가로줄 좌표 글자. 바둑은 관례대로 I 를 건너뛰고, 오목(렌주)은 A~O 를 그대로 쓴다
+fn column_label(game_kind: GameKind, x: u8) -> String {
+    let letter = match game_kind {
+        GameKind::Baduk if x >= 8 => b'A' + x + 1,
+        _ => b'A' + x,
+    };
+    (letter as char).to_string()
+}
+
+/// 화점 위치 (0부터)
+fn star_points(board_size: u8) -> Vec<(u8, u8)> {
+    let lines: &[u8] = match board_size {
+        19 => &[3, 9, 15],
+        15 => &[3, 7, 11],
+        13 => &[3, 6, 9],
+        9 => &[2, 4, 6],
+        _ => &[],
+    };
+    lines
+        .iter()
+        .flat_map(|&x| lines.iter().map(move |&y| (x, y)))
+        .collect()
+}
+
+fn canvas_size(board_size: u8) -> (f32, f32) {
+    let board = MARGIN * 2.0 + (board_size as f32 - 1.0) * CELL;
+    (board, board + CAPTION)
+}
+
+/// 국면 → 도형 목록
+fn layout(game_kind: GameKind, position: &Position, options: DiagramOptions) -> Vec<Shape> {
+    let size = position.board_size;
+    let (width, height) = canvas_size(size);
+    let far = MARGIN + (size as f32 - 1.0) * CELL;
+    let at = |i: u8| MARGIN + i as f32 * CELL;
+    let mut shapes = Vec::new();
+
+    shapes.push(Shape::Rect {
+        x: 0.0,
+        y: 0.0,
+        w: width,
+        h: height,
+        fill: BOARD_COLOR,
+    });
+
+    // 줄, 화점, 좌표
+    for i in 0..size {
+        shapes.push(Shape::Line {
+            x1: at(i),
+            y1: MARGIN,
+            x2: at(i),
+            y2: far,
+            width: 1.0,
+            stroke: LINE_COLOR,
+        });
+        shapes.push(Shape::Line {
+            x1: MARGIN,
+            y1: at(i),
+            x2: far,
+            y2: at(i),
+            width: 1.0,
+            stroke: LINE_COLOR,
+        });
+    }
+    for (x, y) in star_points(size) {
+        shapes.push(Shape::Circle {
+            cx: at(x),
+            cy: at(y),
+            r: 3.5,
+            fill: Some(LINE_COLOR),
+            stroke: None,
+        });
+    }
+    for i in 0..size {
+        let column = column_label(game_kind, i);
+        let row = (size - i).to_string();
+        for (x, y, text) in [
+            (at(i), MARGIN - CELL, &column),
+            (at(i), far + CELL, &column),
+            (MARGIN - CELL, at(i), &row),
+            (far + CELL, at(i), &row),
+        ] {
+            shapes.push(Shape::Text {
+                x,
+                y,
+                size: 14.0,
+                text: text.clone(),
+                fill: LINE_COLOR,
+            });
+        }
+    }
+
+    // 돌과 수 번호
+    let last_point = position.last_move.and_then(|m| m.point);
+    for y in 0..size {
+        for x in 0..size {
+            let index = y as usize * size as usize + x as usize;
+            let (fill, ink) = match position.stones[index] {
+                Color::Black => (BLACK_STONE, WHITE_STONE),
+                Color::White => (WHITE_STONE, BLACK_STONE),
+                _ => continue,
+            };
+            shapes.push(Shape::Circle {
+                cx: at(x),
+                cy: at(y),
+                r: CELL / 2.0 - 1.0,
+                fill: Some(fill),
+                stroke: Some((BLACK_STONE, 1.0)),
+            });
+
+            let is_last = last_point == Some((x, y));
+            match position.move_numbers[index] {
+                Some(number) if options.move_numbers => {
+                    let text = number.to_string();
+                    shapes.push(Shape::Text {
+                        x: at(x),
+                        y: at(y),
+                        size: if text.len() > 2 { 10.0 } else { 14.0 },
+                        text,
+                        fill: if is_last { LAST_MOVE_COLOR } else { ink },
+                    });
+                }
+                _ if is_last => shapes.push(Shape::Circle {
+                    cx: at(x),
+                    cy: at(y),
+                    r: CELL / 4.0,
+                    fill: None,
+                    stroke: Some((LAST_MOVE_COLOR, 2.0)),
+                }),
+                _ => {}
+            }
+        }
+    }
+
+    // 아래 줄: 몇 수째인지, 따낸 돌 (PNG 글꼴에 맞춰 영문 대문자)
+    let mut caption = format!("MOVE {}", position.move_number);
+    if position.last_move.is_some_and(|m| m.point.is_none()) {
+        caption.push_str(" PASS");
+    }
+    if game_kind == GameKind::Baduk {
+        caption.push_str(&format!(
+            "   B CAPTURES {}   W CAPTURES {}",
+            position.black_captures, position.white_captures
+        ));
+    }
+    shapes.push(Shape::Text {
+        x: width / 2.0,
+        y: height - CAPTION / 2.0 - 4.0,
+        size: 14.0,
+        text: caption,
+        fill: LINE_COLOR,
+    });
+
+    shapes
+}
+
+//
+// ── SVG ──
+//
+
+fn svg_color(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// 국면 → SVG 문서
+pub fn render_svg(game_kind: GameKind, position: &Position, options: DiagramOptions) -> String {
+    let (width, height) = canvas_size(position.board_size);
+    let mut s = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+        w = width,
+        h = height
+    );
+
+    for shape in layout(game_kind, position, options) {
+        match shape {
+            Shape::Rect { x, y, w, h, fill } => s.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                x,
+                y,
+                w,
+                h,
+                svg_color(fill)
+            )),
+            Shape::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                width,
+                stroke,
+            } => s.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                x1,
+                y1,
+                x2,
+                y2,
+                svg_color(stroke),
+                width
+            )),
+            Shape::Circle {
+                cx,
+                cy,
+                r,
+                fill,
+                stroke,
+            } => {
+                let fill = fill.map(svg_color).unwrap_or_else(|| "none".to_string());
+                let stroke = match stroke {
+                    Some((color, width)) => format!(
+                        " stroke=\"{}\" stroke-width=\"{}\"",
+                        svg_color(color),
+                        width
+                    ),
+                    None => String::new(),
+                };
+                s.push_str(&format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"{}/>\n",
+                    cx, cy, r, fill, stroke
+                ));
+            }
+            Shape::Text {
+                x,
+                y,
+                size,
+                text,
+                fill,
+            } => s.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">{}</text>\n",
+                x,
+                y,
+                size,
+                svg_color(fill),
+                text.replace('&', "&amp;").replace('<', "&lt;")
+            )),
+        }
+    }
+
+    s.push_str("</svg>\n");
+    s
+}
+
+//
+// ── PNG ──
+//
+
+/// RGB 캔버스. 도형마다 픽셀 중심까지의 거리로 덮임 정도(0~1)를 구해 섞는다
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![0xFF; width * height * 3],
+        }
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Rgb, alpha: f32) {
+        if x >= self.width || y >= self.height || alpha <= 0.0 {
+            return;
+        }
+        let alpha = alpha.min(1.0);
+        let offset = (y * self.width + x) * 3;
+        for (channel, value) in color.iter().enumerate() {
+            let old = self.pixels[offset + channel] as f32;
+            self.pixels[offset + channel] = (old + (*value as f32 - old) * alpha).round() as u8;
+        }
+    }
+
+    /// (x0, y0) ~ (x1, y1) 범위 픽셀마다 coverage(픽셀 중심) 를 계산해 칠한다
+    fn paint(
+        &mut self,
+        bounds: (f32, f32, f32, f32),
+        color: Rgb,
+        coverage: impl Fn(f32, f32) -> f32,
+    ) {
+        let (x0, y0, x1, y1) = bounds;
+        let clamp_x = |v: f32| (v.max(0.0) as usize).min(self.width);
+        let clamp_y = |v: f32| (v.max(0.0) as usize).min(self.height);
+        let (x0, x1) = (clamp_x(x0.floor()), clamp_x(x1.ceil() + 1.0));
+        let (y0, y1) = (clamp_y(y0.floor()), clamp_y(y1.ceil() + 1.0));
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let alpha = coverage(x as f32 + 0.5, y as f32 + 0.5).clamp(0.0, 1.0);
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+
+    fn draw(&mut self, shape: &Shape) {
+        match *shape {
+            Shape::Rect { x, y, w, h, fill } => {
+                self.paint((x, y, x + w, y + h), fill, |px, py| {
+                    let inside_x = (px - x + 0.5).min(x + w - px + 0.5);
+                    let inside_y = (py - y + 0.5).min(y + h - py + 0.5);
+                    inside_x.min(inside_y)
+                });
+            }
+            Shape::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                width,
+                stroke,
+            } => {
+                let half = width / 2.0;
+                let bounds = (
+                    x1.min(x2) - half,
+                    y1.min(y2) - half,
+                    x1.max(x2) + half,
+                    y1.max(y2) + half,
+                );
+                self.paint(bounds, stroke, |px, py| {
+                    half - segment_distance(px, py, (x1, y1), (x2, y2)) + 0.5
+                });
+            }
+            Shape::Circle {
+                cx,
+                cy,
+                r,
+                fill,
+                stroke,
+            } => {
+                let bounds = (cx - r - 2.0, cy - r - 2.0, cx + r + 2.0, cy + r + 2.0);
+                let distance =
+                    move |px: f32, py: f32| ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+                if let Some(fill) = fill {
+                    self.paint(bounds, fill, |px, py| r - distance(px, py) + 0.5);
+                }
+                if let Some((color, width)) = stroke {
+                    self.paint(bounds, color, |px, py| {
+                        width / 2.0 - (distance(px, py) - r).abs() + 0.5
+                    });
+                }
+            }
+            Shape::Text {
+                x,
+                y,
+                size,
+                ref text,
+                fill,
+            } => self.draw_text(x, y, size, text, fill),
+        }
+    }
+
+    /// 5x7 비트맵 글꼴을 정수 배율로 키워 (x, y) 가운데에 찍는다
+    fn draw_text(&mut self, x: f32, y: f32, size: f32, text: &str, fill: Rgb) {
+        let scale = (size / 7.0).round().max(1.0);
+        let count = text.chars().count() as f32;
+        let width = (count * 6.0 - 1.0) * scale;
+        let left = (x - width / 2.0).round();
+        let top = (y - 3.5 * scale).round();
+
+        for (i, c) in text.chars().enumerate() {
+            let rows = glyph(c);
+            for (row, bits) in rows.iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) == 0 {
+                        continue;
+                    }
+                    let px = left + (i as f32 * 6.0 + col as f32) * scale;
+                    let py = top + row as f32 * scale;
+                    self.paint(
+                        (px, py, px + scale - 1.0, py + scale - 1.0),
+                        fill,
+                        |qx, qy| {
+                            if qx >= px && qx < px + scale && qy >= py && qy < py + scale {
+                                1.0
+                            } else {
+                                0.0
+                            }
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// 점 (px, py) 와 선분 a-b 사이 거리
+fn segment_distance(px: f32, py: f32, a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((px - a.0) * dx + (py - a.1) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let (nx, ny) = (a.0 + t * dx, a.1 + t * dy);
+    ((px - nx).powi(2) + (py - ny).powi(2)).sqrt()
+}
+
+/// 5x7 글꼴 (한 줄 5비트, 왼쪽이 최상위 비트). 모르는 글자는 빈칸
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        '0' => [
+            0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110,
+        ],
+        '1' => [
+            0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        '2' => [
+            0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+        ],
+        '3' => [
+            0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+        ],
+        '4' => [
+            0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+        ],
+        '5' => [
+            0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+        ],
+        '6' => [
+            0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+        ],
+        '7' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+        ],
+        '8' => [
+            0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+        ],
+        '9' => [
+            0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+        ],
+        'A' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001,
+        ],
+        'B' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110,
+        ],
+        'C' => [
+            0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110,
+        ],
+        'D' => [
+            0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100,
+        ],
+        'E' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111,
+        ],
+        'F' => [
+            0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'G' => [
+            0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111,
+        ],
+        'H' => [
+            0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001,
+        ],
+        'I' => [
+            0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+        ],
+        'J' => [
+            0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100,
+        ],
+        'K' => [
+            0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001,
+        ],
+        'L' => [
+            0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111,
+        ],
+        'M' => [
+            0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001,
+        ],
+        'N' => [
+            0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001,
+        ],
+        'O' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'P' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000,
+        ],
+        'Q' => [
+            0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101,
+        ],
+        'R' => [
+            0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001,
+        ],
+        'S' => [
+            0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110,
+        ],
+        'T' => [
+            0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100,
+        ],
+        'U' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110,
+        ],
+        'V' => [
+            0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100,
+        ],
+        'W' => [
+            0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010,
+        ],
+        'X' => [
+            0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001,
+        ],
+        'Y' => [
+            0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100,
+        ],
+        'Z' => [
+            0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111,
+        ],
+        _ => [0; 7],
+    }
+}
+
+/// 국면 → PNG 바이트
+pub fn render_png(
+    game_kind: GameKind,
+    position: &Position,
+    options: DiagramOptions,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let (width, height) = canvas_size(position.board_size);
+    let mut canvas = Canvas::new(width as usize, height as usize);
+    for shape in layout(game_kind, position, options) {
+        canvas.draw(&shape);
+    }
+
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, canvas.width as u32, canvas.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&canvas.pixels)?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::kibo::SgfGame;
+
+    #[test]
+    fn diagram_test() {
+        // 흑이 백 한 점을 따내는 수순
+        let mut game = SgfGame::baduk();
+        for (i, (x, y)) in [(1, 0), (0, 0), (0, 1)].into_iter().enumerate() {
+            let color = if i % 2 == 0 {
+                Color::Black
+            } else {
+                Color::White
+            };
+            game.add_move(color, x, y);
+        }
+        let position = game.position_at(3).unwrap();
+        assert_eq!(position.stones[0], Color::Free);
+        assert_eq!(position.move_numbers[0], None);
+        assert_eq!(position.black_captures, 1);
+
+        let options = DiagramOptions { move_numbers: true };
+        let svg = render_svg(GameKind::Baduk, &position, options);
+        assert!(svg.contains("B CAPTURES 1"));
+        assert!(svg.contains(">T</text>"));
+        assert!(!svg.contains(">I</text>"));
+
+        let png = render_png(GameKind::Baduk, &position, options).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+    }
+}
@@ -0,0 +1,732 @@
+// kibo_formats.rs
+//
+// SGF 이외의 기보 형식 변환기. 서버가 들고 있는 SgfGame 을 기준으로
+//  - 바둑: Tygem GIB(.gib), Oro NGF(.ngf)
+//  - 오목: RIF XML(.rif), Piskvork PSQ(.psq)
+// 로 내보내고, 줄 단위 텍스트인 GIB / NGF / PSQ 는 다시 SgfGame 으로 읽어 들인다.
+
+use game_core::baduk_board::Color;
+
+use crate::soyul::kibo::{GameKind, KiboError, SgfGame};
+
+/// 내보내기/가져오기 기보 형식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KiboFormat {
+    Sgf,
+    Gib,
+    Ngf,
+    Rif,
+    Psq,
+}
+impl KiboFormat {
+    /// 쿼리 스트링 값("sgf", "gib" ...) → 형식. 대소문자 무시
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "sgf" => Some(KiboFormat::Sgf),
+            "gib" => Some(KiboFormat::Gib),
+            "ngf" => Some(KiboFormat::Ngf),
+            "rif" => Some(KiboFormat::Rif),
+            "psq" => Some(KiboFormat::Psq),
+            _ => None,
+        }
+    }
+
+    /// 파일 확장자 (Content-Disposition 파일 이름용)
+    pub fn extension(self) -> &'static str {
+        match self {
+            KiboFormat::Sgf => "sgf",
+            KiboFormat::Gib => "gib",
+            KiboFormat::Ngf => "ngf",
+            KiboFormat::Rif => "rif",
+            KiboFormat::Psq => "psq",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            KiboFormat::Sgf => "application/x-go-sgf; charset=utf-8",
+            KiboFormat::Rif => "application/xml; charset=utf-8",
+            _ => "text/plain; charset=utf-8",
+        }
+    }
+
+    /// 해당 게임 종류를 담을 수 있는 형식인지 (GIB/NGF 는 바둑, RIF/PSQ 는 오목 전용)
+    pub fn supports(self, game_kind: GameKind) -> bool {
+        match self {
+            KiboFormat::Sgf => true,
+            KiboFormat::Gib | KiboFormat::Ngf => game_kind == GameKind::Baduk,
+            KiboFormat::Rif | KiboFormat::Psq => game_kind == GameKind::Omok,
+        }
+    }
+}
+
+/// SgfGame → format 문자열
+pub fn export_kibo(game: &SgfGame, format: KiboFormat) -> Result<String, KiboError> {
+    if !format.supports(game.game_kind) {
+        return Err(KiboError::Unsupported(format!(
+            "{} 기보는 .{} 형식으로 내보낼 수 없습니다",
+            game.game_kind.game_type(),
+            format.extension()
+        )));
+    }
+    Ok(match format {
+        KiboFormat::Sgf => game.to_sgf_string(),
+        KiboFormat::Gib => to_gib(game),
+        KiboFormat::Ngf => to_ngf(game),
+        KiboFormat::Rif => to_rif(game),
+        KiboFormat::Psq => to_psq(game)?,
+    })
+}
+
+/// format 문자열 → SgfGame. 재생 검증(replay)은 호출하는 쪽에서 한다
+pub fn import_kibo(text: &str, format: KiboFormat) -> Result<SgfGame, KiboError> {
+    match format {
+        KiboFormat::Sgf => SgfGame::from_sgf_str(text),
+        KiboFormat::Gib => from_gib(text),
+        KiboFormat::Ngf => from_ngf(text),
+        KiboFormat::Psq => from_psq(text),
+        KiboFormat::Rif => Err(KiboError::Unsupported(
+            "RIF 기보 가져오기는 지원하지 않습니다".to_string(),
+        )),
+    }
+}
+
+/// 서버 RE[] 표기("B+R", "W+5", "Draw" ...)를 승자와 사유로 나눈 것
+enum Outcome {
+    Win(Color, WinBy),
+    Draw,
+    Unknown,
+}
+enum WinBy {
+    Resign,
+    Time,
+    Forfeit,
+    Score(f32),
+    Other,
+}
+
+fn outcome(result: &str) -> Outcome {
+    if result == "Draw" {
+        return Outcome::Draw;
+    }
+    let Some((winner, detail)) = result.split_once('+') else {
+        return Outcome::Unknown;
+    };
+    let winner = match winner {
+        "B" => Color::Black,
+        "W" => Color::White,
+        _ => return Outcome::Unknown,
+    };
+    let by = match detail {
+        "R" => WinBy::Resign,
+        "T" => WinBy::Time,
+        "F" => WinBy::Forfeit,
+        _ => match detail.parse::<f32>() {
+            Ok(score) => WinBy::Score(score),
+            Err(_) => WinBy::Other,
+        },
+    };
+    Outcome::Win(winner, by)
+}
+
+/// 1수 → (0, 0) 처럼 흑부터 번갈아 두는 형식의 n번째(0부터) 수 색
+fn alternate_color(index: usize) -> Color {
+    match index % 2 {
+        0 => Color::Black,
+        _ => Color::White,
+    }
+}
+
+//
+// ── Tygem GIB ──
+//
+// \HS ~ \HE 헤더(\[KEY=VALUE\]) 뒤에 \GS ~ \GE 수순.
+//  STO 0 {수 번호} {1=흑, 2=백} {x} {y}   (0부터 시작, 왼쪽 위 기준)
+//  SKI 0 {수 번호}                         (패스)
+// 결과는 GAMEINFOMAIN 의 GRLT(0/1 계가, 3/4 불계, 7/8 시간) 와 ZIPSU(집 차이 x10).
+//
+
+fn to_gib(game: &SgfGame) -> String {
+    let (grlt, zipsu, result_text) = match outcome(&game.result) {
+        Outcome::Win(winner, by) => {
+            let (side, offset) = match winner {
+                Color::White => ("백", 1),
+                _ => ("흑", 0),
+            };
+            match by {
+                WinBy::Score(score) => (
+                    offset,
+                    (score * 10.0).round() as i32,
+                    format!("{} {}집승", side, score),
+                ),
+                WinBy::Time => (7 + offset, 0, format!("{} 시간승", side)),
+                _ => (3 + offset, 0, format!("{} 불계승", side)),
+            }
+        }
+        Outcome::Draw => (2, 0, "무승부".to_string()),
+        Outcome::Unknown => (-1, 0, String::new()),
+    };
+
+    let mut s = String::new();
+    s.push_str("\\HS\n");
+    let mut header = |key: &str, value: &str| {
+        s.push_str(&format!("\\[{}={}\\]\n", key, value.replace('\\', "")));
+    };
+    header("GAMEBLACKNAME", &game.black_player);
+    header("GAMEWHITENAME", &game.white_player);
+    header("GAMEBLACKLEVEL", &game.black_rank);
+    header("GAMEWHITELEVEL", &game.white_rank);
+    header("GAMEDATE", &game.date);
+    header("GAMEPLACE", &game.place);
+    header("GAMERESULT", &result_text);
+    header(
+        "GAMEINFOMAIN",
+        &format!(
+            "GRLT:{},ZIPSU:{},GONGJE:0,TCNT:{},AUSZ:{}",
+            grlt,
+            zipsu,
+            game.moves.len(),
+            game.board_size
+        ),
+    );
+    s.push_str("\\HE\n");
+
+    s.push_str("\\GS\n");
+    s.push_str("INI 0 1 0 &4\n");
+    for (index, m) in game.moves.iter().enumerate() {
+        let color = match m.color {
+            Color::White => 2,
+            _ => 1,
+        };
+        match m.point {
+            Some((x, y)) => s.push_str(&format!("STO 0 {} {} {} {}\n", index + 1, color, x, y)),
+            None => s.push_str(&format!("SKI 0 {}\n", index + 1)),
+        }
+    }
+    s.push_str("\\GE\n");
+    s
+}
+
+fn from_gib(text: &str) -> Result<SgfGame, KiboError> {
+    let mut game = SgfGame::baduk();
+    let (mut black, mut white) = (String::new(), String::new());
+
+    for (line_number, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        // 헤더: \[KEY=VALUE\]
+        if let Some(entry) = line
+            .strip_prefix("\\[")
+            .and_then(|rest| rest.strip_suffix("\\]"))
+        {
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key {
+                "GAMEBLACKNAME" => black = value.to_string(),
+                "GAMEWHITENAME" => white = value.to_string(),
+                "GAMEBLACKLEVEL" => game.black_rank = value.to_string(),
+                "GAMEWHITELEVEL" => game.white_rank = value.to_string(),
+                "GAMEDATE" => game.date = value.to_string(),
+                "GAMEPLACE" => game.place = value.to_string(),
+                "GAMEINFOMAIN" => {
+                    if let Some(result) = gib_result(value) {
+                        game.set_result(&result);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let syntax = || KiboError::Syntax(format!("{}번째 줄: {}", line_number + 1, line));
+        match fields.as_slice() {
+            ["STO", _, _, color, x, y] => {
+                let color = match *color {
+                    "1" => Color::Black,
+                    "2" => Color::White,
+                    _ => return Err(syntax()),
+                };
+                let (x, y) = match (x.parse::<u8>(), y.parse::<u8>()) {
+                    (Ok(x), Ok(y)) => (x, y),
+                    _ => return Err(syntax()),
+                };
+                push_move(&mut game, color, x, y, &format!("{} {}", x, y))?;
+            }
+            ["SKI", ..] => {
+                // 패스는 색이 적혀 있지 않으므로 직전 수의 반대색
+                let color = match game.moves.last() {
+                    Some(m) => m.color.reverse(),
+                    None => Color::Black,
+                };
+                game.add_pass(color);
+            }
+            ["INI", _, _, handicap, ..] if *handicap != "0" => {
+                return Err(KiboError::Unsupported(
+                    "접바둑(배치돌) 기보는 가져올 수 없습니다".to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    game.set_players(&black, &white);
+    Ok(game)
+}
+
+/// GAMEINFOMAIN 값("GRLT:3,ZIPSU:0,...")에서 결과를 읽는다
+fn gib_result(info: &str) -> Option<String> {
+    let field = |name: &str| {
+        info.split(',')
+            .filter_map(|entry| entry.split_once(':'))
+            .find(|(key, _)| key.trim() == name)
+            .and_then(|(_, value)| value.trim().parse::<i32>().ok())
+    };
+    let grlt = field("GRLT")?;
+    let result = match grlt {
+        0 | 1 => {
+            let zipsu = field("ZIPSU").unwrap_or(0);
+            let winner = if grlt == 0 { "B" } else { "W" };
+            match zipsu % 10 {
+                0 => format!("{}+{}", winner, zipsu / 10),
+                _ => format!("{}+{:.1}", winner, zipsu as f32 / 10.0),
+            }
+        }
+        2 => "Draw".to_string(),
+        3 => "B+R".to_string(),
+        4 => "W+R".to_string(),
+        7 => "B+T".to_string(),
+        8 => "W+T".to_string(),
+        _ => return None,
+    };
+    Some(result)
+}
+
+//
+// ── Oro NGF ──
+//
+// 12줄 고정 헤더(제목, 판 크기, 백, 흑, 장소, 치석, 0, 덤, 날짜, 시간, 결과, 수 개수) 뒤에
+//  PM{수 번호 2글자}{B|W}{x}{y}{y}{x}   (좌표는 'B' 부터 1칸, 'A' 이면 패스)
+//
+
+fn to_ngf(game: &SgfGame) -> String {
+    let result = match outcome(&game.result) {
+        Outcome::Win(winner, by) => {
+            let side = match winner {
+                Color::White => "White",
+                _ => "Black",
+            };
+            match by {
+                WinBy::Resign => format!("{} wins by resign!", side),
+                WinBy::Time => format!("{} wins on time!", side),
+                WinBy::Score(score) => format!("{} wins by {}!", side, score),
+                WinBy::Forfeit | WinBy::Other => format!("{} wins!", side),
+            }
+        }
+        Outcome::Draw => "Draw!".to_string(),
+        Outcome::Unknown => "Unknown".to_string(),
+    };
+
+    let mut s = String::new();
+    let mut line = |value: &str| {
+        s.push_str(value);
+        s.push('\n');
+    };
+    line(&match game.game_id {
+        Some(id) => format!("{} #{}", game.place, id),
+        None => game.place.clone(),
+    });
+    line(&game.board_size.to_string());
+    line(&format!("{} {}", game.white_player, game.white_rank).replace('\n', " "));
+    line(&format!("{} {}", game.black_player, game.black_rank).replace('\n', " "));
+    line(&game.place);
+    line("0");
+    line("0");
+    line("0");
+    line(&format!("{} [00:00]", game.date.replace('-', "")));
+    line("0");
+    line(&result);
+    line(&game.moves.len().to_string());
+
+    for (index, m) in game.moves.iter().enumerate() {
+        let number = index + 1;
+        let color = match m.color {
+            Color::White => 'W',
+            _ => 'B',
+        };
+        let (x, y) = match m.point {
+            Some((x, y)) => ((b'B' + x) as char, (b'B' + y) as char),
+            None => ('A', 'A'),
+        };
+        s.push_str(&format!(
+            "PM{}{}{}{}{}{}{}\n",
+            (b'A' + (number / 26) as u8) as char,
+            (b'A' + (number % 26) as u8) as char,
+            color,
+            x,
+            y,
+            y,
+            x
+        ));
+    }
+    s
+}
+
+fn from_ngf(text: &str) -> Result<SgfGame, KiboError> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    if lines.len() < 12 {
+        return Err(KiboError::Syntax(
+            "NGF 헤더가 12줄보다 짧습니다".to_string(),
+        ));
+    }
+
+    let mut game = SgfGame::baduk();
+    match lines[1].parse::<u8>() {
+        Ok(size) if size == game.board_size => {}
+        _ => {
+            return Err(KiboError::Unsupported(format!(
+                "{}줄 바둑판은 지원하지 않습니다",
+                lines[1]
+            )));
+        }
+    }
+    if !matches!(lines[5], "" | "0") {
+        return Err(KiboError::Unsupported(
+            "접바둑(배치돌) 기보는 가져올 수 없습니다".to_string(),
+        ));
+    }
+
+    // "이름 랭크" → 이름 (랭크는 버린다). 이름에 공백이 있을 수 있으니
+    // 마지막 단어가 랭크 모양일 때만 떼어 낸다
+    let name = |line: &str| match line.rsplit_once(' ') {
+        Some((name, rank)) if is_ngf_rank(rank) => name.trim().to_string(),
+        _ => line.to_string(),
+    };
+    game.set_players(&name(lines[3]), &name(lines[2]));
+    if let Some(date) = lines[8]
+        .split_whitespace()
+        .next()
+        .filter(|d| d.len() == 8 && d.bytes().all(|b| b.is_ascii_digit()))
+    {
+        game.date = format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8]);
+    }
+    if let Some(result) = ngf_result(lines[10]) {
+        game.set_result(&result);
+    }
+
+    for line in &lines[12..] {
+        let Some(body) = line.strip_prefix("PM") else {
+            continue;
+        };
+        let bytes = body.as_bytes();
+        if bytes.len() < 5 {
+            return Err(KiboError::Syntax(format!("잘못된 NGF 수: {}", line)));
+        }
+        let color = match bytes[2] {
+            b'B' => Color::Black,
+            b'W' => Color::White,
+            _ => return Err(KiboError::Syntax(format!("잘못된 NGF 수: {}", line))),
+        };
+        match (bytes[3], bytes[4]) {
+            (b'A', b'A') => game.add_pass(color),
+            (x, y) if (b'B'..=b'Z').contains(&x) && (b'B'..=b'Z').contains(&y) => {
+                push_move(&mut game, color, x - b'B', y - b'B', &body[3..5])?;
+            }
+            _ => return Err(KiboError::Syntax(format!("잘못된 NGF 수: {}", line))),
+        }
+    }
+
+    Ok(game)
+}
+
+/// "3d", "10k", "1p" 같은 급·단 표기나 서버가 적는 레이팅 숫자인지
+fn is_ngf_rank(word: &str) -> bool {
+    let digits = word.trim_end_matches(['k', 'K', 'd', 'D', 'p', 'P']);
+    word.len() - digits.len() <= 1
+        && !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// "White wins by resign!" 같은 NGF 결과 줄 → RE[] 표기
+fn ngf_result(line: &str) -> Option<String> {
+    let lower = line.to_ascii_lowercase();
+    if lower.starts_with("draw") || lower.starts_with("jigo") {
+        return Some("Draw".to_string());
+    }
+    let winner = if lower.starts_with("black") {
+        "B"
+    } else if lower.starts_with("white") {
+        "W"
+    } else {
+        return None;
+    };
+    let detail = if lower.contains("resign") {
+        "R".to_string()
+    } else if lower.contains("time") {
+        "T".to_string()
+    } else {
+        lower
+            .trim_end_matches('!')
+            .rsplit(' ')
+            .next()
+            .filter(|score| score.parse::<f32>().is_ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Some(format!("{}+{}", winner, detail))
+}
+
+//
+// ── RIF (RenjuNet XML) ──
+//
+// <database> 안에 players / games 를 두고, 수순은 "h8 i9 ..." 처럼
+// 열(a~o) + 행(아래에서부터 1~15) 표기를 공백으로 이어 쓴다.
+// bresult 는 흑 기준 결과 (1 승, 0 패, 0.5 무승부).
+//
+
+fn to_rif(game: &SgfGame) -> String {
+    let bresult = match outcome(&game.result) {
+        Outcome::Win(Color::Black, _) => "1",
+        Outcome::Win(_, _) => "0",
+        Outcome::Draw => "0.5",
+        Outcome::Unknown => "",
+    };
+    let moves: Vec<String> = game
+        .moves
+        .iter()
+        .filter_map(|m| m.point)
+        .map(|(x, y)| format!("{}{}", (b'a' + x) as char, game.board_size - y))
+        .collect();
+
+    let mut s = String::new();
+    s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    s.push_str("<database>\n");
+    s.push_str("  <players>\n");
+    s.push_str(&format!(
+        "    <player id=\"1\" name=\"{}\" rating=\"{}\"/>\n",
+        escape_xml(&game.black_player),
+        escape_xml(&game.black_rank)
+    ));
+    s.push_str(&format!(
+        "    <player id=\"2\" name=\"{}\" rating=\"{}\"/>\n",
+        escape_xml(&game.white_player),
+        escape_xml(&game.white_rank)
+    ));
+    s.push_str("  </players>\n");
+    s.push_str("  <games>\n");
+    s.push_str(&format!(
+        "    <game id=\"{}\" bid=\"1\" wid=\"2\" bresult=\"{}\" rule=\"1\" date=\"{}\" place=\"{}\">\n",
+        game.game_id.unwrap_or(0),
+        bresult,
+        escape_xml(&game.date),
+        escape_xml(&game.place)
+    ));
+    s.push_str(&format!("      <move>{}</move>\n", moves.join(" ")));
+    s.push_str("    </game>\n");
+    s.push_str("  </games>\n");
+    s.push_str("</database>\n");
+    s
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+//
+// ── Piskvork PSQ ──
+//
+// 첫 줄 "Piskvorky 15x15, 11:11, 0" 뒤에 한 줄에 한 수씩 "x,y,걸린 시간(ms)".
+// 좌표는 1부터 시작하고, 흑부터 번갈아 둔다. 수순 뒤에는 양쪽 이름을 적는다.
+// 패스를 적을 방법이 없어서, 패스가 든 기보는 내보내지 않는다 (빼고 적으면 색이 뒤바뀜)
+//
+
+fn to_psq(game: &SgfGame) -> Result<String, KiboError> {
+    let mut s = format!(
+        "Piskvorky {}x{}, 11:11, 0\n",
+        game.board_size, game.board_size
+    );
+    for (index, m) in game.moves.iter().enumerate() {
+        let Some((x, y)) = m.point else {
+            return Err(KiboError::Unsupported(format!(
+                "{}수가 패스라서 .psq 형식으로 내보낼 수 없습니다",
+                index + 1
+            )));
+        };
+        s.push_str(&format!("{},{},0\n", x + 1, y + 1));
+    }
+    s.push_str(&format!("{}\n{}\n", game.black_player, game.white_player));
+    s.push_str("-1\n");
+    Ok(s)
+}
+
+fn from_psq(text: &str) -> Result<SgfGame, KiboError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| KiboError::Syntax("빈 PSQ 기보".to_string()))?;
+
+    // "Piskvorky 15x15, 11:11, 0" → 15
+    let size = header
+        .split_whitespace()
+        .nth(1)
+        .and_then(|size| size.trim_end_matches(',').split_once('x'))
+        .filter(|(width, height)| width == height)
+        .and_then(|(width, _)| width.parse::<u8>().ok())
+        .ok_or_else(|| KiboError::Syntax(format!("PSQ 헤더 형식 오류: {}", header)))?;
+
+    let mut game = SgfGame::omok();
+    if size != game.board_size {
+        return Err(KiboError::Unsupported(format!(
+            "{}x{} 오목판은 지원하지 않습니다",
+            size, size
+        )));
+    }
+
+    let mut names = Vec::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        match fields.as_slice() {
+            [x, y, ..] if names.is_empty() => {
+                let (x, y) = match (x.parse::<u8>(), y.parse::<u8>()) {
+                    (Ok(x), Ok(y)) if x >= 1 && y >= 1 => (x - 1, y - 1),
+                    _ => return Err(KiboError::Syntax(format!("PSQ 수 형식 오류: {}", line))),
+                };
+                let color = alternate_color(game.moves.len());
+                push_move(&mut game, color, x, y, line)?;
+            }
+            ["-1"] => break,
+            _ => names.push(line.to_string()),
+        }
+    }
+
+    if let [black, white, ..] = names.as_slice() {
+        game.set_players(black, white);
+    }
+    Ok(game)
+}
+
+/// 판 안의 좌표인지 확인하고 수를 추가한다
+fn push_move(
+    game: &mut SgfGame,
+    color: Color,
+    x: u8,
+    y: u8,
+    coordinate: &str,
+) -> Result<(), KiboError> {
+    if x >= game.board_size || y >= game.board_size {
+        return Err(KiboError::IllegalMove {
+            number: game.moves.len() + 1,
+            color,
+            coordinate: coordinate.to_string(),
+            reason: "판 밖 좌표".to_string(),
+        });
+    }
+    game.add_move(color, x, y);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_baduk() -> SgfGame {
+        let mut game = SgfGame::baduk();
+        game.set_players("흑돌", "백돌");
+        game.set_result("W+R");
+        game.add_move(Color::Black, 15, 3);
+        game.add_move(Color::White, 3, 15);
+        game.add_pass(Color::Black);
+        game.add_move(Color::White, 16, 16);
+        game
+    }
+
+    #[test]
+    fn gib_roundtrip_test() {
+        let gib = export_kibo(&sample_baduk(), KiboFormat::Gib).unwrap();
+        assert!(gib.contains("STO 0 1 1 15 3\n"));
+        assert!(gib.contains("SKI 0 3\n"));
+
+        let parsed = import_kibo(&gib, KiboFormat::Gib).unwrap();
+        assert_eq!(parsed.black_player, "흑돌");
+        assert_eq!(parsed.result, "W+R");
+        assert_eq!(parsed.moves.len(), 4);
+        assert_eq!(parsed.moves[2].point, None);
+        assert_eq!(parsed.moves[3].color, Color::White);
+        assert_eq!(parsed.replay(), Ok(None));
+    }
+
+    #[test]
+    fn ngf_roundtrip_test() {
+        let ngf = export_kibo(&sample_baduk(), KiboFormat::Ngf).unwrap();
+        assert!(ngf.contains("White wins by resign!\n4\nPMABBQEEQ\n"));
+
+        let parsed = import_kibo(&ngf, KiboFormat::Ngf).unwrap();
+        assert_eq!(parsed.white_player, "백돌");
+        assert_eq!(parsed.result, "W+R");
+        assert_eq!(parsed.moves[1].point, Some((3, 15)));
+        assert_eq!(parsed.moves[2].point, None);
+    }
+
+    #[test]
+    fn ngf_malformed_date_test() {
+        let ngf = export_kibo(&sample_baduk(), KiboFormat::Ngf).unwrap();
+        let mut lines: Vec<String> = ngf.lines().map(str::to_string).collect();
+        // 8바이트지만 숫자가 아닌 날짜는 건너뛴다
+        lines[8] = "가나ab [00:00]".to_string();
+
+        let parsed = import_kibo(&lines.join("\n"), KiboFormat::Ngf).unwrap();
+        assert_eq!(parsed.moves.len(), 4);
+    }
+
+    #[test]
+    fn renju_formats_test() {
+        let mut game = SgfGame::omok();
+        game.set_result("B+");
+        for (i, x) in [7u8, 7, 8, 8, 9].iter().enumerate() {
+            game.add_move(alternate_color(i), *x, if i % 2 == 0 { 7 } else { 8 });
+        }
+
+        let rif = export_kibo(&game, KiboFormat::Rif).unwrap();
+        assert!(rif.contains("bresult=\"1\""));
+        assert!(rif.contains("<move>h8 h7 i8 i7 j8</move>"));
+
+        let psq = export_kibo(&game, KiboFormat::Psq).unwrap();
+        let parsed = import_kibo(&psq, KiboFormat::Psq).unwrap();
+        assert_eq!(parsed.moves.len(), 5);
+        assert_eq!(parsed.moves[4].point, Some((9, 7)));
+        assert_eq!(parsed.moves[4].color, Color::Black);
+
+        assert!(export_kibo(&game, KiboFormat::Gib).is_err());
+
+        // 패스가 있으면 PSQ 로 적으면 뒤 수의 색이 바뀌므로 거절한다
+        game.add_pass(Color::White);
+        game.add_move(Color::Black, 10, 7);
+        assert!(matches!(
+            export_kibo(&game, KiboFormat::Psq),
+            Err(KiboError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn ngf_player_name_test() {
+        let mut game = sample_baduk();
+        game.set_players("Lee Sedol", "Cho Hunhyun");
+        game.white_rank = "9p".to_string();
+        let ngf = export_kibo(&game, KiboFormat::Ngf).unwrap();
+
+        // 랭크가 붙은 이름은 랭크만, 랭크가 없는 이름은 통째로
+        let parsed = import_kibo(&ngf, KiboFormat::Ngf).unwrap();
+        assert_eq!(parsed.white_player, "Cho Hunhyun");
+        assert_eq!(parsed.black_player, "Lee Sedol");
+
+        assert!(is_ngf_rank("1500"));
+        assert!(is_ngf_rank("10k"));
+        assert!(!is_ngf_rank("dd"));
+        assert!(!is_ngf_rank("Sedol"));
+    }
+}
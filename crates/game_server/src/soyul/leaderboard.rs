@@ -0,0 +1,432 @@
+// leaderboard.rs
+//
+// 게임 종류·판 크기별 레이팅 순위표 REST API.
+//  - GET /api/leaderboard                        : 순위표 (커서 페이지네이션)
+//  - GET /api/leaderboard/session/{session_key}  : 순위표 + 로그인 유저 본인 순위
+//
+// 순위는 user_game_stats 의 (rating 내림차순, user_id 오름차순) 순서다.
+// 커서는 앞 페이지 마지막 줄의 "rating:user_id" 이고, 다음 페이지는 그보다 뒤인 줄부터 읽는다
+// (keyset 페이지네이션 → 페이지가 깊어져도 OFFSET 처럼 앞줄을 다 읽지 않는다).
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use game_core::UserID;
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::soyul::session::{SessionStore, get_user_id_by_session};
+
+/// 한 페이지 기본 줄 수
+const DEFAULT_LIMIT: u32 = 20;
+/// 한 페이지 최대 줄 수
+const MAX_LIMIT: u32 = 100;
+
+/// 순위표 필터(URL 쿼리 스트링)
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeaderboardQuery {
+    /// "baduk" | "omok". 생략 시 "baduk"
+    pub game_type: Option<String>,
+    /// 판 크기. 생략 시 게임 기본 크기 (바둑 19, 오목 15)
+    pub board_size: Option<i32>,
+    /// 이 판 수 이상 둔 유저만
+    pub min_games: Option<i32>,
+    /// 최근 N일 안에 둔 유저만
+    pub active_days: Option<i32>,
+    /// 한 페이지 줄 수 (기본 20, 최대 100)
+    pub limit: Option<u32>,
+    /// 앞 페이지 응답의 next_cursor. 생략 시 1위부터
+    pub cursor: Option<String>,
+}
+
+/// 순위표 한 줄
+#[derive(Serialize, ToSchema, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub user_id: i64,
+    pub username: Option<String>,
+    pub rating: i32,
+    pub win: i32,
+    pub lose: i32,
+    pub draw: i32,
+    pub last_played_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LeaderboardResponse {
+    pub success: bool,
+    pub message: String,
+    pub game_type: String,
+    pub board_size: i32,
+    pub entries: Vec<LeaderboardEntry>,
+    /// 다음 페이지 커서. 마지막 페이지면 null
+    pub next_cursor: Option<String>,
+    /// 로그인 유저 본인 줄 (페이지 밖이어도 채운다). 필터에 걸리면 null
+    pub my_rank: Option<LeaderboardEntry>,
+}
+
+/// 순위표 조회 조건
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardFilter {
+    pub game_type: String,
+    pub board_size: i32,
+    pub min_games: i32,
+    pub active_days: Option<i32>,
+}
+impl LeaderboardFilter {
+    fn from_query(q: &LeaderboardQuery) -> Self {
+        let game_type = q.game_type.clone().unwrap_or_else(|| "baduk".into());
+        let board_size = q.board_size.unwrap_or(match game_type.as_str() {
+            "omok" => 15,
+            _ => 19,
+        });
+        LeaderboardFilter {
+            game_type,
+            board_size,
+            min_games: q.min_games.unwrap_or(0),
+            active_days: q.active_days,
+        }
+    }
+}
+
+/// 순위 커서: 앞 페이지 마지막 줄의 (rating, user_id)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeaderboardCursor {
+    pub rating: i32,
+    pub user_id: i64,
+}
+impl LeaderboardCursor {
+    pub fn parse(s: &str) -> Option<Self> {
+        let (rating, user_id) = s.split_once(':')?;
+        Some(LeaderboardCursor {
+            rating: rating.parse().ok()?,
+            user_id: user_id.parse().ok()?,
+        })
+    }
+}
+impl std::fmt::Display for LeaderboardCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.rating, self.user_id)
+    }
+}
+
+// 순위표 줄이 나오는 곳. 탈퇴한 유저의 전적 행은 JOIN 으로 빠진다
+const FROM_SQL: &str = "FROM user_game_stats s
+         JOIN users u ON u.id = s.user_id";
+
+// 필터 조건. ?1 game_type, ?2 board_size, ?3 min_games, ?4 active_days
+const FILTER_SQL: &str = "s.game_type = ?1 AND s.board_size = ?2
+           AND s.win + s.lose + s.draw >= ?3
+           AND (?4 IS NULL OR s.last_played_at >= datetime('now', '-' || ?4 || ' days'))";
+
+/// (rating, user_id) 보다 앞 순위인 줄 수. 순위 = 이 값 + 1
+fn count_ahead(
+    conn: &Connection,
+    filter: &LeaderboardFilter,
+    cursor: LeaderboardCursor,
+) -> rusqlite::Result<i64> {
+    conn.query_row(
+        &format!(
+            "SELECT COUNT(*) {FROM_SQL}
+             WHERE {FILTER_SQL}
+               AND (s.rating > ?5 OR (s.rating = ?5 AND s.user_id < ?6))"
+        ),
+        params![
+            filter.game_type,
+            filter.board_size,
+            filter.min_games,
+            filter.active_days,
+            cursor.rating,
+            cursor.user_id
+        ],
+        |row| row.get(0),
+    )
+}
+
+/// cursor 다음 줄부터 limit 줄 조회. 다음 페이지가 있으면 next_cursor 도 돌려준다
+pub fn get_leaderboard_page(
+    conn: &Connection,
+    filter: &LeaderboardFilter,
+    cursor: Option<LeaderboardCursor>,
+    limit: u32,
+) -> rusqlite::Result<(Vec<LeaderboardEntry>, Option<LeaderboardCursor>)> {
+    // 커서 줄 자신도 앞 페이지에 있었으므로 +2
+    let first_rank = match cursor {
+        Some(cursor) => count_ahead(conn, filter, cursor)? + 2,
+        None => 1,
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT s.user_id, u.username, s.rating, s.win, s.lose, s.draw, s.last_played_at
+         {FROM_SQL}
+         WHERE {FILTER_SQL}
+           AND (?5 IS NULL OR s.rating < ?5 OR (s.rating = ?5 AND s.user_id > ?6))
+         ORDER BY s.rating DESC, s.user_id ASC
+         LIMIT ?7"
+    ))?;
+
+    // 한 줄 더 읽어서 다음 페이지가 있는지 본다
+    let rows = stmt.query_map(
+        params![
+            filter.game_type,
+            filter.board_size,
+            filter.min_games,
+            filter.active_days,
+            cursor.map(|c| c.rating),
+            cursor.map(|c| c.user_id),
+            limit as i64 + 1
+        ],
+        |row| {
+            Ok(LeaderboardEntry {
+                rank: 0,
+                user_id: row.get(0)?,
+                username: row.get(1)?,
+                rating: row.get(2)?,
+                win: row.get(3)?,
+                lose: row.get(4)?,
+                draw: row.get(5)?,
+                last_played_at: row.get(6)?,
+            })
+        },
+    )?;
+
+    let mut entries = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+    let has_next = entries.len() > limit as usize;
+    entries.truncate(limit as usize);
+    for (i, entry) in entries.iter_mut().enumerate() {
+        entry.rank = first_rank + i as i64;
+    }
+
+    let next_cursor = match entries.last() {
+        Some(last) if has_next => Some(LeaderboardCursor {
+            rating: last.rating,
+            user_id: last.user_id,
+        }),
+        _ => None,
+    };
+    Ok((entries, next_cursor))
+}
+
+/// user_id 의 순위표 줄. 기록이 없거나 필터에 걸리면 None
+pub fn get_user_rank(
+    conn: &Connection,
+    filter: &LeaderboardFilter,
+    user_id: UserID,
+) -> rusqlite::Result<Option<LeaderboardEntry>> {
+    let entry = conn.query_row(
+        &format!(
+            "SELECT s.user_id, u.username, s.rating, s.win, s.lose, s.draw, s.last_played_at
+             {FROM_SQL}
+             WHERE {FILTER_SQL} AND s.user_id = ?5"
+        ),
+        params![
+            filter.game_type,
+            filter.board_size,
+            filter.min_games,
+            filter.active_days,
+            i64::from(user_id)
+        ],
+        |row| {
+            Ok(LeaderboardEntry {
+                rank: 0,
+                user_id: row.get(0)?,
+                username: row.get(1)?,
+                rating: row.get(2)?,
+                win: row.get(3)?,
+                lose: row.get(4)?,
+                draw: row.get(5)?,
+                last_played_at: row.get(6)?,
+            })
+        },
+    );
+
+    let mut entry = match entry {
+        Ok(entry) => entry,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    entry.rank = count_ahead(
+        conn,
+        filter,
+        LeaderboardCursor {
+            rating: entry.rating,
+            user_id: entry.user_id,
+        },
+    )? + 1;
+    Ok(Some(entry))
+}
+
+/// 두 핸들러 공통: 순위표 조회 후 응답 생성 (user_id 가 있으면 my_rank 포함)
+fn leaderboard_response(q: LeaderboardQuery, user_id: Option<UserID>) -> axum::response::Response {
+    let cursor = match q.cursor.as_deref().map(LeaderboardCursor::parse) {
+        Some(None) => {
+            eprintln!("❌ 순위표 조회 실패: 잘못된 커서 {:?}", q.cursor);
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+        Some(cursor) => cursor,
+        None => None,
+    };
+    let limit = q.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let filter = LeaderboardFilter::from_query(&q);
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("⚠️ 순위표 조회(DB 오픈 실패): {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let page = get_leaderboard_page(&conn, &filter, cursor, limit).and_then(|page| {
+        let my_rank = match user_id {
+            Some(user_id) => get_user_rank(&conn, &filter, user_id)?,
+            None => None,
+        };
+        Ok((page, my_rank))
+    });
+
+    match page {
+        Ok(((entries, next_cursor), my_rank)) => {
+            #[cfg(debug_assertions)]
+            println!(
+                "✅ 순위표 조회 성공: {} {}줄, {}명",
+                filter.game_type,
+                filter.board_size,
+                entries.len()
+            );
+            (
+                StatusCode::OK,
+                Json(LeaderboardResponse {
+                    success: true,
+                    message: "순위표 조회 성공".into(),
+                    game_type: filter.game_type,
+                    board_size: filter.board_size,
+                    entries,
+                    next_cursor: next_cursor.map(|c| c.to_string()),
+                    my_rank,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            eprintln!("⚠️ 순위표 조회 중 에러: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/leaderboard",
+    tag = "leaderboard",
+    params(LeaderboardQuery),
+    responses(
+        (status = 200, description = "순위표 조회 성공", body = LeaderboardResponse),
+        (status = 400, description = "커서가 올바르지 않음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_leaderboard(Query(q): Query<LeaderboardQuery>) -> impl IntoResponse {
+    leaderboard_response(q, None)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/leaderboard/session/{session_key}",
+    tag = "leaderboard",
+    params(
+        ("session_key" = String, Path, description = "세션 키"),
+        LeaderboardQuery,
+    ),
+    responses(
+        (status = 200, description = "순위표 조회 성공 (my_rank 포함)", body = LeaderboardResponse),
+        (status = 400, description = "세션 키 또는 커서가 올바르지 않음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_leaderboard_with_me(
+    State(session_store): State<SessionStore>,
+    Path(session_key): Path<String>,
+    Query(q): Query<LeaderboardQuery>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    leaderboard_response(q, Some(user_id))
+}
+
+pub fn leaderboard_router() -> OpenApiRouter<SessionStore> {
+    OpenApiRouter::new()
+        .routes(routes!(get_leaderboard))
+        .routes(routes!(get_leaderboard_with_me))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::soyul_db::init_schema;
+
+    #[test]
+    fn leaderboard_page_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash, username)
+             VALUES (1, 'a', '', 'a'), (2, 'b', '', 'b'), (3, 'c', '', 'c'), (4, 'd', '', 'd');",
+        )
+        .unwrap();
+        conn.execute_batch(
+            "INSERT INTO user_game_stats (user_id, game_type, board_size, rating, win, last_played_at)
+             VALUES (1, 'baduk', 19, 1600, 5, CURRENT_TIMESTAMP),
+                    (2, 'baduk', 19, 1700, 5, CURRENT_TIMESTAMP),
+                    (3, 'baduk', 19, 1600, 1, '2000-01-01 00:00:00'),
+                    (4, 'omok',  15, 2000, 5, CURRENT_TIMESTAMP),
+                    (9, 'baduk', 19, 1650, 5, CURRENT_TIMESTAMP);",
+        )
+        .unwrap();
+
+        let filter = LeaderboardFilter {
+            game_type: "baduk".into(),
+            board_size: 19,
+            min_games: 0,
+            active_days: None,
+        };
+
+        // 같은 레이팅이면 user_id 순, 커서로 이어 읽어도 순위가 이어진다
+        let (page, next) = get_leaderboard_page(&conn, &filter, None, 2).unwrap();
+        let ids: Vec<_> = page.iter().map(|e| (e.rank, e.user_id)).collect();
+        assert_eq!(ids, vec![(1, 2), (2, 1)]);
+        let next = LeaderboardCursor::parse(&next.unwrap().to_string()).unwrap();
+
+        // 탈퇴한 유저(9)의 전적 행은 순위를 밀어내지 않는다
+        let (page, next) = get_leaderboard_page(&conn, &filter, Some(next), 2).unwrap();
+        let ids: Vec<_> = page.iter().map(|e| (e.rank, e.user_id)).collect();
+        assert_eq!(ids, vec![(3, 3)]);
+        assert_eq!(next, None);
+
+        // 판 수·최근 활동 필터
+        let filter = LeaderboardFilter {
+            min_games: 2,
+            active_days: Some(30),
+            ..filter
+        };
+        let (page, _) = get_leaderboard_page(&conn, &filter, None, 10).unwrap();
+        assert_eq!(page.len(), 2);
+
+        // 페이지 밖이어도 본인 순위
+        assert_eq!(
+            get_user_rank(&conn, &filter, UserID(1))
+                .unwrap()
+                .map(|e| e.rank),
+            Some(2)
+        );
+        assert_eq!(get_user_rank(&conn, &filter, UserID(3)).unwrap(), None);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod game_record;
 pub mod kibo;
+pub mod kibo_diagram;
+pub mod kibo_formats;
+pub mod leaderboard;
+pub mod rating;
 pub mod session;
 pub mod soyul_db;
 pub mod soyul_login;
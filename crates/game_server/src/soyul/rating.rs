@@ -0,0 +1,271 @@
+// rating.rs
+//
+// 대국 종료 시 레이팅 계산 (Elo). 레이팅은 게임 종류·판 크기마다 따로 매긴다 (user_game_stats).
+// 처음 PROVISIONAL_GAMES 판까지는 K를 크게 잡아(잠정 레이팅) 실력에 빨리 수렴하게 하고,
+// 그 뒤로는 K를 줄여 한 판의 결과로 크게 흔들리지 않게 한다.
+
+use game_core::{UserID, baduk_board::Color};
+use rusqlite::{Connection, params};
+
+use crate::soyul::soyul_login::{record_game_draw, record_game_lose, record_game_win};
+
+/// users.rating 기본값
+pub const DEFAULT_RATING: i32 = 1500;
+/// 이 판 수보다 적게 둔 유저는 잠정 레이팅
+pub const PROVISIONAL_GAMES: i32 = 30;
+const PROVISIONAL_K: f64 = 40.0;
+const ESTABLISHED_K: f64 = 20.0;
+/// 레이팅 하한
+const MIN_RATING: i32 = 100;
+
+/// 한 유저의 대국 전후 레이팅
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingChange {
+    pub before: i32,
+    pub after: i32,
+}
+impl RatingChange {
+    pub fn to_proto(self) -> doljabiproto::badukboard::RatingChange {
+        doljabiproto::badukboard::RatingChange {
+            before: self.before,
+            after: self.after,
+        }
+    }
+}
+
+/// 흑·백 레이팅 변화
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingResult {
+    pub black: RatingChange,
+    pub white: RatingChange,
+    /// 같이 쌓은 rating_history 행 번호 (흑, 백). 기보가 저장되면 game_id 를 채운다
+    pub history_ids: [i64; 2],
+}
+impl RatingResult {
+    pub fn to_proto(self) -> doljabiproto::badukboard::RatingResult {
+        doljabiproto::badukboard::RatingResult {
+            black: Some(self.black.to_proto()),
+            white: Some(self.white.to_proto()),
+        }
+    }
+}
+
+/// rating 이 opponent 를 상대로 얻을 기대 점수 (0~1)
+pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) as f64 / 400.0))
+}
+
+/// 지금까지 둔 판 수에 따른 K
+pub fn k_factor(games_played: i32) -> f64 {
+    if games_played < PROVISIONAL_GAMES {
+        PROVISIONAL_K
+    } else {
+        ESTABLISHED_K
+    }
+}
+
+/// score(승 1, 무 0.5, 패 0)를 얻은 뒤의 새 레이팅
+pub fn next_rating(rating: i32, games_played: i32, opponent: i32, score: f64) -> i32 {
+    let delta = k_factor(games_played) * (score - expected_score(rating, opponent));
+    (rating + delta.round() as i32).max(MIN_RATING)
+}
+
+/// 승자 색 → (흑 점수, 백 점수). Color::Free 는 무승부
+fn scores(winner: Color) -> Option<(f64, f64)> {
+    match winner {
+        Color::Black => Some((1.0, 0.0)),
+        Color::White => Some((0.0, 1.0)),
+        Color::Free => Some((0.5, 0.5)),
+        Color::ColorError => None,
+    }
+}
+
+/// game_type·board_size 의 (rating, 지금까지 둔 판 수). 없는 유저면 QueryReturnedNoRows
+fn rating_and_games(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: &str,
+    board_size: u8,
+) -> rusqlite::Result<(i32, i32)> {
+    conn.query_row(
+        "SELECT COALESCE(s.rating, ?4), COALESCE(s.win + s.lose + s.draw, 0)
+         FROM users u
+         LEFT JOIN user_game_stats s
+           ON s.user_id = u.id AND s.game_type = ?2 AND s.board_size = ?3
+         WHERE u.id = ?1",
+        params![
+            i64::from(user_id),
+            game_type,
+            board_size as i64,
+            DEFAULT_RATING
+        ],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )
+}
+
+/// 대국 결과를 두 유저에게 한 트랜잭션으로 반영한다.
+/// 승/패/무 전적과 레이팅, rating_history 가 함께 갱신되고, 중간에 실패하면 아무것도 바뀌지 않는다.
+/// 기보는 아직 저장 전이라 rating_history.game_id 는 NULL 로 두고, 저장 뒤 update_game_ratings 가 채운다.
+pub fn apply_rated_result(
+    conn: &mut Connection,
+    black_id: UserID,
+    white_id: UserID,
+    winner: Color,
+    game_type: &str,
+    board_size: u8,
+) -> rusqlite::Result<Option<RatingResult>> {
+    let Some((black_score, white_score)) = scores(winner) else {
+        return Ok(None);
+    };
+
+    let tx = conn.transaction()?;
+
+    let (black_rating, black_games) = rating_and_games(&tx, black_id, game_type, board_size)?;
+    let (white_rating, white_games) = rating_and_games(&tx, white_id, game_type, board_size)?;
+    let mut result = RatingResult {
+        black: RatingChange {
+            before: black_rating,
+            after: next_rating(black_rating, black_games, white_rating, black_score),
+        },
+        white: RatingChange {
+            before: white_rating,
+            after: next_rating(white_rating, white_games, black_rating, white_score),
+        },
+        history_ids: [0; 2],
+    };
+
+    for (i, (user_id, score, change)) in [
+        (black_id, black_score, result.black),
+        (white_id, white_score, result.white),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        match score {
+            1.0 => record_game_win(&tx, user_id, game_type, board_size)?,
+            0.0 => record_game_lose(&tx, user_id, game_type, board_size)?,
+            _ => record_game_draw(&tx, user_id, game_type, board_size)?,
+        }
+        tx.execute(
+            "UPDATE user_game_stats SET rating = ?4
+             WHERE user_id = ?1 AND game_type = ?2 AND board_size = ?3",
+            params![
+                i64::from(user_id),
+                game_type,
+                board_size as i64,
+                change.after
+            ],
+        )?;
+        tx.execute(
+            "INSERT INTO rating_history (user_id, game_type, board_size, rating_before, rating_after)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                i64::from(user_id),
+                game_type,
+                board_size as i64,
+                change.before,
+                change.after
+            ],
+        )?;
+        result.history_ids[i] = tx.last_insert_rowid();
+    }
+
+    tx.commit()?;
+
+    #[cfg(debug_assertions)]
+    println!(
+        "✅ 레이팅 반영: 흑 {} → {}, 백 {} → {}",
+        result.black.before, result.black.after, result.white.before, result.white.after
+    );
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elo_test() {
+        // 같은 레이팅이면 기대 점수 0.5, 잠정 K=40 → ±20
+        assert_eq!(next_rating(1500, 0, 1500, 1.0), 1520);
+        assert_eq!(next_rating(1500, 0, 1500, 0.0), 1480);
+        assert_eq!(next_rating(1500, 0, 1500, 0.5), 1500);
+
+        // 판 수가 쌓이면 K=20
+        assert_eq!(next_rating(1500, PROVISIONAL_GAMES, 1500, 1.0), 1510);
+
+        // 약한 쪽이 이기면 많이 오르고, 강한 쪽이 이기면 조금 오른다
+        assert!(next_rating(1300, 50, 1700, 1.0) - 1300 > 15);
+        assert!(next_rating(1700, 50, 1300, 1.0) - 1700 < 5);
+
+        assert_eq!(next_rating(MIN_RATING, 50, 1500, 0.0), MIN_RATING);
+    }
+
+    #[test]
+    fn apply_rated_result_test() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::soyul::soyul_db::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash) VALUES (1, 'a', ''), (2, 'b', '');",
+        )
+        .unwrap();
+
+        let result = apply_rated_result(&mut conn, UserID(1), UserID(2), Color::White, "omok", 15)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            result.black,
+            RatingChange {
+                before: 1500,
+                after: 1480
+            }
+        );
+        assert_eq!(
+            result.white,
+            RatingChange {
+                before: 1500,
+                after: 1520
+            }
+        );
+
+        let row: (i32, i32, i32) = conn
+            .query_row(
+                "SELECT rating, win, lose FROM user_game_stats
+                 WHERE user_id = 2 AND game_type = 'omok' AND board_size = 15",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(row, (1520, 1, 0));
+
+        // 기보 저장 전이라 game_id 없이 두 줄이 쌓인다
+        let history: Vec<(i64, Option<i64>, i32, i32)> = conn
+            .prepare("SELECT user_id, game_id, rating_before, rating_after FROM rating_history ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(history, vec![(1, None, 1500, 1480), (2, None, 1500, 1520)]);
+        assert_eq!(result.history_ids, [1, 2]);
+
+        // 바둑 레이팅은 오목과 따로 시작한다
+        let result = apply_rated_result(&mut conn, UserID(1), UserID(2), Color::Free, "baduk", 19)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.black.before, DEFAULT_RATING);
+
+        // 없는 유저가 끼면 아무것도 바뀌지 않는다
+        assert!(
+            apply_rated_result(&mut conn, UserID(1), UserID(9), Color::Black, "omok", 15).is_err()
+        );
+        let rating: i32 = conn
+            .query_row(
+                "SELECT rating FROM user_game_stats WHERE user_id = 1 AND game_type = 'omok'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(rating, 1480);
+    }
+}
@@ -2,6 +2,8 @@
 
 use rusqlite::{Connection, Result, params};
 
+use crate::soyul::{kibo::ChatLine, rating::RatingResult};
+
 /// games 테이블 생성 함수
 ///
 /// 없으면 새로 만들고
@@ -17,12 +19,20 @@ pub fn init_games_table(conn: &Connection) -> Result<()> {
             board_size INTEGER NOT NULL DEFAULT 15,        -- SGF SZ[]
             result     TEXT,                               -- 예: "B+R", "W+5.5", "Draw"
             sgf        TEXT    NOT NULL,                    -- SGF 기보 본문
+            imported   INTEGER NOT NULL DEFAULT 0,         -- 1이면 업로드로 가져온 기보 (상대 미상이면 id 0)
+            black_rating_before INTEGER,                   -- 대국 전후 흑 레이팅 (레이팅 반영 안 된 게임은 NULL)
+            black_rating_after  INTEGER,
+            white_rating_before INTEGER,                   -- 대국 전후 백 레이팅
+            white_rating_after  INTEGER,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP      -- 생성 시간
         );
         "#,
         [],
     )?;
 
+    // 예전 스키마로 만들어진 games 테이블에는 나중에 추가된 컬럼을 붙여 준다
+    add_missing_games_columns(conn)?;
+
     // 특정 유저의 게임 리스트 조회(WHERE black_id=? OR white_id=?) 가속용 인덱스
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_games_black ON games(black_id);",
@@ -36,13 +46,69 @@ pub fn init_games_table(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// CREATE TABLE 이후에 추가된 games 컬럼 (이름, 정의)
+const GAMES_ADDED_COLUMNS: &[(&str, &str)] = &[
+    ("imported", "INTEGER NOT NULL DEFAULT 0"),
+    ("black_rating_before", "INTEGER"),
+    ("black_rating_after", "INTEGER"),
+    ("white_rating_before", "INTEGER"),
+    ("white_rating_after", "INTEGER"),
+];
+
+fn add_missing_games_columns(conn: &Connection) -> Result<()> {
+    let current_columns: Vec<String> = {
+        let mut stmt = conn.prepare("PRAGMA table_info(games)")?;
+        stmt.query_map([], |row| row.get(1))?
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    for (name, definition) in GAMES_ADDED_COLUMNS {
+        if !current_columns.iter().any(|c| c == name) {
+            conn.execute(
+                &format!("ALTER TABLE games ADD COLUMN {} {}", name, definition),
+                [],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// users 테이블 생성 함수 (회원가입·마이그레이션과 같은 정의)
+pub fn init_users_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            login_id TEXT UNIQUE NOT NULL,
+            password_hash TEXT NOT NULL,
+            username TEXT UNIQUE,
+            rating INTEGER DEFAULT 1500,
+            win INTEGER DEFAULT 0,
+            lose INTEGER DEFAULT 0,
+            draw INTEGER DEFAULT 0,
+            is_bot INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// 서버가 시작할 때 한 번 준비하는 스키마.
+/// 요청을 처리하는 조회 경로에서는 DDL 을 돌리지 않는다
+pub fn init_schema(conn: &Connection) -> Result<()> {
+    init_users_table(conn)?;
+    init_bot_tables(conn)?;
+    init_games_table(conn)?;
+    init_user_game_stats_table(conn)?;
+    init_rating_history_table(conn)?;
+    init_game_chat_table(conn)?;
+    Ok(())
+}
+
 /// 새 게임을 생성하고, 생성된 게임 고유번호(game_id)를 리턴
 ///
 /// - `black_id`, `white_id`는 `users.id` (정수 PK)라고 가정
 pub fn create_game_db(conn: &Connection, black_id: i64, white_id: i64) -> Result<i64> {
-    // 테이블이 없으면 먼저 만들어 두기 (한 번만 실행돼도 상관 없음)
-    init_games_table(conn)?;
-
     conn.execute(
         r#"
         INSERT INTO games (black_id, white_id, result)
@@ -65,9 +131,6 @@ pub fn save_finished_game(
     result: &str,
     sgf: &str,
 ) -> Result<i64> {
-    // 테이블이 없으면 먼저 만들어 두기 (인덱스 포함)
-    init_games_table(conn)?;
-
     conn.execute(
         r#"
         INSERT INTO games (black_id, white_id, game_type, board_size, result, sgf)
@@ -85,3 +148,306 @@ pub fn save_finished_game(
 
     Ok(conn.last_insert_rowid())
 }
+
+/// 업로드한 기보(오프라인 대국 등)를 imported = 1 로 저장
+///
+/// - 상대가 서버 유저가 아니면 해당 색의 id 는 0
+/// - 결과를 알 수 없으면 `result` 는 None (NULL 저장)
+pub fn save_imported_game(
+    conn: &Connection,
+    black_id: i64,
+    white_id: i64,
+    game_type: &str,
+    board_size: u8,
+    result: Option<&str>,
+    sgf: &str,
+) -> Result<i64> {
+    conn.execute(
+        r#"
+        INSERT INTO games (black_id, white_id, game_type, board_size, result, sgf, imported)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1);
+        "#,
+        params![
+            black_id,
+            white_id,
+            game_type,
+            board_size as i64,
+            result,
+            sgf
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// 저장된 게임의 SGF 본문 교체 (INSERT 후 받은 game_id 를 GN[] 에 넣어 다시 쓸 때 사용)
+pub fn update_game_sgf(conn: &Connection, game_id: i64, sgf: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE games SET sgf = ?1 WHERE id = ?2",
+        params![sgf, game_id],
+    )?;
+    Ok(())
+}
+
+/// 레이팅이 반영된 대국의 전후 레이팅 기록
+///
+/// games 행의 *_rating_before / *_rating_after 를 채우고, 레이팅을 반영할 때 쌓아 둔
+/// 두 플레이어의 rating_history 행에 game_id 를 채운다.
+pub fn update_game_ratings(conn: &Connection, game_id: i64, ratings: &RatingResult) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    tx.execute(
+        r#"
+        UPDATE games
+        SET black_rating_before = ?1, black_rating_after = ?2,
+            white_rating_before = ?3, white_rating_after = ?4
+        WHERE id = ?5
+        "#,
+        params![
+            ratings.black.before,
+            ratings.black.after,
+            ratings.white.before,
+            ratings.white.after,
+            game_id
+        ],
+    )?;
+
+    tx.execute(
+        "UPDATE rating_history SET game_id = ?1 WHERE id IN (?2, ?3)",
+        params![game_id, ratings.history_ids[0], ratings.history_ids[1]],
+    )?;
+
+    tx.commit()
+}
+
+/// rating_history 테이블 생성 함수
+///
+/// 레이팅이 반영된 대국마다 플레이어별로 한 줄씩 쌓인다 (프로필 레이팅 그래프용).
+pub fn init_rating_history_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS rating_history (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id       INTEGER NOT NULL,                -- users.id
+            game_id       INTEGER,                         -- games.id (기보 저장 전이면 NULL)
+            game_type     TEXT    NOT NULL,                -- 'baduk' | 'omok'
+            board_size    INTEGER NOT NULL,
+            rating_before INTEGER NOT NULL,
+            rating_after  INTEGER NOT NULL,
+            created_at    TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        [],
+    )?;
+
+    // 유저별 게임 종류 시계열 조회 가속용 인덱스
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_rating_history_user
+         ON rating_history(user_id, game_type, board_size, id);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// user_game_stats 테이블 생성 함수
+///
+/// 레이팅과 승/패/무 전적을 (유저, 게임 종류, 판 크기) 별로 따로 쌓는다.
+/// users 테이블의 rating / win / lose / draw 는 예전 통합 전적으로만 남는다.
+pub fn init_user_game_stats_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS user_game_stats (
+            user_id        INTEGER NOT NULL,               -- users.id
+            game_type      TEXT    NOT NULL,               -- 'baduk' | 'omok'
+            board_size     INTEGER NOT NULL,               -- SGF SZ[]
+            rating         INTEGER NOT NULL DEFAULT 1500,
+            win            INTEGER NOT NULL DEFAULT 0,
+            lose           INTEGER NOT NULL DEFAULT 0,
+            draw           INTEGER NOT NULL DEFAULT 0,
+            last_played_at TEXT,                           -- 마지막 대국 시간
+            PRIMARY KEY (user_id, game_type, board_size)
+        );
+        "#,
+        [],
+    )?;
+
+    // 순위표(레이팅 내림차순 keyset 페이지네이션) 조회 가속용 인덱스
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_user_game_stats_rank
+         ON user_game_stats(game_type, board_size, rating DESC, user_id);",
+        [],
+    )?;
+    Ok(())
+}
+
+/// game_chat 테이블 생성 함수
+///
+/// 대국 중 채팅을 게임별로 쌓는다 (기보 다시보기, SGF C[] 내보내기용).
+pub fn init_game_chat_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS game_chat (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            game_id     INTEGER NOT NULL,                -- games.id
+            move_number INTEGER NOT NULL,                -- 보낸 시점까지 둔 수
+            spectator   INTEGER NOT NULL DEFAULT 0,      -- 1이면 관전자 채팅
+            user_id     INTEGER NOT NULL,                -- users.id
+            username    TEXT    NOT NULL,                -- 보낸 시점의 이름
+            text        TEXT    NOT NULL,
+            sent_at_ms  INTEGER NOT NULL                 -- 보낸 시각 (Unix ms)
+        );
+        "#,
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_game_chat_game ON game_chat(game_id, id);",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// 게임 하나의 채팅 저장 (보낸 순서대로)
+pub fn save_game_chat(conn: &Connection, game_id: i64, chat: &[ChatLine]) -> Result<()> {
+    if chat.is_empty() {
+        return Ok(());
+    }
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO game_chat (game_id, move_number, spectator, user_id, username, text, sent_at_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )?;
+        for line in chat {
+            stmt.execute(params![
+                game_id,
+                line.move_number as i64,
+                line.spectator,
+                line.user_id,
+                line.user_name,
+                line.text,
+                line.sent_at_ms as i64
+            ])?;
+        }
+    }
+    tx.commit()
+}
+
+/// 게임 하나의 채팅 (보낸 순서대로)
+pub fn load_game_chat(conn: &Connection, game_id: i64) -> Result<Vec<ChatLine>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT move_number, spectator, user_id, username, text, sent_at_ms
+        FROM game_chat WHERE game_id = ?1 ORDER BY id
+        "#,
+    )?;
+    stmt.query_map([game_id], |row| {
+        Ok(ChatLine {
+            move_number: row.get::<_, i64>(0)? as usize,
+            spectator: row.get(1)?,
+            user_id: row.get(2)?,
+            user_name: row.get(3)?,
+            text: row.get(4)?,
+            sent_at_ms: row.get::<_, i64>(5)? as u64,
+        })
+    })?
+    .collect()
+}
+
+/// 봇 계정용 스키마 생성 함수 (init_schema 에서 users 다음에 한 번)
+///
+/// users.is_bot (1이면 봇 계정) 컬럼을 붙이고, 봇 API 토큰 테이블을 만든다.
+/// 토큰은 원문 대신 해시만 저장한다.
+pub fn init_bot_tables(conn: &Connection) -> Result<()> {
+    let has_is_bot = {
+        let mut stmt = conn.prepare("PRAGMA table_info(users)")?;
+        stmt.query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>>>()?
+            .iter()
+            .any(|c| c == "is_bot")
+    };
+    if !has_is_bot {
+        conn.execute(
+            "ALTER TABLE users ADD COLUMN is_bot INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS api_tokens (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id    INTEGER NOT NULL,                 -- users.id
+            token_hash TEXT    NOT NULL UNIQUE,          -- 토큰 해시 (원문은 발급 때 한 번만 보여 준다)
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// 봇 계정인지
+pub fn is_bot_account(conn: &Connection, user_id: i64) -> Result<bool> {
+    let is_bot = conn
+        .query_row("SELECT is_bot FROM users WHERE id = ?1", [user_id], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map(|is_bot| is_bot != 0);
+    match is_bot {
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+        other => other,
+    }
+}
+
+/// 계정을 봇으로 바꾼다. 대국 기록이 있는 계정은 바꾸지 않고 false
+pub fn upgrade_to_bot(conn: &Connection, user_id: i64) -> Result<bool> {
+    let played: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM games WHERE black_id = ?1 OR white_id = ?1",
+        [user_id],
+        |row| row.get(0),
+    )?;
+    if played > 0 {
+        return Ok(false);
+    }
+    let updated = conn.execute("UPDATE users SET is_bot = 1 WHERE id = ?1", [user_id])?;
+    Ok(updated > 0)
+}
+
+/// API 토큰 저장
+pub fn insert_api_token(conn: &Connection, user_id: i64, token_hash: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO api_tokens (user_id, token_hash) VALUES (?1, ?2)",
+        params![user_id, token_hash],
+    )?;
+    Ok(())
+}
+
+/// 토큰 해시로 봇 계정 찾기. 없는 토큰이거나 봇이 아닌 계정이면 None
+pub fn bot_by_api_token(conn: &Connection, token_hash: &str) -> Result<Option<i64>> {
+    let user_id = conn.query_row(
+        r#"
+        SELECT t.user_id FROM api_tokens t
+        JOIN users u ON u.id = t.user_id
+        WHERE t.token_hash = ?1 AND u.is_bot = 1
+        "#,
+        [token_hash],
+        |row| row.get(0),
+    );
+    match user_id {
+        Ok(user_id) => Ok(Some(user_id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// API 토큰 폐기
+pub fn delete_api_token(conn: &Connection, token_hash: &str) -> Result<()> {
+    conn.execute("DELETE FROM api_tokens WHERE token_hash = ?1", [token_hash])?;
+    Ok(())
+}
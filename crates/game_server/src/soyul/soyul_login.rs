@@ -6,7 +6,7 @@ use argon2::{
 };
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
@@ -15,11 +15,15 @@ use rusqlite::{self, Connection, params};
 use serde::{Deserialize, Serialize};
 #[cfg(debug_assertions)]
 use serde_json;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::soyul::session::{
-    SessionStore, generate_session_key, get_user_id_by_session, insert_session, remove_session,
+use crate::soyul::{
+    rating::DEFAULT_RATING,
+    session::{
+        SessionStore, generate_session_key, get_user_id_by_session, insert_session, remove_session,
+    },
+    soyul_db::init_users_table,
 };
 
 //
@@ -58,16 +62,32 @@ fn verify_argon2(input: &str, hashed: &str) -> std::result::Result<bool, passwor
 pub struct UserProfile {
     pub username: Option<String>, // NULL 가능성 있으니까 Option
     pub rating: i32,
+    /// 게임 종류·판 크기별 레이팅과 전적
+    pub stats: Vec<GameTypeStats>,
     // 나중에 필드 더 추가 가능 (예: created_at, bio 등)
 }
 impl UserProfile {
-    pub fn convert_session2proto(self) -> doljabiproto::badukboard::UserInfo {
+    /// game_type·board_size 레이팅. 아직 둔 적이 없으면 기본 레이팅
+    pub fn rating_for(&self, game_type: &str, board_size: u8) -> i32 {
+        self.stats
+            .iter()
+            .find(|s| s.game_type == game_type && s.board_size == board_size as i32)
+            .map(|s| s.rating)
+            .unwrap_or(DEFAULT_RATING)
+    }
+
+    /// 대국 화면용 유저 정보 (해당 게임 종류·판 크기 레이팅)
+    pub fn convert_session2proto(
+        self,
+        game_type: &str,
+        board_size: u8,
+    ) -> doljabiproto::badukboard::UserInfo {
         doljabiproto::badukboard::UserInfo {
+            rating: self.rating_for(game_type, board_size) as u32,
             user_name: match self.username {
                 Some(name) => name,
                 None => "".to_string(),
             },
-            rating: self.rating as u32,
         }
     }
 }
@@ -87,11 +107,15 @@ pub fn get_user_profile_by_id(
         Ok(UserProfile {
             username: row.get(0)?,
             rating: row.get(1)?,
+            stats: Vec::new(),
         })
     });
 
     match result {
-        Ok(profile) => Ok(Some(profile)),
+        Ok(mut profile) => {
+            profile.stats = get_game_stats_by_id(conn, user_id)?;
+            Ok(Some(profile))
+        }
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
         Err(e) => Err(e),
     }
@@ -224,19 +248,7 @@ pub async fn signup(Json(form): Json<SignupForm>) -> (StatusCode, Json<ApiRespon
     };
 
     // users 테이블 없으면 생성
-    if let Err(e) = conn.execute(
-        "CREATE TABLE IF NOT EXISTS users (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            login_id TEXT UNIQUE NOT NULL,
-            password_hash TEXT NOT NULL,
-            username TEXT UNIQUE,
-            rating INTEGER DEFAULT 1500,
-            win INTEGER DEFAULT 0,
-            lose INTEGER DEFAULT 0,
-            draw INTEGER DEFAULT 0
-        )",
-        [],
-    ) {
+    if let Err(e) = init_users_table(&conn) {
         eprintln!("❌ 테이블 생성 실패: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -844,90 +856,142 @@ pub async fn delete_user(
     }
 }
 
-/// 게임 승리 기록: user_id의 win 컬럼을 1 증가
-pub fn record_game_win(user_id: UserID) -> rusqlite::Result<()> {
-    let conn = match Connection::open("mydb.db") {
-        Ok(conn) => conn,
-        Err(e) => {
-            eprintln!("❌ DB 오픈 실패: {}", e);
-            return Err(e);
-        }
-    };
-
+/// (user_id, game_type, board_size) 전적 행의 column 을 1 증가 (행이 없으면 만든다)
+fn increment_game_stats(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: &str,
+    board_size: u8,
+    column: &str,
+) -> rusqlite::Result<()> {
     conn.execute(
-        "UPDATE users SET win = win + 1 WHERE id = ?1",
-        params![i64::from(user_id)],
+        &format!(
+            "INSERT INTO user_game_stats (user_id, game_type, board_size, {column}, last_played_at)
+             VALUES (?1, ?2, ?3, 1, CURRENT_TIMESTAMP)
+             ON CONFLICT (user_id, game_type, board_size)
+             DO UPDATE SET {column} = {column} + 1, last_played_at = CURRENT_TIMESTAMP"
+        ),
+        params![i64::from(user_id), game_type, board_size as i64],
     )?;
+    Ok(())
+}
+
+/// 게임 승리 기록: user_game_stats 의 (user_id, game_type, board_size) win 을 1 증가
+///
+/// 레이팅 갱신과 한 트랜잭션으로 묶을 수 있도록 연결을 받는다
+pub fn record_game_win(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: &str,
+    board_size: u8,
+) -> rusqlite::Result<()> {
+    increment_game_stats(conn, user_id, game_type, board_size, "win")?;
 
     #[cfg(debug_assertions)]
-    println!("✅ 승리 기록 성공: user_id={}", user_id);
+    println!("✅ 승리 기록 성공: user_id={}, {}", user_id, game_type);
     Ok(())
 }
 
-/// 게임 패배 기록: user_id의 lose 컬럼을 1 증가
-pub fn record_game_lose(user_id: UserID) -> rusqlite::Result<()> {
-    let conn = match Connection::open("mydb.db") {
-        Ok(conn) => conn,
-        Err(e) => {
-            eprintln!("❌ DB 오픈 실패: {}", e);
-            return Err(e);
-        }
-    };
+/// 게임 패배 기록: user_game_stats 의 lose 를 1 증가
+pub fn record_game_lose(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: &str,
+    board_size: u8,
+) -> rusqlite::Result<()> {
+    increment_game_stats(conn, user_id, game_type, board_size, "lose")?;
 
-    conn.execute(
-        "UPDATE users SET lose = lose + 1 WHERE id = ?1",
-        params![i64::from(user_id)],
-    )?;
+    #[cfg(debug_assertions)]
+    println!("✅ 패배 기록 성공: user_id={}, {}", user_id, game_type);
+    Ok(())
+}
+
+/// 게임 무승부 기록: user_game_stats 의 draw 를 1 증가
+pub fn record_game_draw(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: &str,
+    board_size: u8,
+) -> rusqlite::Result<()> {
+    increment_game_stats(conn, user_id, game_type, board_size, "draw")?;
 
     #[cfg(debug_assertions)]
-    println!("✅ 패배 기록 성공: user_id={}", user_id);
+    println!("✅ 무승부 기록 성공: user_id={}, {}", user_id, game_type);
     Ok(())
 }
 
-/// 게임 무승부 기록: user_id의 draw 컬럼을 1 증가
-pub fn record_game_draw(user_id: UserID) -> rusqlite::Result<()> {
-    let conn = match Connection::open("mydb.db") {
-        Ok(conn) => conn,
-        Err(e) => {
-            eprintln!("❌ DB 오픈 실패: {}", e);
-            return Err(e);
-        }
-    };
+/// 게임 종류·판 크기별 레이팅과 전적
+#[derive(Serialize, ToSchema, Debug, Clone)]
+pub struct GameTypeStats {
+    /// "baduk" | "omok"
+    pub game_type: String,
+    pub board_size: i32,
+    pub rating: i32,
+    pub win: i32,
+    pub lose: i32,
+    pub draw: i32,
+    pub last_played_at: Option<String>,
+}
 
-    conn.execute(
-        "UPDATE users SET draw = draw + 1 WHERE id = ?1",
-        params![i64::from(user_id)],
+/// user_id 의 게임 종류·판 크기별 전적 (대국 기록이 없으면 빈 목록)
+pub fn get_game_stats_by_id(
+    conn: &Connection,
+    user_id: UserID,
+) -> rusqlite::Result<Vec<GameTypeStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT game_type, board_size, rating, win, lose, draw, last_played_at
+         FROM user_game_stats
+         WHERE user_id = ?1
+         ORDER BY game_type, board_size",
     )?;
 
-    #[cfg(debug_assertions)]
-    println!("✅ 무승부 기록 성공: user_id={}", user_id);
-    Ok(())
+    stmt.query_map([i64::from(user_id)], |row| {
+        Ok(GameTypeStats {
+            game_type: row.get(0)?,
+            board_size: row.get(1)?,
+            rating: row.get(2)?,
+            win: row.get(3)?,
+            lose: row.get(4)?,
+            draw: row.get(5)?,
+            last_played_at: row.get(6)?,
+        })
+    })?
+    .collect()
 }
 
-/// user_id로 게임 결과(win, lose, draw) 조회
+/// user_id로 게임 결과 조회: 전체 합계(win, lose, draw) + 게임 종류·판 크기별 내역
+///
+/// 합계에는 user_game_stats 이전에 users 테이블에 쌓인 예전 전적도 더한다
 pub fn get_game_result_by_id(
     conn: &Connection,
     user_id: UserID,
 ) -> rusqlite::Result<Option<GameResultInformationFrom>> {
-    let mut stmt = conn.prepare(
-        "SELECT win, lose, draw
+    let legacy = conn.query_row(
+        "SELECT COALESCE(win, 0), COALESCE(lose, 0), COALESCE(draw, 0)
          FROM users
          WHERE id = ?1",
-    )?;
-
-    let result = stmt.query_row([i64::from(user_id)], |row| {
-        Ok(GameResultInformationFrom {
-            win: row.get(0)?,
-            lose: row.get(1)?,
-            draw: row.get(2)?,
-        })
-    });
+        [i64::from(user_id)],
+        |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, i32>(2)?,
+            ))
+        },
+    );
+    let (win, lose, draw) = match legacy {
+        Ok(legacy) => legacy,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e),
+    };
 
-    match result {
-        Ok(info) => Ok(Some(info)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e),
-    }
+    let by_game_type = get_game_stats_by_id(conn, user_id)?;
+    Ok(Some(GameResultInformationFrom {
+        win: win + by_game_type.iter().map(|s| s.win).sum::<i32>(),
+        lose: lose + by_game_type.iter().map(|s| s.lose).sum::<i32>(),
+        draw: draw + by_game_type.iter().map(|s| s.draw).sum::<i32>(),
+        by_game_type,
+    }))
 }
 
 #[derive(Serialize, ToSchema, Debug)]
@@ -935,6 +999,8 @@ pub struct GameResultInformationFrom {
     pub win: i32,
     pub lose: i32,
     pub draw: i32,
+    /// 게임 종류·판 크기별 레이팅과 전적
+    pub by_game_type: Vec<GameTypeStats>,
 }
 
 #[utoipa::path(
@@ -997,6 +1063,149 @@ pub async fn get_game_result(
     }
 }
 
+//
+// 레이팅 변화 기록 (프로필 그래프)
+//
+
+/// 레이팅 기록 조회 필터(URL 쿼리 스트링)
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RatingHistoryQuery {
+    /// "baduk" | "omok". 생략 시 전체
+    pub game_type: Option<String>,
+    /// 판 크기. 생략 시 전체
+    pub board_size: Option<i32>,
+}
+
+/// 대국 한 판의 레이팅 변화
+#[derive(Serialize, ToSchema, Debug)]
+pub struct RatingPoint {
+    /// 레이팅이 바뀐 게임 (games.id). 기보 저장에 실패한 대국이면 null
+    pub game_id: Option<i64>,
+    pub rating_before: i32,
+    pub rating_after: i32,
+    pub created_at: Option<String>,
+}
+
+/// 게임 종류·판 크기 하나의 레이팅 시계열 (오래된 순)
+#[derive(Serialize, ToSchema, Debug)]
+pub struct RatingSeries {
+    pub game_type: String,
+    pub board_size: i32,
+    pub points: Vec<RatingPoint>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RatingHistoryResponse {
+    pub success: bool,
+    pub message: String,
+    pub series: Vec<RatingSeries>,
+}
+
+/// user_id 의 레이팅 기록을 게임 종류·판 크기별 시계열로 묶어 조회
+pub fn get_rating_history_by_id(
+    conn: &Connection,
+    user_id: UserID,
+    game_type: Option<&str>,
+    board_size: Option<i32>,
+) -> rusqlite::Result<Vec<RatingSeries>> {
+    let mut stmt = conn.prepare(
+        "SELECT game_type, board_size, game_id, rating_before, rating_after, created_at
+         FROM rating_history
+         WHERE user_id = ?1
+           AND (?2 IS NULL OR game_type = ?2)
+           AND (?3 IS NULL OR board_size = ?3)
+         ORDER BY game_type, board_size, id",
+    )?;
+
+    let rows = stmt.query_map(params![i64::from(user_id), game_type, board_size], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i32>(1)?,
+            RatingPoint {
+                game_id: row.get(2)?,
+                rating_before: row.get(3)?,
+                rating_after: row.get(4)?,
+                created_at: row.get(5)?,
+            },
+        ))
+    })?;
+
+    // 정렬돼 있으므로 (game_type, board_size) 가 바뀔 때마다 새 시계열
+    let mut series: Vec<RatingSeries> = Vec::new();
+    for row in rows {
+        let (game_type, board_size, point) = row?;
+        match series.last_mut() {
+            Some(last) if last.game_type == game_type && last.board_size == board_size => {
+                last.points.push(point)
+            }
+            _ => series.push(RatingSeries {
+                game_type,
+                board_size,
+                points: vec![point],
+            }),
+        }
+    }
+    Ok(series)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/user/rating_history/session/{session_key}",
+    tag = "user",
+    params(
+        ("session_key" = String, Path, description = "세션 키"),
+        RatingHistoryQuery,
+    ),
+    responses(
+        (status = 200, description = "레이팅 기록 조회 성공", body = RatingHistoryResponse),
+        (status = 400, description = "세션 키가 올바르지 않음"),
+        (status = 500, description = "서버 내부 오류"),
+    )
+)]
+pub async fn get_rating_history(
+    State(session_store): State<SessionStore>,
+    Path(session_key): Path<String>,
+    Query(q): Query<RatingHistoryQuery>,
+) -> impl IntoResponse {
+    let user_id = match get_user_id_by_session(&session_store, &session_key).await {
+        Some(id) => id,
+        None => return StatusCode::BAD_REQUEST.into_response(),
+    };
+
+    let conn = match Connection::open("mydb.db") {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("⚠️ 레이팅 기록 조회(DB 오픈 실패): {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    match get_rating_history_by_id(&conn, user_id, q.game_type.as_deref(), q.board_size) {
+        Ok(series) => {
+            #[cfg(debug_assertions)]
+            println!(
+                "✅ 레이팅 기록 조회 성공: user_id={}, 시계열 {}개",
+                user_id,
+                series.len()
+            );
+            (
+                StatusCode::OK,
+                Json(RatingHistoryResponse {
+                    success: true,
+                    message: "레이팅 기록 조회 성공".into(),
+                    series,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            eprintln!("⚠️ 레이팅 기록 조회 중 에러: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 pub fn login_router() -> OpenApiRouter<SessionStore> {
     OpenApiRouter::new()
         .routes(routes!(signup))
@@ -1007,4 +1216,93 @@ pub fn login_router() -> OpenApiRouter<SessionStore> {
         .routes(routes!(update_password))
         .routes(routes!(delete_user))
         .routes(routes!(get_game_result))
+        .routes(routes!(get_rating_history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::soyul::{
+        rating::apply_rated_result,
+        soyul_db::{init_schema, save_finished_game, update_game_ratings},
+    };
+    use game_core::baduk_board::Color;
+
+    #[test]
+    fn rating_history_test() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash) VALUES (1, 'a', ''), (2, 'b', '');",
+        )
+        .unwrap();
+
+        let first = apply_rated_result(&mut conn, UserID(1), UserID(2), Color::Black, "omok", 15)
+            .unwrap()
+            .unwrap();
+        let game_id = save_finished_game(&conn, 1, 2, "omok", 15, "B+", "(;)").unwrap();
+        update_game_ratings(&conn, game_id, &first).unwrap();
+        apply_rated_result(&mut conn, UserID(1), UserID(2), Color::White, "baduk", 19).unwrap();
+
+        let series = get_rating_history_by_id(&conn, UserID(1), None, None).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].game_type, "baduk");
+        // 기보 저장 전에 끝난 판은 game_id 없이 남는다
+        assert_eq!(series[0].points[0].game_id, None);
+        assert_eq!(series[1].game_type, "omok");
+        assert_eq!(series[1].points[0].game_id, Some(game_id));
+        assert_eq!(series[1].points[0].rating_before, 1500);
+        assert_eq!(series[1].points[0].rating_after, 1520);
+
+        let omok = get_rating_history_by_id(&conn, UserID(2), Some("omok"), Some(15)).unwrap();
+        assert_eq!(omok.len(), 1);
+        assert_eq!(omok[0].points[0].rating_after, 1480);
+    }
+
+    #[test]
+    fn increment_game_stats_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+
+        // 첫 기록은 행을 새로 만든다
+        record_game_win(&conn, UserID(1), "baduk", 19).unwrap();
+        // 같은 (유저, 게임 종류, 판 크기)는 그 행의 칸만 1 올린다
+        record_game_win(&conn, UserID(1), "baduk", 19).unwrap();
+        record_game_lose(&conn, UserID(1), "baduk", 19).unwrap();
+        // 판 크기가 다르면 다른 행
+        record_game_draw(&conn, UserID(1), "baduk", 13).unwrap();
+
+        let stats = get_game_stats_by_id(&conn, UserID(1)).unwrap();
+        let rows: Vec<_> = stats
+            .iter()
+            .map(|s| (s.board_size, s.rating, s.win, s.lose, s.draw))
+            .collect();
+        assert_eq!(rows, [(13, 1500, 0, 0, 1), (19, 1500, 2, 1, 0)]);
+        assert!(stats.iter().all(|s| s.last_played_at.is_some()));
+    }
+
+    #[test]
+    fn game_result_totals_test() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        // user_game_stats 이전에 users 에 쌓인 예전 전적
+        conn.execute_batch(
+            "INSERT INTO users (id, login_id, password_hash, win, lose, draw)
+             VALUES (1, 'old', '', 3, 2, 1), (2, 'new', '', 0, 0, 0);",
+        )
+        .unwrap();
+        record_game_win(&conn, UserID(1), "omok", 15).unwrap();
+        record_game_lose(&conn, UserID(1), "baduk", 19).unwrap();
+        record_game_draw(&conn, UserID(1), "baduk", 19).unwrap();
+
+        let result = get_game_result_by_id(&conn, UserID(1)).unwrap().unwrap();
+        assert_eq!((result.win, result.lose, result.draw), (4, 3, 2));
+        assert_eq!(result.by_game_type.len(), 2);
+
+        // 대국 기록이 없으면 예전 전적 그대로, 없는 유저는 None
+        let result = get_game_result_by_id(&conn, UserID(2)).unwrap().unwrap();
+        assert_eq!((result.win, result.lose, result.draw), (0, 0, 0));
+        assert!(result.by_game_type.is_empty());
+        assert!(get_game_result_by_id(&conn, UserID(3)).unwrap().is_none());
+    }
 }
@@ -14,5 +14,5 @@ import type {
 } from '../../model';
 
 
-export const getCreateRoomRequestResponseMock = (overrideResponse: Partial<Extract<CreateRoomResponseForm, object>> = {}): CreateRoomResponseForm => ({enter_code: faker.number.int({min: 0}), ...overrideResponse})
+export const getCreateRoomRequestResponseMock = (overrideResponse: Partial<Extract<CreateRoomResponseForm, object>> = {}): CreateRoomResponseForm => ({enter_code: faker.string.alpha({length: {min: 10, max: 20}}), invite_token: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), ...overrideResponse})
 
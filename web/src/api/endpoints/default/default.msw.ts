@@ -23,7 +23,7 @@ import type {
 } from '../../model';
 
 
-export const getCreateRoomRequestResponseMock = (overrideResponse: Partial< CreateRoomResponseForm > = {}): CreateRoomResponseForm => ({enter_code: faker.number.int({min: 0, max: undefined}), ...overrideResponse})
+export const getCreateRoomRequestResponseMock = (overrideResponse: Partial< CreateRoomResponseForm > = {}): CreateRoomResponseForm => ({enter_code: faker.string.alpha({length: {min: 10, max: 20}}), invite_token: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), ...overrideResponse})
 
 
 export const getCreateRoomRequestMockHandler = (overrideResponse?: CreateRoomResponseForm | ((info: Parameters<Parameters<typeof http.post>[1]>[0]) => Promise<CreateRoomResponseForm> | CreateRoomResponseForm), options?: RequestHandlerOptions) => {
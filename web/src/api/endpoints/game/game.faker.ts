@@ -17,5 +17,5 @@ import type {
 
 export const getGetGameSgfResponseMock = (overrideResponse: Partial<Extract<GameSgfResponse, object>> = {}): GameSgfResponse => ({sgf: faker.string.alpha({length: {min: 10, max: 20}}), ...overrideResponse})
 
-export const getGetMyGamesResponseMock = (overrideResponse: Partial<Extract<GameListResponse, object>> = {}): GameListResponse => ({games: Array.from({ length: faker.number.int({min: 1, max: 10}) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), created_at: faker.string.alpha({length: {min: 10, max: 20}}), game_type: faker.string.alpha({length: {min: 10, max: 20}}), id: faker.number.int(), my_color: faker.string.alpha({length: {min: 10, max: 20}}), opponent_id: faker.number.int(), result: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined])})), message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
+export const getGetMyGamesResponseMock = (overrideResponse: Partial<Extract<GameListResponse, object>> = {}): GameListResponse => ({games: Array.from({ length: faker.number.int({min: 1, max: 10}) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), created_at: faker.string.alpha({length: {min: 10, max: 20}}), game_type: faker.string.alpha({length: {min: 10, max: 20}}), id: faker.number.int(), imported: faker.datatype.boolean(), my_color: faker.string.alpha({length: {min: 10, max: 20}}), opponent_id: faker.number.int(), result: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined])})), message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
 
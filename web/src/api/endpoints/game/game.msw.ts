@@ -26,7 +26,7 @@ import type {
 
 export const getGetGameSgfResponseMock = (overrideResponse: Partial< GameSgfResponse > = {}): GameSgfResponse => ({sgf: faker.string.alpha({length: {min: 10, max: 20}}), ...overrideResponse})
 
-export const getGetMyGamesResponseMock = (overrideResponse: Partial< GameListResponse > = {}): GameListResponse => ({games: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int({min: undefined, max: undefined}), created_at: faker.string.alpha({length: {min: 10, max: 20}}), game_type: faker.string.alpha({length: {min: 10, max: 20}}), id: faker.number.int({min: undefined, max: undefined}), my_color: faker.string.alpha({length: {min: 10, max: 20}}), opponent_id: faker.number.int({min: undefined, max: undefined}), result: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}),null,]), undefined])})), message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
+export const getGetMyGamesResponseMock = (overrideResponse: Partial< GameListResponse > = {}): GameListResponse => ({games: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int({min: undefined, max: undefined}), created_at: faker.string.alpha({length: {min: 10, max: 20}}), game_type: faker.string.alpha({length: {min: 10, max: 20}}), id: faker.number.int({min: undefined, max: undefined}), imported: faker.datatype.boolean(), my_color: faker.string.alpha({length: {min: 10, max: 20}}), opponent_id: faker.number.int({min: undefined, max: undefined}), result: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}),null,]), undefined])})), message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
 
 
 export const getGetGameSgfMockHandler = (overrideResponse?: GameSgfResponse | ((info: Parameters<Parameters<typeof http.get>[1]>[0]) => Promise<GameSgfResponse> | GameSgfResponse), options?: RequestHandlerOptions) => {
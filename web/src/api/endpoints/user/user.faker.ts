@@ -19,9 +19,9 @@ import type {
 
 export const getDeleteUserResponseMock = (overrideResponse: Partial<Extract<ApiResponse, object>> = {}): ApiResponse => ({message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
 
-export const getGetGameResultResponseMock = (overrideResponse: Partial<Extract<GameResultInformationFrom, object>> = {}): GameResultInformationFrom => ({draw: faker.number.int(), lose: faker.number.int(), win: faker.number.int(), ...overrideResponse})
+export const getGetGameResultResponseMock = (overrideResponse: Partial<Extract<GameResultInformationFrom, object>> = {}): GameResultInformationFrom => ({by_game_type: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), draw: faker.number.int(), game_type: faker.string.alpha({length: {min: 10, max: 20}}), last_played_at: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), lose: faker.number.int(), rating: faker.number.int(), win: faker.number.int()})), draw: faker.number.int(), lose: faker.number.int(), win: faker.number.int(), ...overrideResponse})
 
-export const getGetUserProfileHandlerResponseUserProfileMock = (overrideResponse: Partial<UserProfile> = {}): UserProfile => ({...{rating: faker.number.int(), username: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined])}, ...overrideResponse});
+export const getGetUserProfileHandlerResponseUserProfileMock = (overrideResponse: Partial<UserProfile> = {}): UserProfile => ({...{rating: faker.number.int(), stats: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), draw: faker.number.int(), game_type: faker.string.alpha({length: {min: 10, max: 20}}), last_played_at: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), lose: faker.number.int(), rating: faker.number.int(), win: faker.number.int()})), username: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined])}, ...overrideResponse});
 
 export const getGetUserProfileHandlerResponseMock = (overrideResponse: Partial<Extract<UserProfileResponse, object>> = {}): UserProfileResponse => ({message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), user: faker.helpers.arrayElement([faker.helpers.arrayElement([null,{...getGetUserProfileHandlerResponseUserProfileMock()},]), undefined]), ...overrideResponse})
 
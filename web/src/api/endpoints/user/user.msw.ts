@@ -28,9 +28,9 @@ import type {
 
 export const getDeleteUserResponseMock = (overrideResponse: Partial< ApiResponse > = {}): ApiResponse => ({message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), ...overrideResponse})
 
-export const getGetGameResultResponseMock = (overrideResponse: Partial< GameResultInformationFrom > = {}): GameResultInformationFrom => ({draw: faker.number.int({min: undefined, max: undefined}), lose: faker.number.int({min: undefined, max: undefined}), win: faker.number.int({min: undefined, max: undefined}), ...overrideResponse})
+export const getGetGameResultResponseMock = (overrideResponse: Partial< GameResultInformationFrom > = {}): GameResultInformationFrom => ({by_game_type: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), draw: faker.number.int(), game_type: faker.string.alpha({length: {min: 10, max: 20}}), last_played_at: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), lose: faker.number.int(), rating: faker.number.int(), win: faker.number.int()})), draw: faker.number.int({min: undefined, max: undefined}), lose: faker.number.int({min: undefined, max: undefined}), win: faker.number.int({min: undefined, max: undefined}), ...overrideResponse})
 
-export const getGetUserProfileHandlerResponseUserProfileMock = (overrideResponse: Partial<UserProfile> = {}): UserProfile => ({...{rating: faker.number.int({min: undefined, max: undefined}), username: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}),null,]), undefined])}, ...overrideResponse});
+export const getGetUserProfileHandlerResponseUserProfileMock = (overrideResponse: Partial<UserProfile> = {}): UserProfile => ({...{rating: faker.number.int({min: undefined, max: undefined}), stats: Array.from({ length: faker.number.int({ min: 1, max: 10 }) }, (_, i) => i + 1).map(() => ({board_size: faker.number.int(), draw: faker.number.int(), game_type: faker.string.alpha({length: {min: 10, max: 20}}), last_played_at: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}), null]), undefined]), lose: faker.number.int(), rating: faker.number.int(), win: faker.number.int()})), username: faker.helpers.arrayElement([faker.helpers.arrayElement([faker.string.alpha({length: {min: 10, max: 20}}),null,]), undefined])}, ...overrideResponse});
 
 export const getGetUserProfileHandlerResponseMock = (overrideResponse: Partial< UserProfileResponse > = {}): UserProfileResponse => ({message: faker.string.alpha({length: {min: 10, max: 20}}), success: faker.datatype.boolean(), user: faker.helpers.arrayElement([faker.helpers.arrayElement([null,{...getGetUserProfileHandlerResponseUserProfileMock()},]), undefined]), ...overrideResponse})
 
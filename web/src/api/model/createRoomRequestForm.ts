@@ -5,7 +5,7 @@
  * doljabi project REST API를 정의한 문서입니다.
  * OpenAPI spec version: 0.2.4
  */
-import type { CreateRoomRequestFormOneOf } from './createRoomRequestFormOneOf';
-import type { CreateRoomRequestFormOneOfThree } from './createRoomRequestFormOneOfThree';
+import type { CreateRoomRequestFormAllOf } from './createRoomRequestFormAllOf';
+import type { RoomGameForm } from './roomGameForm';
 
-export type CreateRoomRequestForm = CreateRoomRequestFormOneOf | CreateRoomRequestFormOneOfThree;
+export type CreateRoomRequestForm = RoomGameForm & CreateRoomRequestFormAllOf;
@@ -0,0 +1,42 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+import type { CreateRoomRequestFormAllOfDisconnectGraceSecs } from './createRoomRequestFormAllOfDisconnectGraceSecs';
+import type { CreateRoomRequestFormAllOfMaxSpectators } from './createRoomRequestFormAllOfMaxSpectators';
+import type { CreateRoomRequestFormAllOfPassword } from './createRoomRequestFormAllOfPassword';
+import type { CreateRoomRequestFormAllOfRated } from './createRoomRequestFormAllOfRated';
+
+export type CreateRoomRequestFormAllOf = {
+  /**
+   * 대국 중 접속이 끊긴 플레이어의 자리를 유지하는 시간(초, 10~600). 생략 시 60초.
+지나면 상대가 승리를 요청할 수 있고, 양쪽 다 끊겨 있으면 대국을 중단한다
+   * @minimum 0
+   */
+  disconnect_grace_secs?: CreateRoomRequestFormAllOfDisconnectGraceSecs;
+  /**
+   * 랙 보정. 켜면 착수한 플레이어에게 측정한 네트워크 지연(편도, 최대 1초)만큼 메인 시간을 돌려준다.
+지연은 클라이언트가 늦게 답해 부풀릴 수 있으므로 친선 대국에서만 켤 수 있다
+   */
+  lag_compensation?: boolean;
+  /**
+   * 최대 관전자 수. 생략 시 제한 없음, 0 이면 관전 불가
+   * @minimum 0
+   */
+  max_spectators?: CreateRoomRequestFormAllOfMaxSpectators;
+  /** 비공개 방 비밀번호. 지정하면 private 가 아니어도 비공개 방이 된다 */
+  password?: CreateRoomRequestFormAllOfPassword;
+  /** 비공개 방 여부. 비공개 방은 로비에 보이지 않고, 초대 토큰이나 비밀번호가 있어야 입장할 수 있다 */
+  private?: boolean;
+  /** 레이팅 대국 여부. 생략 시 시간 설정이 레이팅 기준에 맞으면 레이팅 대국,
+아니면 친선 대국(기보만 저장, 레이팅·전적 미반영). 랙 보정을 켰으면 친선 대국 */
+  rated?: CreateRoomRequestFormAllOfRated;
+  /**
+   * 관전 딜레이(수). 레이팅 대국에서만 쓸 수 있고, 관전자는 이만큼 전 국면을 본다
+   * @minimum 0
+   */
+  spectator_delay?: number;
+};
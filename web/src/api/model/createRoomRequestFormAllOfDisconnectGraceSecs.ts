@@ -0,0 +1,14 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+
+/**
+ * 대국 중 접속이 끊긴 플레이어의 자리를 유지하는 시간(초, 10~600). 생략 시 60초.
+지나면 상대가 승리를 요청할 수 있고, 양쪽 다 끊겨 있으면 대국을 중단한다
+ * @minimum 0
+ */
+export type CreateRoomRequestFormAllOfDisconnectGraceSecs = number | null;
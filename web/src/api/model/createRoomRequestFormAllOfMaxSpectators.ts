@@ -0,0 +1,13 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+
+/**
+ * 최대 관전자 수. 생략 시 제한 없음, 0 이면 관전 불가
+ * @minimum 0
+ */
+export type CreateRoomRequestFormAllOfMaxSpectators = number | null;
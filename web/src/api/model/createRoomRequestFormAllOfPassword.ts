@@ -0,0 +1,12 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+
+/**
+ * 비공개 방 비밀번호. 지정하면 private 가 아니어도 비공개 방이 된다
+ */
+export type CreateRoomRequestFormAllOfPassword = string | null;
@@ -0,0 +1,13 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+
+/**
+ * 레이팅 대국 여부. 생략 시 시간 설정이 레이팅 기준에 맞으면 레이팅 대국,
+아니면 친선 대국(기보만 저장, 레이팅·전적 미반영)
+ */
+export type CreateRoomRequestFormAllOfRated = boolean | null;
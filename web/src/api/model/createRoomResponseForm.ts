@@ -5,8 +5,10 @@
  * doljabi project REST API를 정의한 문서입니다.
  * OpenAPI spec version: 0.2.4
  */
+import type { CreateRoomResponseFormInviteToken } from './createRoomResponseFormInviteToken';
 
 export interface CreateRoomResponseForm {
-  /** @minimum 0 */
-  enter_code: number;
+  enter_code: string;
+  /** 비공개 방 초대 토큰. 공개 방이면 null */
+  invite_token?: CreateRoomResponseFormInviteToken;
 }
@@ -0,0 +1,12 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+
+/**
+ * 비공개 방 초대 토큰. 공개 방이면 null
+ */
+export type CreateRoomResponseFormInviteToken = string | null;
@@ -5,8 +5,11 @@
  * doljabi project REST API를 정의한 문서입니다.
  * OpenAPI spec version: 0.2.4
  */
+import type { GameTypeStats } from './gameTypeStats';
 
 export interface GameResultInformationFrom {
+  /** 게임 종류·판 크기별 레이팅과 전적 */
+  by_game_type: GameTypeStats[];
   draw: number;
   lose: number;
   win: number;
@@ -13,6 +13,8 @@ export interface GameSummary {
   /** "baduk" | "omok" */
   game_type: string;
   id: number;
+  /** 업로드로 가져온 기보이면 true */
+  imported: boolean;
   /** 요청자가 둔 색 ("black" | "white") */
   my_color: string;
   /** 요청자 입장에서 상대 플레이어의 user_id */
@@ -0,0 +1,22 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+import type { GameTypeStatsLastPlayedAt } from './gameTypeStatsLastPlayedAt';
+
+/**
+ * 게임 종류·판 크기별 레이팅과 전적
+ */
+export interface GameTypeStats {
+  board_size: number;
+  draw: number;
+  /** "baduk" | "omok" */
+  game_type: string;
+  last_played_at?: GameTypeStatsLastPlayedAt;
+  lose: number;
+  rating: number;
+  win: number;
+}
@@ -9,20 +9,29 @@
 export * from './apiResponse';
 export * from './badukBoardGameConfig';
 export * from './createRoomRequestForm';
-export * from './createRoomRequestFormOneOf';
-export * from './createRoomRequestFormOneOfGameType';
-export * from './createRoomRequestFormOneOfThree';
-export * from './createRoomRequestFormOneOfThreeGameType';
+export * from './createRoomRequestFormAllOf';
+export * from './createRoomRequestFormAllOfDisconnectGraceSecs';
+export * from './createRoomRequestFormAllOfMaxSpectators';
+export * from './createRoomRequestFormAllOfPassword';
+export * from './createRoomRequestFormAllOfRated';
 export * from './createRoomResponseForm';
+export * from './createRoomResponseFormInviteToken';
 export * from './gameListResponse';
 export * from './gameResultInformationFrom';
 export * from './gameSgfResponse';
 export * from './gameSummary';
 export * from './gameSummaryResult';
+export * from './gameTypeStats';
+export * from './gameTypeStatsLastPlayedAt';
 export * from './getMyGamesParams';
 export * from './loginForm';
 export * from './loginResponse';
 export * from './loginResponseSessionKey';
+export * from './roomGameForm';
+export * from './roomGameFormOneOf';
+export * from './roomGameFormOneOfGameType';
+export * from './roomGameFormOneOfThree';
+export * from './roomGameFormOneOfThreeGameType';
 export * from './sessionCheckForm';
 export * from './signupForm';
 export * from './updatePasswordForm';
@@ -0,0 +1,11 @@
+/**
+ * Generated by orval v7.21.0 🍺
+ * Do not edit manually.
+ * doljabi
+ * doljabi project REST API를 정의한 문서입니다.
+ * OpenAPI spec version: 0.2.4
+ */
+import type { RoomGameFormOneOf } from './roomGameFormOneOf';
+import type { RoomGameFormOneOfThree } from './roomGameFormOneOfThree';
+
+export type RoomGameForm = RoomGameFormOneOf | RoomGameFormOneOfThree;
@@ -6,9 +6,9 @@
  * OpenAPI spec version: 0.2.4
  */
 import type { BadukBoardGameConfig } from './badukBoardGameConfig';
-import type { CreateRoomRequestFormOneOfGameType } from './createRoomRequestFormOneOfGameType';
+import type { RoomGameFormOneOfGameType } from './roomGameFormOneOfGameType';
 
-export type CreateRoomRequestFormOneOf = {
+export type RoomGameFormOneOf = {
   game_config: BadukBoardGameConfig;
-  game_type: CreateRoomRequestFormOneOfGameType;
+  game_type: RoomGameFormOneOfGameType;
 };
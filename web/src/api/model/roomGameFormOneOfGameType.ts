@@ -6,10 +6,10 @@
  * OpenAPI spec version: 0.2.4
  */
 
-export type CreateRoomRequestFormOneOfGameType = typeof CreateRoomRequestFormOneOfGameType[keyof typeof CreateRoomRequestFormOneOfGameType];
+export type RoomGameFormOneOfGameType = typeof RoomGameFormOneOfGameType[keyof typeof RoomGameFormOneOfGameType];
 
 
 // eslint-disable-next-line @typescript-eslint/no-redeclare
-export const CreateRoomRequestFormOneOfGameType = {
+export const RoomGameFormOneOfGameType = {
   baduk: 'baduk',
 } as const;
@@ -6,9 +6,9 @@
  * OpenAPI spec version: 0.2.4
  */
 import type { BadukBoardGameConfig } from './badukBoardGameConfig';
-import type { CreateRoomRequestFormOneOfThreeGameType } from './createRoomRequestFormOneOfThreeGameType';
+import type { RoomGameFormOneOfThreeGameType } from './roomGameFormOneOfThreeGameType';
 
-export type CreateRoomRequestFormOneOfThree = {
+export type RoomGameFormOneOfThree = {
   game_config: BadukBoardGameConfig;
-  game_type: CreateRoomRequestFormOneOfThreeGameType;
+  game_type: RoomGameFormOneOfThreeGameType;
 };
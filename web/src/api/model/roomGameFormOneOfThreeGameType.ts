@@ -6,10 +6,10 @@
  * OpenAPI spec version: 0.2.4
  */
 
-export type CreateRoomRequestFormOneOfThreeGameType = typeof CreateRoomRequestFormOneOfThreeGameType[keyof typeof CreateRoomRequestFormOneOfThreeGameType];
+export type RoomGameFormOneOfThreeGameType = typeof RoomGameFormOneOfThreeGameType[keyof typeof RoomGameFormOneOfThreeGameType];
 
 
 // eslint-disable-next-line @typescript-eslint/no-redeclare
-export const CreateRoomRequestFormOneOfThreeGameType = {
+export const RoomGameFormOneOfThreeGameType = {
   omok: 'omok',
 } as const;
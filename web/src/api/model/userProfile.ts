@@ -5,9 +5,12 @@
  * doljabi project REST API를 정의한 문서입니다.
  * OpenAPI spec version: 0.2.4
  */
+import type { GameTypeStats } from './gameTypeStats';
 import type { UserProfileUsername } from './userProfileUsername';
 
 export interface UserProfile {
   rating: number;
+  /** 게임 종류·판 크기별 레이팅과 전적 */
+  stats: GameTypeStats[];
   username?: UserProfileUsername;
 }
@@ -68,7 +68,7 @@ export const GameLobby = ({ onNavigate, gameType = 'go', mode = 'multi', current
     const sessionKey = SessionManager.getSessionKey();
     if (!sessionKey) { onNavigate && onNavigate('login'); return; }
     if (!joinCode) { setJoinError('방 코드를 입력해주세요.'); return; }
-    onNavigate && onNavigate('game_waiting', selectedGameType, Number(joinCode));
+    onNavigate && onNavigate('game_waiting', selectedGameType, joinCode.trim().toUpperCase());
   };
 
   const handleCreateRoom = () => {
@@ -271,7 +271,8 @@ export const GameLobby = ({ onNavigate, gameType = 'go', mode = 'multi', current
               <div className="relative">
                 <input
                   placeholder="방 코드 입력"
-                  type="number"
+                  type="text"
+                  maxLength={6}
                   value={joinCode}
                   onChange={(e) => { setJoinCode(e.target.value); setJoinError(''); }}
                   onKeyDown={(e) => e.key === 'Enter' && handleJoinRoom()}